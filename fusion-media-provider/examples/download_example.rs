@@ -1,7 +1,7 @@
 use dotenvy::dotenv;
 use fusion_media_provider::{
     BatchDownloadProgress, DownloadConfig, DownloadProgress, DownloadState, ImageQuality,
-    MediaDownloader, MediaType, PixabayProvider, SearchParams, VideoQuality,
+    MediaDownloader, MediaType, PixabayProvider, RetryPolicy, SearchParams, VideoQuality,
 };
 use std::env;
 use std::sync::Arc;
@@ -13,9 +13,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Get API keys from environment
     let pixabay_key = env::var("PIXABAY_API_KEY").expect("PIXABAY_API_KEY must be set");
 
-    // Optional: Pexels support
-    let pexels_key = env::var("PEXELS_API_KEY").ok();
-
     // Create downloader with configuration and progress callback
     let config = DownloadConfig {
         image_quality: ImageQuality::Large,
@@ -62,18 +59,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         })),
+        manifest_path: None,
+        write_sidecars: false,
+        preflight: false,
+        require_attribution: false,
+        circuit_breaker: None,
+        file_mode: None,
+        max_concurrent_searches: None,
+        transport_retries: RetryPolicy::new(3, std::time::Duration::from_millis(200)),
+        status_retries: RetryPolicy::new(2, std::time::Duration::from_secs(2)),
+        retry_budget: 10,
+        provider_quality: Default::default(),
+        min_free_bytes: Some(100 * 1024 * 1024),
+        progress_interval: std::time::Duration::from_millis(100),
+        pool_max_idle_per_host: 10,
+        pool_idle_timeout: Some(std::time::Duration::from_secs(90)),
+        tcp_keepalive: Some(std::time::Duration::from_secs(60)),
+        url_rewriter: None,
+        prefetch_thumbnails: false,
     };
 
-    let mut downloader = MediaDownloader::new()
+    let downloader = MediaDownloader::new()
         .with_config(config)
         .add_provider(Arc::new(PixabayProvider::new(pixabay_key)));
 
-    // Add Pexels if available
+    // Optional: add Pexels if the feature is enabled and a key is available
     #[cfg(feature = "pexels")]
-    if let Some(key) = pexels_key {
-        downloader =
-            downloader.add_provider(Arc::new(fusion_media_provider::PexelsProvider::new(key)));
-    }
+    let downloader = match env::var("PEXELS_API_KEY") {
+        Ok(key) => {
+            downloader.add_provider(Arc::new(fusion_media_provider::PexelsProvider::new(key)))
+        }
+        Err(_) => downloader,
+    };
 
     println!("=== Media Downloader Demo ===");
     println!("Active providers: {}", downloader.providers().len());
@@ -0,0 +1,164 @@
+/*!
+熔断器模块 - 在提供商连续失败后暂时跳过它，冷却结束后再试探性地恢复。
+*/
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 半开状态下一次试探请求独占的最短时长
+const MIN_PROBE_LEASE: Duration = Duration::from_secs(10);
+
+/// 熔断器阈值配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// 在 `window` 内连续失败多少次后熔断
+    pub failure_threshold: u32,
+    /// 统计连续失败的时间窗口，超出窗口后重新计数
+    pub window: Duration,
+    /// 熔断后跳过该提供商的时长，之后进入半开状态允许一次试探请求
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// 单个提供商的熔断状态
+#[derive(Debug, Default)]
+struct ProviderCircuit {
+    consecutive_failures: u32,
+    first_failure: Option<Instant>,
+    opened_at: Option<Instant>,
+    /// 半开状态下放行试探请求的时间，试探结果记录前不再放行其他请求
+    ///
+    /// 试探请求可能因调用方提前返回而没有记录结果，超过 `cooldown`（至少
+    /// [`MIN_PROBE_LEASE`]）后允许新的试探
+    probe_started: Option<Instant>,
+}
+
+/// 按提供商名称记录熔断状态
+#[derive(Debug, Default)]
+pub(crate) struct CircuitBreaker {
+    circuits: Mutex<HashMap<String, ProviderCircuit>>,
+}
+
+impl CircuitBreaker {
+    /// 是否允许向该提供商发送请求
+    ///
+    /// 熔断且仍在冷却期内时返回 `false`；冷却结束后进入半开状态，只有一个调用方
+    /// 得到 `true` 并发送试探请求，其结果决定是恢复还是重新熔断
+    pub(crate) fn allow(&self, provider: &str, config: &CircuitBreakerConfig) -> bool {
+        let mut circuits = self.circuits.lock().unwrap();
+        let Some(circuit) = circuits.get_mut(provider) else {
+            return true;
+        };
+        let Some(opened_at) = circuit.opened_at else {
+            return true;
+        };
+        if opened_at.elapsed() < config.cooldown {
+            return false;
+        }
+        if circuit
+            .probe_started
+            .is_some_and(|started| started.elapsed() < config.cooldown.max(MIN_PROBE_LEASE))
+        {
+            return false;
+        }
+        circuit.probe_started = Some(Instant::now());
+        true
+    }
+
+    /// 记录一次成功，重置该提供商的熔断状态
+    pub(crate) fn record_success(&self, provider: &str) {
+        self.circuits.lock().unwrap().remove(provider);
+    }
+
+    /// 记录一次失败，达到阈值（或半开试探失败）时熔断
+    pub(crate) fn record_failure(&self, provider: &str, config: &CircuitBreakerConfig) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(provider.to_string()).or_default();
+        let now = Instant::now();
+
+        // 半开状态下的试探失败：立即重新熔断
+        if circuit.opened_at.is_some() {
+            circuit.opened_at = Some(now);
+            circuit.probe_started = None;
+            return;
+        }
+
+        match circuit.first_failure {
+            Some(first) if now.duration_since(first) <= config.window => {
+                circuit.consecutive_failures += 1;
+            }
+            _ => {
+                circuit.first_failure = Some(now);
+                circuit.consecutive_failures = 1;
+            }
+        }
+
+        if circuit.consecutive_failures >= config.failure_threshold {
+            circuit.opened_at = Some(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_half_open_after_cooldown() {
+        let breaker = CircuitBreaker::default();
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            window: Duration::from_secs(60),
+            cooldown: Duration::ZERO,
+        };
+
+        breaker.record_failure("Pixabay", &config);
+        assert!(breaker.allow("Pixabay", &config));
+        breaker.record_failure("Pixabay", &config);
+
+        // 冷却时间为 0，熔断后立即进入半开状态
+        assert!(breaker.allow("Pixabay", &config));
+        let strict = CircuitBreakerConfig {
+            cooldown: Duration::from_secs(60),
+            ..config
+        };
+        assert!(!breaker.allow("Pixabay", &strict));
+
+        breaker.record_success("Pixabay");
+        assert!(breaker.allow("Pixabay", &strict));
+    }
+
+    #[test]
+    fn test_half_open_allows_single_probe() {
+        let breaker = CircuitBreaker::default();
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            window: Duration::from_secs(60),
+            cooldown: Duration::ZERO,
+        };
+
+        breaker.record_failure("Pixabay", &config);
+        assert!(breaker.allow("Pixabay", &config));
+        // 试探结果记录前，其他调用方不会被放行
+        assert!(!breaker.allow("Pixabay", &config));
+        assert!(!breaker.allow("Pixabay", &config));
+
+        // 试探失败后重新熔断，冷却结束后再放行一次试探
+        breaker.record_failure("Pixabay", &config);
+        assert!(breaker.allow("Pixabay", &config));
+        assert!(!breaker.allow("Pixabay", &config));
+
+        breaker.record_success("Pixabay");
+        assert!(breaker.allow("Pixabay", &config));
+        assert!(breaker.allow("Pixabay", &config));
+    }
+}
@@ -1,21 +1,78 @@
 use crate::error::{MediaError, Result};
 use crate::media_provider::MediaProvider;
+use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
 
 #[cfg(feature = "pexels")]
 use crate::PexelsProvider;
 use crate::PixabayProvider;
 
-/// 根据 provider 名称创建对应的 MediaProvider 实例
+/// 支持的提供商种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProviderKind {
+    Pexels,
+    Pixabay,
+}
+
+impl ProviderKind {
+    /// 该提供商是否已编译进当前构建
+    ///
+    /// Pexels 需要启用 `pexels` 特性（默认启用），Pixabay 始终可用
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            ProviderKind::Pexels => cfg!(feature = "pexels"),
+            ProviderKind::Pixabay => true,
+        }
+    }
+
+    /// 该提供商内容所适用的许可证名称
+    pub fn license(&self) -> &'static str {
+        match self {
+            ProviderKind::Pexels => "Pexels License",
+            ProviderKind::Pixabay => "Pixabay Content License",
+        }
+    }
+
+    /// 许可证说明页面的 URL
+    pub fn license_url(&self) -> &'static str {
+        match self {
+            ProviderKind::Pexels => "https://www.pexels.com/license/",
+            ProviderKind::Pixabay => "https://pixabay.com/service/license-summary/",
+        }
+    }
+}
+
+impl FromStr for ProviderKind {
+    type Err = MediaError;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "pexels" => Ok(ProviderKind::Pexels),
+            "pixabay" => Ok(ProviderKind::Pixabay),
+            _ => Err(MediaError::UnknownProvider(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ProviderKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProviderKind::Pexels => write!(f, "pexels"),
+            ProviderKind::Pixabay => write!(f, "pixabay"),
+        }
+    }
+}
+
+/// 根据提供商种类创建对应的 MediaProvider 实例
 pub fn create_provider(
-    provider_name: &str,
+    kind: ProviderKind,
     api_key: &str,
 ) -> Result<Arc<dyn MediaProvider + Send + Sync>> {
     if api_key.is_empty() {
         return Err(MediaError::ApiKeyIsEmpty);
     }
-    match provider_name.to_lowercase().as_str() {
-        "pexels" => {
+    match kind {
+        ProviderKind::Pexels => {
             #[cfg(feature = "pexels")]
             {
                 let provider = PexelsProvider::new(api_key.to_string());
@@ -26,10 +83,70 @@ pub fn create_provider(
                 "Pexels feature is not enabled".to_string(),
             ))
         }
-        "pixabay" => {
+        ProviderKind::Pixabay => {
             let provider = PixabayProvider::new(api_key.to_string());
             Ok(Arc::new(provider))
         }
-        _ => Err(MediaError::UnknownProvider(provider_name.to_string())),
+    }
+}
+
+/// 根据 provider 名称（不区分大小写）创建对应的 MediaProvider 实例
+pub fn create_provider_str(
+    provider_name: &str,
+    api_key: &str,
+) -> Result<Arc<dyn MediaProvider + Send + Sync>> {
+    create_provider(provider_name.parse()?, api_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_kind_from_str() {
+        assert_eq!(
+            "Pexels".parse::<ProviderKind>().unwrap(),
+            ProviderKind::Pexels
+        );
+        assert_eq!(
+            "pixabay".parse::<ProviderKind>().unwrap(),
+            ProviderKind::Pixabay
+        );
+        assert!(matches!(
+            "pexel".parse::<ProviderKind>(),
+            Err(MediaError::UnknownProvider(name)) if name == "pexel"
+        ));
+
+        for kind in [ProviderKind::Pexels, ProviderKind::Pixabay] {
+            assert_eq!(kind.to_string().parse::<ProviderKind>().unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn test_create_provider_dispatch() {
+        let provider = create_provider(ProviderKind::Pixabay, "key").unwrap();
+        assert_eq!(provider.name(), "Pixabay");
+
+        #[cfg(feature = "pexels")]
+        {
+            let provider = create_provider_str("PEXELS", "key").unwrap();
+            assert_eq!(provider.name(), "Pexels");
+        }
+        #[cfg(not(feature = "pexels"))]
+        assert!(matches!(
+            create_provider_str("PEXELS", "key"),
+            Err(MediaError::ProviderNotEnabled(_))
+        ));
+        assert_eq!(ProviderKind::Pexels.is_enabled(), cfg!(feature = "pexels"));
+        assert!(ProviderKind::Pixabay.is_enabled());
+
+        assert!(matches!(
+            create_provider_str("flickr", "key"),
+            Err(MediaError::UnknownProvider(_))
+        ));
+        assert!(matches!(
+            create_provider(ProviderKind::Pixabay, ""),
+            Err(MediaError::ApiKeyIsEmpty)
+        ));
     }
 }
@@ -1,17 +1,62 @@
-use crate::create_provider::create_provider;
-use crate::error::{MediaError, Result};
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use crate::create_provider::create_provider_str;
+use crate::error::{map_download_status, MediaError, Result};
+use crate::manifest::{
+    matches_record, read_sidecars, sha256_file, write_sidecar, DownloadManifest, ManifestEntry,
+    VerifyReport,
+};
 use crate::media_provider::MediaProvider;
 use crate::models::{
-    AggregatedSearchResult, BatchDownloadProgress, DownloadProgress, DownloadState, ImageQuality,
-    MediaItem, MediaType, ProgressCallback, SearchResult, VideoQuality,
+    AggregatedSearchResult, BatchDownloadProgress, BatchSummary, DownloadProgress, DownloadState,
+    ExportFormat, ImageQuality, MediaItem, MediaType, ProgressCallback, RateLimitInfo,
+    SearchResult, VideoQuality,
 };
+use crate::PixabayColor;
 use futures::future::join_all;
-use log::error;
-use std::path::Path;
-use std::sync::Arc;
-use std::time::Instant;
+use log::{error, warn};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
 use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::task::{JoinHandle, JoinSet};
+
+/// 生成文件名的最大字节数（常见文件系统的单个文件名上限为 255 字节）
+const MAX_FILENAME_BYTES: usize = 255;
+
+/// 标题被截断时追加的省略号
+const TITLE_ELLIPSIS: &str = "…";
+
+/// 未写完的下载文件，在 drop 时删除，除非已调用 [`keep`](Self::keep)
+struct PartialFile {
+    path: Option<PathBuf>,
+}
+
+impl PartialFile {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: Some(path.to_path_buf()),
+        }
+    }
+
+    /// 下载完成，保留文件
+    fn keep(mut self) {
+        self.path = None;
+    }
+}
+
+impl Drop for PartialFile {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
 
 /// 媒体下载配置
 #[derive(Clone)]
@@ -28,8 +73,100 @@ pub struct DownloadConfig {
     pub max_concurrent: usize,
     /// 进度回调（可选）
     pub progress_callback: Option<ProgressCallback>,
+    /// 下载清单路径（可选，JSON Lines 格式）
+    ///
+    /// 设置后，每个成功下载的媒体项都会追加一条 `{id, provider, mediaType, path, bytes}` 记录；
+    /// 再次运行时会跳过清单中已记录的媒体项，用于大批量下载中断后续传
+    pub manifest_path: Option<String>,
+    /// 是否在每个下载的文件旁写入 `<文件名>.json` 附属记录（sidecar）
+    ///
+    /// 附属记录包含媒体 ID、提供商、媒体类型、文件大小与 SHA-256，
+    /// 供 [`MediaDownloader::verify_dir`] 校验并重新下载损坏的文件。默认不写入
+    pub write_sidecars: bool,
+    /// 是否在下载前发送 `HEAD` 请求进行预检
+    ///
+    /// 启用后会在开始传输前确认状态码成功且 `Content-Type` 属于预期的媒体类别
+    /// （`image/*` 或 `video/*`），以便尽早发现失效链接或返回 HTML 错误页的地址。
+    /// 不支持 `HEAD` 的服务器会跳过预检
+    pub preflight: bool,
+    /// 是否要求媒体项带有署名信息
+    ///
+    /// 启用后，`author` 或 `source_url` 为空的媒体项会在下载前被拒绝，
+    /// 返回 `MediaError::InvalidParameter("missing attribution")`
+    pub require_attribution: bool,
+    /// 聚合搜索的熔断器配置（可选）
+    ///
+    /// 设置后，某个提供商在时间窗口内连续失败达到阈值时，会在冷却期内被跳过，
+    /// 并在 `provider_errors` 中记录为 "circuit open"；冷却结束后允许一次试探请求
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// 新建下载文件时使用的权限位（如 `0o644`），`None` 时沿用进程默认行为
+    ///
+    /// 仅在 Unix 上生效，且仍受进程 umask 影响；在 Windows 上会被忽略
+    pub file_mode: Option<u32>,
+    /// 单次聚合搜索中同时进行的提供商请求上限，`None` 表示不限制
+    ///
+    /// [`MediaDownloader::search_all_types`] 的图片与视频搜索共用同一个上限，
+    /// 因此 2 个提供商 × 2 种类型的 4 个请求在上限为 2 时最多同时进行 2 个
+    pub max_concurrent_searches: Option<usize>,
+    /// 批量下载时传输层失败（连接被拒绝或重置、超时、读取响应体中断）的重试策略，默认不重试
+    ///
+    /// 这类失败通常是瞬时的，适合较短的重试间隔
+    pub transport_retries: RetryPolicy,
+    /// 批量下载时服务器返回错误状态（限流 429 以及 5xx 等）的重试策略，默认不重试
+    ///
+    /// 404、401 和 403 不重试。服务器过载时应使用较长的重试间隔；
+    /// 响应带有 `Retry-After`（秒数）时，等待该时长与 `delay` 中较大的一个
+    pub status_retries: RetryPolicy,
+    /// 一次批量下载中所有媒体项共享的重试总次数上限
+    ///
+    /// 避免大量媒体项各自重试时成倍放大对限流 API 的请求量；
+    /// 预算耗尽后，后续失败的媒体项不再重试。传输层与状态码重试共用此预算。
+    /// 默认为 `usize::MAX`（仅受各自的 `max_retries` 限制）
+    pub retry_budget: usize,
+    /// 按提供商覆盖的 `(图片质量, 视频质量)`，键为 `MediaItem::provider`
+    ///
+    /// 不同提供商的最佳质量档位不同（如 Pixabay 的 `large` 与 Pexels 的 `large2x`），
+    /// 未在此配置的提供商使用全局的 `image_quality` / `video_quality`
+    pub provider_quality: HashMap<String, (ImageQuality, VideoQuality)>,
+    /// 下载前要求目标文件系统在写入文件后仍保留的最小空闲字节数（可选）
+    ///
+    /// 仅在响应带有 `Content-Length` 时检查；空间不足时返回
+    /// `MediaError::DownloadError("insufficient disk space")`，不会创建文件。
+    /// 需要启用 `disk-space` 特性（默认启用），未启用时忽略此设置
+    pub min_free_bytes: Option<u64>,
+    /// 下载过程中两次进度回调之间的最小间隔，默认 100ms，为零时每个数据块都会回调
+    ///
+    /// 状态变化（开始、写入、完成、失败）始终会立即回调，不受此间隔限制
+    pub progress_interval: Duration,
+    /// 下载客户端连接池中每个主机保留的最大空闲连接数，默认与 reqwest 一致（不限制）
+    ///
+    /// 高并发批量下载时建议设置为不小于 `max_concurrent` 的值（如 `max_concurrent * 2`），
+    /// 既能复用连接，又不会在批次结束后长时间占用大量空闲套接字
+    pub pool_max_idle_per_host: usize,
+    /// 空闲连接在连接池中保留的时长，`None` 表示不超时，默认与 reqwest 一致（90 秒）
+    ///
+    /// 批次之间间隔较长时可适当缩短（如 30 秒），避免复用已被 CDN 关闭的连接
+    pub pool_idle_timeout: Option<Duration>,
+    /// TCP keep-alive 探测间隔，`None` 表示不启用，默认与 reqwest 一致（15 秒）
+    ///
+    /// 下载大视频等长连接时建议保持启用（如 30–60 秒），以便及时发现被中间设备断开的连接
+    pub tcp_keepalive: Option<Duration>,
+    /// 在请求前改写解析出的下载 URL（可选）
+    ///
+    /// 适用于通过内部缓存 CDN 代理提供商图片主机的场景，例如将 `images.pexels.com`
+    /// 改写为 `cdn.internal/pexels`。下载、[`MediaDownloader::estimate_total_size`]
+    /// 的 `HEAD` 请求、缩略图预取和联系表的缩略图请求都使用改写后的 URL
+    pub url_rewriter: Option<UrlRewriter>,
+    /// [`MediaDownloader::prefetch_next_page`] 是否同时下载下一页各媒体项的缩略图，默认不下载
+    ///
+    /// 缩略图保存到 [`MediaDownloader::thumbnail_path`] 给出的位置（`output_dir/thumbnails`），
+    /// 已存在的文件不会重复下载；单个缩略图下载失败会被忽略，不影响预取的搜索结果
+    pub prefetch_thumbnails: bool,
 }
 
+/// 下载 URL 改写函数，见 [`DownloadConfig::url_rewriter`]
+pub type UrlRewriter = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
 impl Default for DownloadConfig {
     fn default() -> Self {
         Self {
@@ -39,6 +176,24 @@ impl Default for DownloadConfig {
             use_original_names: false,
             max_concurrent: 5,
             progress_callback: None,
+            manifest_path: None,
+            write_sidecars: false,
+            preflight: false,
+            require_attribution: false,
+            circuit_breaker: None,
+            file_mode: None,
+            max_concurrent_searches: None,
+            transport_retries: RetryPolicy::default(),
+            status_retries: RetryPolicy::default(),
+            retry_budget: usize::MAX,
+            provider_quality: HashMap::new(),
+            min_free_bytes: None,
+            progress_interval: Duration::from_millis(100),
+            pool_max_idle_per_host: usize::MAX,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            tcp_keepalive: Some(Duration::from_secs(15)),
+            url_rewriter: None,
+            prefetch_thumbnails: false,
         }
     }
 }
@@ -52,10 +207,119 @@ impl std::fmt::Debug for DownloadConfig {
             .field("use_original_names", &self.use_original_names)
             .field("max_concurrent", &self.max_concurrent)
             .field("progress_callback", &self.progress_callback.is_some())
+            .field("manifest_path", &self.manifest_path)
+            .field("write_sidecars", &self.write_sidecars)
+            .field("preflight", &self.preflight)
+            .field("require_attribution", &self.require_attribution)
+            .field("circuit_breaker", &self.circuit_breaker)
+            .field("file_mode", &self.file_mode)
+            .field("max_concurrent_searches", &self.max_concurrent_searches)
+            .field("transport_retries", &self.transport_retries)
+            .field("status_retries", &self.status_retries)
+            .field("retry_budget", &self.retry_budget)
+            .field("provider_quality", &self.provider_quality)
+            .field("min_free_bytes", &self.min_free_bytes)
+            .field("progress_interval", &self.progress_interval)
+            .field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("tcp_keepalive", &self.tcp_keepalive)
+            .field("url_rewriter", &self.url_rewriter.is_some())
+            .field("prefetch_thumbnails", &self.prefetch_thumbnails)
             .finish()
     }
 }
 
+/// [`MediaDownloader::export_attributions`] 导出的一条署名记录
+#[derive(serde::Serialize)]
+struct AttributionRecord<'a> {
+    id: &'a str,
+    provider: &'a str,
+    author: &'a str,
+    author_url: &'a str,
+    source_url: &'a str,
+    license: &'a str,
+    attribution: String,
+    local_path: String,
+}
+
+impl AttributionRecord<'_> {
+    const CSV_HEADER: [&'static str; 8] = [
+        "id",
+        "provider",
+        "author",
+        "author_url",
+        "source_url",
+        "license",
+        "attribution",
+        "local_path",
+    ];
+
+    /// 按 RFC 4180 转义后的一行 CSV（不含换行符）
+    fn csv_row(&self) -> String {
+        [
+            self.id,
+            self.provider,
+            self.author,
+            self.author_url,
+            self.source_url,
+            self.license,
+            &self.attribution,
+            &self.local_path,
+        ]
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+/// 一类下载失败的重试次数与间隔，见 [`DownloadConfig::transport_retries`] 和
+/// [`DownloadConfig::status_retries`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RetryPolicy {
+    /// 单个媒体项的最大重试次数，0 表示不重试
+    pub max_retries: usize,
+    /// 每次重试前的等待时间
+    pub delay: Duration,
+}
+
+impl RetryPolicy {
+    /// 创建重试策略
+    pub fn new(max_retries: usize, delay: Duration) -> Self {
+        Self { max_retries, delay }
+    }
+}
+
+/// 下载失败的重试类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RetryKind {
+    /// 传输层失败：连接、超时或读取响应体出错
+    Transport,
+    /// 服务器返回了可重试的错误状态
+    Status,
+}
+
+impl RetryKind {
+    /// 判断下载错误所属的重试类别，不值得重试的错误返回 `None`
+    fn of(error: &MediaError) -> Option<Self> {
+        match error {
+            MediaError::HttpError(e)
+                if e.is_connect() || e.is_timeout() || e.is_request() || e.is_body() =>
+            {
+                Some(Self::Transport)
+            }
+            MediaError::HttpStatus(..) | MediaError::RateLimit(_) => Some(Self::Status),
+            _ => None,
+        }
+    }
+}
+
 /// 搜索参数
 #[derive(Debug, Clone)]
 pub struct SearchParams {
@@ -63,6 +327,13 @@ pub struct SearchParams {
     pub limit: u32,    //每页记录数
     pub page: u32,     //第几页
     pub media_type: MediaType,
+    pub min_width: Option<u32>,          //最小宽度（像素）
+    pub min_height: Option<u32>,         //最小高度（像素）
+    pub providers: Option<Vec<String>>,  //限定搜索的提供商名称，None 表示全部
+    pub fail_fast: bool,                 //任一提供商失败时整个搜索返回错误
+    pub max_per_provider: Option<usize>, //聚合时每个提供商最多保留的结果数
+    pub colors: Vec<PixabayColor>,       //图片颜色筛选，为空表示不筛选
+    pub offset: Option<u32>,             //从第几个结果开始（从 0 计），设置后忽略 page
 }
 
 impl SearchParams {
@@ -72,9 +343,20 @@ impl SearchParams {
             limit: 20,
             page: 1,
             media_type,
+            min_width: None,
+            min_height: None,
+            providers: None,
+            fail_fast: false,
+            max_per_provider: None,
+            colors: Vec::new(),
+            offset: None,
         }
     }
 
+    /// 设置每页结果数
+    ///
+    /// 各提供商会将其截断到自身支持的范围（Pexels 为 1-80，Pixabay 为 3-200），
+    /// 实际生效的值记录在返回结果的 `per_page` 中
     pub fn limit(mut self, limit: u32) -> Self {
         self.limit = limit;
         self
@@ -85,10 +367,129 @@ impl SearchParams {
         self
     }
 
+    /// 从第 `offset` 个结果（从 0 计）开始返回最多 `limit` 个结果，设置后忽略 `page`
+    ///
+    /// 下载器按各提供商实际生效的 `per_page` 换算出页码，必要时多请求一页，
+    /// 并去掉页首多余的结果。聚合搜索时偏移量对每个提供商分别生效（与 `page` 相同）。
+    /// 翻页时请保持 `limit` 不变；客户端筛选（如 Pexels 的 `min_width`）会使偏移量不精确
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// 设置每页结果数，同 [`SearchParams::limit`]
     pub fn per_page(mut self, per_page: u32) -> Self {
         self.limit = per_page;
         self
     }
+
+    /// 设置最小宽度（像素）
+    ///
+    /// Pixabay 在服务端筛选；Pexels 不支持，会在客户端对已获取的结果进行筛选
+    pub fn min_width(mut self, min_width: u32) -> Self {
+        self.min_width = Some(min_width);
+        self
+    }
+
+    /// 设置最小高度（像素），筛选方式同 [`SearchParams::min_width`]
+    pub fn min_height(mut self, min_height: u32) -> Self {
+        self.min_height = Some(min_height);
+        self
+    }
+
+    /// 将聚合搜索限定在指定名称的提供商（如 `["Pexels"]`）
+    ///
+    /// 只影响 [`MediaDownloader::search`]；未注册的名称会记录在结果的 `provider_errors` 中
+    pub fn with_providers<I, S>(mut self, providers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.providers = Some(providers.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// 任一提供商失败时让 [`MediaDownloader::search`] 返回错误而不是部分结果，默认关闭
+    ///
+    /// 未注册的提供商名称返回 `MediaError::UnknownProvider`，
+    /// 已熔断的提供商返回 `MediaError::ProviderUnavailable`，其他失败返回该提供商的错误
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// 聚合搜索时每个提供商最多保留前 `max` 个结果，避免结果较多的提供商占满合并后的列表
+    ///
+    /// 只截断 `items`，`total`、`total_hits` 等统计仍为提供商返回的真实值
+    pub fn max_per_provider(mut self, max: usize) -> Self {
+        self.max_per_provider = Some(max);
+        self
+    }
+
+    /// 按颜色筛选图片，视频搜索会忽略此参数
+    ///
+    /// Pixabay 支持同时筛选多个颜色。Pexels 只接受一个颜色，使用列表中第一个
+    /// 两者共有的颜色（`Lilac` 对应 Pexels 的 `violet`）；`Grayscale` 和 `Transparent`
+    /// 只有 Pixabay 支持，Pexels 会忽略，列表中没有共有颜色时 Pexels 不做颜色筛选
+    pub fn colors(mut self, colors: impl IntoIterator<Item = PixabayColor>) -> Self {
+        self.colors = colors.into_iter().collect();
+        self
+    }
+}
+
+/// 从 Pixabay 图片搜索参数转换
+///
+/// 保留 `query`、`per_page`、`page`、`min_width`、`min_height`，未设置的字段使用
+/// [`SearchParams::new`] 的默认值。Pixabay 特有的 `image_type`、`orientation`、`category`、
+/// `colors`、`editors_choice`、`safesearch`、`order` 等筛选条件在聚合搜索中没有对应项，会被丢弃
+impl From<pixabay_sdk::SearchImageParams> for SearchParams {
+    fn from(params: pixabay_sdk::SearchImageParams) -> Self {
+        Self::from_pixabay(
+            params.query,
+            params.per_page,
+            params.page,
+            MediaType::Image,
+            params.min_width,
+            params.min_height,
+        )
+    }
+}
+
+/// 从 Pixabay 视频搜索参数转换
+///
+/// 保留的字段同图片参数的转换；`video_type`、`category`、`editors_choice`、`safesearch`、
+/// `order`、`lang` 等 Pixabay 特有的筛选条件会被丢弃
+impl From<pixabay_sdk::SearchVideoParams> for SearchParams {
+    fn from(params: pixabay_sdk::SearchVideoParams) -> Self {
+        Self::from_pixabay(
+            params.query,
+            params.per_page,
+            params.page,
+            MediaType::Video,
+            params.min_width,
+            params.min_height,
+        )
+    }
+}
+
+impl SearchParams {
+    fn from_pixabay(
+        query: Option<String>,
+        per_page: Option<u32>,
+        page: Option<u32>,
+        media_type: MediaType,
+        min_width: Option<u32>,
+        min_height: Option<u32>,
+    ) -> Self {
+        let defaults = Self::new(query.unwrap_or_default(), media_type);
+        Self {
+            limit: per_page.unwrap_or(defaults.limit),
+            page: page.unwrap_or(defaults.page),
+            min_width,
+            min_height,
+            ..defaults
+        }
+    }
 }
 
 /// 聚合多个提供商的主媒体下载器
@@ -96,36 +497,217 @@ pub struct MediaDownloader {
     providers: Vec<Arc<dyn MediaProvider>>,
     config: DownloadConfig,
     http_client: reqwest::Client,
+    search_cache: Option<SearchCache>,
+    temp_dir: Option<Arc<TempDir>>,
+    circuit_breaker: Arc<CircuitBreaker>,
+}
+
+/// 按配置中的连接池设置构建下载使用的 HTTP 客户端
+fn build_http_client(config: &DownloadConfig) -> reqwest::Client {
+    reqwest::Client::builder()
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .pool_idle_timeout(config.pool_idle_timeout)
+        .tcp_keepalive(config.tcp_keepalive)
+        // 媒体文件本身已压缩；协商传输压缩后 `Content-Length` 会变成压缩后的大小或缺失，
+        // 导致进度、剩余时间与 `estimate_total_size` 不准确
+        .no_gzip()
+        .no_brotli()
+        .build()
+        .expect("无法创建 HTTP 客户端")
+}
+
+/// 搜索缓存默认最多保留的结果数
+const DEFAULT_SEARCH_CACHE_CAPACITY: usize = 256;
+/// 搜索缓存结果的默认有效期
+const DEFAULT_SEARCH_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// 搜索结果缓存，键包含提供商集合指纹和搜索参数，克隆的下载器共享同一个缓存
+type SearchCache = Arc<Mutex<SearchCacheEntries>>;
+
+/// 带容量上限和有效期的搜索结果表
+struct SearchCacheEntries {
+    entries: HashMap<String, (Instant, AggregatedSearchResult)>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl SearchCacheEntries {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+            ttl,
+        }
+    }
+
+    /// 取出未过期的结果，过期的结果会被移除
+    fn get(&mut self, key: &str) -> Option<AggregatedSearchResult> {
+        let (inserted, result) = self.entries.get(key)?;
+        if inserted.elapsed() < self.ttl {
+            return Some(result.clone());
+        }
+        self.entries.remove(key);
+        None
+    }
+
+    /// 写入结果，达到容量上限时先移除过期结果，仍然已满则移除最早写入的结果
+    fn insert(&mut self, key: String, result: AggregatedSearchResult) {
+        if self.capacity == 0 {
+            return;
+        }
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let ttl = self.ttl;
+            self.entries
+                .retain(|_, (inserted, _)| inserted.elapsed() < ttl);
+            if self.entries.len() >= self.capacity {
+                let oldest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, (inserted, _))| *inserted)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+        self.entries.insert(key, (Instant::now(), result));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
 }
 
 impl MediaDownloader {
     /// 创建新的媒体下载器
     pub fn new() -> Self {
+        let config = DownloadConfig::default();
         Self {
             providers: Vec::new(),
-            config: DownloadConfig::default(),
-            http_client: reqwest::Client::new(),
+            http_client: build_http_client(&config),
+            config,
+            search_cache: None,
+            temp_dir: None,
+            circuit_breaker: Arc::new(CircuitBreaker::default()),
         }
     }
 
+    /// 创建下载到自清理临时目录的下载器
+    ///
+    /// 所有下载都写入新建的临时目录，当该下载器及其所有克隆被释放时目录会被删除。
+    /// 返回的文件路径仅在下载器存活期间有效，因此下载器本身就是临时目录的守卫。
+    pub fn temp() -> Result<Self> {
+        let temp_dir = TempDir::new()?;
+        let mut downloader = Self::new();
+        downloader.config.output_dir = temp_dir.path().to_string_lossy().to_string();
+        downloader.temp_dir = Some(Arc::new(temp_dir));
+        Ok(downloader)
+    }
+
+    /// 临时目录模式下返回临时目录路径
+    pub fn temp_dir(&self) -> Option<&Path> {
+        self.temp_dir.as_deref().map(TempDir::path)
+    }
+
     /// 设置下载配置
+    ///
+    /// 临时目录模式下会保留临时目录作为输出目录，忽略 `config.output_dir`
     pub fn with_config(mut self, config: DownloadConfig) -> Self {
+        self.set_config(config);
+        self
+    }
+
+    /// 当前的下载配置
+    pub fn config(&self) -> &DownloadConfig {
+        &self.config
+    }
+
+    /// 就地替换下载配置，保留已添加的提供商、HTTP 客户端和搜索缓存
+    ///
+    /// 与 [`with_config`](Self::with_config) 相同，临时目录模式下会忽略 `config.output_dir`。
+    /// 连接池设置（`pool_max_idle_per_host`、`pool_idle_timeout`、`tcp_keepalive`）
+    /// 发生变化时会按新设置重建 HTTP 客户端
+    pub fn set_config(&mut self, config: DownloadConfig) {
+        if config.pool_max_idle_per_host != self.config.pool_max_idle_per_host
+            || config.pool_idle_timeout != self.config.pool_idle_timeout
+            || config.tcp_keepalive != self.config.tcp_keepalive
+        {
+            self.http_client = build_http_client(&config);
+        }
         self.config = config;
+        if let Some(temp_dir) = &self.temp_dir {
+            self.config.output_dir = temp_dir.path().to_string_lossy().to_string();
+        }
+    }
+
+    /// 设置图片下载质量
+    pub fn set_image_quality(&mut self, quality: ImageQuality) {
+        self.config.image_quality = quality;
+    }
+
+    /// 设置视频下载质量
+    pub fn set_video_quality(&mut self, quality: VideoQuality) {
+        self.config.video_quality = quality;
+    }
+
+    /// 设置下载目录，之后的下载会写入新目录
+    ///
+    /// 临时目录模式下同样生效，临时目录仍会在下载器释放时删除
+    pub fn set_output_dir(&mut self, output_dir: impl Into<String>) {
+        self.config.output_dir = output_dir.into();
+    }
+
+    /// 设置最大并发下载数
+    pub fn set_max_concurrent(&mut self, max_concurrent: usize) {
+        self.config.max_concurrent = max_concurrent;
+    }
+
+    /// 为指定提供商设置质量覆盖，该提供商的媒体项将按此质量下载
+    ///
+    /// # 参数
+    ///
+    /// * `provider` - 提供商名称，与 `MediaItem::provider` 一致（如 `"Pexels"`）
+    /// * `image_quality` - 该提供商的图片质量
+    /// * `video_quality` - 该提供商的视频质量
+    pub fn with_provider_config(
+        mut self,
+        provider: impl Into<String>,
+        image_quality: ImageQuality,
+        video_quality: VideoQuality,
+    ) -> Self {
+        self.config
+            .provider_quality
+            .insert(provider.into(), (image_quality, video_quality));
         self
     }
 
     /// 添加提供商
     pub fn add_provider(mut self, provider: Arc<dyn MediaProvider>) -> Self {
-        self.providers.push(provider);
+        self.push_provider(provider);
         self
     }
 
+    /// 就地添加提供商
+    ///
+    /// 与 [`add_provider`](Self::add_provider) 相同，但不消耗 `self`，
+    /// 适用于构建完成后仍需调整提供商的长期存活的下载器
+    ///
+    /// 提供商按名称去重：已存在同名提供商（如重复注册两个 Pexels 密钥）时记录警告并忽略新的提供商，
+    /// 保留先注册的那个，避免聚合搜索重复计数
+    pub fn push_provider(&mut self, provider: Arc<dyn MediaProvider>) {
+        if self.providers.iter().any(|p| p.name() == provider.name()) {
+            warn!("提供商 {} 已注册，忽略重复的提供商", provider.name());
+            return;
+        }
+        self.providers.push(provider);
+    }
+
     /// 根据名称添加提供商
     pub fn add_provider_by_name_and_apikey(mut self, provider_name: &str, api_key: &str) -> Self {
-        let provider_res = create_provider(provider_name, api_key);
+        let provider_res = create_provider_str(provider_name, api_key);
         match provider_res {
             Ok(provider) => {
-                self.providers.push(provider);
+                self.push_provider(provider);
             }
             Err(e) => {
                 error!("Error creating provider: {}", e);
@@ -139,38 +721,191 @@ impl MediaDownloader {
         &self.providers
     }
 
+    /// 各提供商最近一次报告的速率限制状态（提供商名称 → 状态）
+    ///
+    /// 不报告速率限制或尚未发出请求的提供商不会出现在结果中
+    pub fn rate_limits(&self) -> HashMap<String, RateLimitInfo> {
+        self.providers
+            .iter()
+            .filter_map(|p| Some((p.name().to_string(), p.rate_limit()?)))
+            .collect()
+    }
+
+    /// 启用内存中的搜索结果缓存
+    ///
+    /// 缓存键包含当前提供商集合的指纹，添加或移除提供商后会自动错过旧的缓存结果。
+    /// 克隆的下载器共享同一个缓存。只缓存所有提供商都成功的结果，
+    /// 最多保留 256 个结果，每个结果 5 分钟后过期；需要其他限制时使用
+    /// [`with_search_cache_limits`](Self::with_search_cache_limits)
+    pub fn with_search_cache(self) -> Self {
+        self.with_search_cache_limits(DEFAULT_SEARCH_CACHE_CAPACITY, DEFAULT_SEARCH_CACHE_TTL)
+    }
+
+    /// 启用内存中的搜索结果缓存，并指定容量上限和有效期
+    ///
+    /// 缓存已满时移除最早写入的结果；超过 `ttl` 的结果不再命中。
+    /// 其他行为与 [`with_search_cache`](Self::with_search_cache) 相同
+    pub fn with_search_cache_limits(mut self, capacity: usize, ttl: Duration) -> Self {
+        self.search_cache = Some(Arc::new(Mutex::new(SearchCacheEntries::new(capacity, ttl))));
+        self
+    }
+
+    /// 清空搜索结果缓存
+    pub fn clear_search_cache(&self) {
+        if let Some(cache) = &self.search_cache {
+            cache.lock().unwrap().clear();
+        }
+    }
+
+    /// 计算当前提供商集合（名称）的指纹
+    fn provider_fingerprint(&self) -> u64 {
+        let mut names: Vec<&str> = self.providers.iter().map(|p| p.name()).collect();
+        names.sort_unstable();
+
+        let mut hasher = DefaultHasher::new();
+        names.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// 生成搜索缓存键
+    fn search_cache_key(&self, params: &SearchParams) -> String {
+        format!("{:016x}|{:?}", self.provider_fingerprint(), params)
+    }
+
     /// 从所有提供商搜索媒体
     ///
     /// 返回所有提供商的聚合结果，包含组合的分页信息
     pub async fn search(&self, params: SearchParams) -> Result<AggregatedSearchResult> {
+        let limiter = self.search_limiter();
+        self.search_limited(params, limiter.as_ref()).await
+    }
+
+    /// 同时搜索图片和视频，返回 `(图片结果, 视频结果)`
+    ///
+    /// 忽略 `params.media_type`，分别以图片和视频类型调用各提供商，任一类型的聚合搜索失败时返回该错误。
+    /// 两种类型的请求共享 [`DownloadConfig::max_concurrent_searches`] 的同一个上限，
+    /// 而不是各自计算，避免同时发出的请求数翻倍而触发提供商的速率限制
+    pub async fn search_all_types(
+        &self,
+        params: SearchParams,
+    ) -> Result<(AggregatedSearchResult, AggregatedSearchResult)> {
+        let limiter = self.search_limiter();
+        let images = SearchParams {
+            media_type: MediaType::Image,
+            ..params.clone()
+        };
+        let videos = SearchParams {
+            media_type: MediaType::Video,
+            ..params
+        };
+        tokio::try_join!(
+            self.search_limited(images, limiter.as_ref()),
+            self.search_limited(videos, limiter.as_ref())
+        )
+    }
+
+    /// 只统计搜索结果总数，不拉取完整结果
+    ///
+    /// 以 `per_page = 3`（Pixabay 允许的最小值）请求第 1 页并累加各提供商返回的 `total`，
+    /// 适合显示 "N+ 个结果" 之类的预览。提供商范围、熔断与 `fail_fast` 的行为与
+    /// [`search`](Self::search) 相同，失败的提供商不计入总数
+    pub async fn count(&self, params: SearchParams) -> Result<u32> {
+        let result = self.search(params.per_page(3).page(1)).await?;
+        Ok(result.total)
+    }
+
+    /// 按配置创建限制并发提供商请求数的信号量
+    fn search_limiter(&self) -> Option<tokio::sync::Semaphore> {
+        self.config
+            .max_concurrent_searches
+            .map(|max| tokio::sync::Semaphore::new(max.max(1)))
+    }
+
+    /// 聚合搜索的实现，`limiter` 存在时每个提供商请求都需先获取一个许可
+    async fn search_limited(
+        &self,
+        params: SearchParams,
+        limiter: Option<&tokio::sync::Semaphore>,
+    ) -> Result<AggregatedSearchResult> {
+        if params.query.trim().is_empty() {
+            return Err(MediaError::InvalidParameter("empty query".to_string()));
+        }
         if self.providers.is_empty() {
             return Err(MediaError::NoProviders);
         }
 
+        let cache_key = self
+            .search_cache
+            .as_ref()
+            .map(|_| self.search_cache_key(&params));
+        if let (Some(cache), Some(key)) = (&self.search_cache, &cache_key) {
+            if let Some(cached) = cache.lock().unwrap().get(key) {
+                return Ok(cached);
+            }
+        }
+
+        let breaker_config = self.config.circuit_breaker;
+        let mut provider_errors = HashMap::new();
+
+        if let Some(names) = &params.providers {
+            for name in names {
+                if !self.providers.iter().any(|p| p.name() == name) {
+                    if params.fail_fast {
+                        return Err(MediaError::UnknownProvider(name.clone()));
+                    }
+                    provider_errors.insert(
+                        name.clone(),
+                        MediaError::UnknownProvider(name.clone()).to_string(),
+                    );
+                }
+            }
+        }
+
         let futures: Vec<_> = self
             .providers
             .iter()
+            .filter(|provider| {
+                params
+                    .providers
+                    .as_ref()
+                    .is_none_or(|names| names.iter().any(|n| n == provider.name()))
+            })
+            .filter(|provider| match &breaker_config {
+                Some(config) if !self.circuit_breaker.allow(provider.name(), config) => {
+                    provider_errors.insert(provider.name().to_string(), "circuit open".to_string());
+                    false
+                }
+                _ => true,
+            })
             .map(|provider| {
                 let provider = Arc::clone(provider);
                 let params = params.clone();
 
                 async move {
-                    match params.media_type {
-                        MediaType::Image => {
-                            provider
-                                .search_images(&params.query, params.limit, params.page)
-                                .await
-                        }
-                        MediaType::Video => {
-                            provider
-                                .search_videos(&params.query, params.limit, params.page)
-                                .await
-                        }
-                    }
+                    let _permit = match limiter {
+                        Some(semaphore) => semaphore.acquire().await.ok(),
+                        None => None,
+                    };
+                    let started = Instant::now();
+                    let result =
+                        search_provider(provider.as_ref(), &params)
+                            .await
+                            .map(|mut result| {
+                                result.duration = started.elapsed();
+                                result
+                            });
+                    (provider, result)
                 }
             })
             .collect();
 
+        if params.fail_fast {
+            if let Some(name) = provider_errors.keys().next() {
+                return Err(MediaError::ProviderUnavailable(name.clone()));
+            }
+        }
+
+        let search_started = Instant::now();
         let results = join_all(futures).await;
 
         let mut provider_results = Vec::new();
@@ -178,17 +913,31 @@ impl MediaDownloader {
         let mut total_sum = 0u32;
         let mut total_hits_sum = 0u32;
         let mut total_pages_sum = 0u32;
+        let mut per_page_sum = 0u32;
         let mut has_success = false;
 
-        for result in results {
+        for (provider, result) in results {
+            if let Some(config) = &breaker_config {
+                match &result {
+                    Ok(_) => self.circuit_breaker.record_success(provider.name()),
+                    Err(_) => self.circuit_breaker.record_failure(provider.name(), config),
+                }
+            }
+
             match result {
-                Ok(search_result) => {
+                Ok(mut search_result) => {
                     has_success = true;
 
+                    if let Some(max) = params.max_per_provider {
+                        search_result.items.truncate(max);
+                    }
+
                     // 聚合所有提供商的总数
                     total_sum += search_result.total;
                     total_hits_sum += search_result.total_hits;
                     total_pages_sum += search_result.total_pages;
+                    // 各提供商可能截断了 per_page，累加实际生效的值
+                    per_page_sum += search_result.per_page;
 
                     // 收集项目
                     all_items.extend(search_result.items.clone());
@@ -196,8 +945,10 @@ impl MediaDownloader {
                     // 存储提供商特定的结果
                     provider_results.push(search_result);
                 }
+                Err(e) if params.fail_fast => return Err(e),
                 Err(e) => {
                     eprintln!("提供商失败: {}", e);
+                    provider_errors.insert(provider.name().to_string(), e.to_string());
                 }
             }
         }
@@ -206,7 +957,7 @@ impl MediaDownloader {
             return Err(MediaError::AllProvidersFailed);
         }
 
-        Ok(AggregatedSearchResult {
+        let result = AggregatedSearchResult {
             provider: provider_results
                 .first()
                 .map(|r| r.provider.clone())
@@ -214,11 +965,103 @@ impl MediaDownloader {
             total: total_sum,
             total_hits: total_hits_sum,
             page: params.page,
-            per_page: params.limit,
+            per_page: per_page_sum,
             total_pages: total_pages_sum,
             items: all_items,
             provider_results,
-        })
+            provider_errors,
+            search_duration: search_started.elapsed(),
+        };
+
+        // 部分提供商失败的结果不缓存，以便下次搜索重新请求这些提供商
+        if let (Some(cache), Some(key)) = (&self.search_cache, cache_key) {
+            if result.provider_errors.is_empty() {
+                cache.lock().unwrap().insert(key, result.clone());
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// 在后台预取下一页搜索结果
+    ///
+    /// `params` 为得到 `current` 时使用的搜索参数。若任一提供商还有下一页，以
+    /// [`AggregatedSearchResult::next_page_params`] 给出的参数在后台任务中调用
+    /// [`search`](Self::search) 并返回其句柄，可以等待或直接丢弃；
+    /// 已经是最后一页时返回 `None`。启用了搜索缓存（[`with_search_cache`](Self::with_search_cache)）时，
+    /// 预取的结果会写入缓存，之后请求下一页会直接命中缓存。
+    ///
+    /// 启用 [`DownloadConfig::prefetch_thumbnails`] 时，后台任务还会在搜索完成后
+    /// 下载下一页各媒体项的缩略图，句柄在缩略图下载结束后才完成
+    pub fn prefetch_next_page(
+        &self,
+        params: &SearchParams,
+        current: &AggregatedSearchResult,
+    ) -> Option<JoinHandle<Result<AggregatedSearchResult>>> {
+        let next = current.next_page_params(params)?;
+        let downloader = self.clone();
+        Some(tokio::spawn(async move {
+            let result = downloader.search(next).await?;
+            if downloader.config.prefetch_thumbnails {
+                downloader.download_thumbnails(&result.items).await;
+            }
+            Ok(result)
+        }))
+    }
+
+    /// 媒体项缩略图的本地缓存路径：`output_dir/thumbnails/<文件名>`
+    pub fn thumbnail_path(&self, item: &MediaItem) -> PathBuf {
+        Path::new(&self.config.output_dir)
+            .join("thumbnails")
+            .join(self.generate_filename(item))
+    }
+
+    /// 以 `max_concurrent` 为并发上限下载缩略图，跳过已存在的文件并忽略失败
+    async fn download_thumbnails(&self, items: &[MediaItem]) {
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(
+            self.config.max_concurrent.max(1),
+        ));
+
+        let futures = items
+            .iter()
+            .filter(|item| !item.urls.thumbnail.is_empty())
+            .map(|item| {
+                let semaphore = Arc::clone(&semaphore);
+
+                async move {
+                    let path = self.thumbnail_path(item);
+                    if path.exists() {
+                        return;
+                    }
+                    let Ok(_permit) = semaphore.acquire().await else {
+                        return;
+                    };
+                    let url = self.rewrite_url(item.urls.thumbnail.clone());
+                    let bytes = match self.http_client.get(&url).send().await {
+                        Ok(response) if response.status().is_success() => response.bytes().await,
+                        _ => return,
+                    };
+                    let Ok(bytes) = bytes else {
+                        return;
+                    };
+                    if let Some(parent) = path.parent() {
+                        if tokio::fs::create_dir_all(parent).await.is_err() {
+                            return;
+                        }
+                    }
+                    let _ = tokio::fs::write(&path, &bytes).await;
+                }
+            });
+
+        join_all(futures).await;
+    }
+
+    /// 从所有提供商搜索媒体，任一提供商失败时返回错误
+    ///
+    /// 等同于以 [`SearchParams::fail_fast`] 为 `true` 调用 [`search`](Self::search)，
+    /// 适用于不能容忍遗漏数据源的场景
+    pub async fn search_strict(&self, params: SearchParams) -> Result<AggregatedSearchResult> {
+        self.search(params.fail_fast(true)).await
     }
 
     /// 从特定提供商搜索媒体
@@ -233,41 +1076,120 @@ impl MediaDownloader {
             .find(|p| p.name() == provider_name)
             .ok_or_else(|| MediaError::DownloadError(format!("未找到提供商 {}", provider_name)))?;
 
-        match params.media_type {
-            MediaType::Image => {
-                provider
-                    .search_images(&params.query, params.limit, params.page)
-                    .await
-            }
-            MediaType::Video => {
-                provider
-                    .search_videos(&params.query, params.limit, params.page)
-                    .await
-            }
-        }
+        let started = Instant::now();
+        let mut result = search_provider(provider.as_ref(), &params).await?;
+        result.duration = started.elapsed();
+        Ok(result)
     }
 
     /// 下载单个媒体项并跟踪进度
     pub async fn download_item(&self, item: &MediaItem) -> Result<String> {
-        let start_time = Instant::now();
-        let mut progress = DownloadProgress::new(item);
+        let output_path = self.output_path(item);
+
+        self.download_item_to_path(item, &output_path).await?;
+
+        Ok(output_path.to_string_lossy().to_string())
+    }
+
+    /// 将媒体项下载到指定路径并跟踪进度
+    ///
+    /// 部分 CDN 链接带签名且会过期，缓存较久的媒体项下载时可能返回 401/403。
+    /// 此时通过 `item.provider` 对应提供商的 `get_media` 重新获取媒体项，用新链接重试一次
+    async fn download_item_to_path(&self, item: &MediaItem, output_path: &Path) -> Result<()> {
+        let quality = self.quality_for(item);
+        let (response, progress, start_time) = match self.start_download(item, quality).await {
+            Err(e @ (MediaError::UrlExpired(_) | MediaError::AuthError(_))) => {
+                let Some(fresh) = self.refetch_item(item).await else {
+                    return Err(e);
+                };
+                self.start_download(&fresh, quality).await?
+            }
+            result => result?,
+        };
+
+        // 确保输出目录存在
+        if let Some(parent) = output_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        #[cfg(feature = "disk-space")]
+        if let (Some(margin), Some(len)) = (self.config.min_free_bytes, progress.total_bytes) {
+            let dir = output_path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or(Path::new("."));
+            ensure_free_space(fs2::available_space(dir)?, len, margin)?;
+        }
+
+        // 写入失败或任务被取消时删除未写完的文件
+        let guard = PartialFile::new(output_path);
+        let mut file = self.create_output_file(output_path).await?;
+        self.stream_to_writer(response, &mut file, progress, start_time)
+            .await?;
+        guard.keep();
+
+        Ok(())
+    }
+
+    /// 将媒体项以流的方式写入任意 `AsyncWrite`，不创建任何本地文件
+    ///
+    /// 适用于将媒体直接转发到对象存储或其他流。进度通过配置的进度回调报告。
+    ///
+    /// # 参数
+    ///
+    /// * `item` - 要下载的媒体项
+    /// * `quality` - `(图片质量, 视频质量)`，为 `None` 时使用下载配置中（含按提供商覆盖）的质量偏好
+    /// * `writer` - 接收数据的写入端
+    ///
+    /// # 返回
+    ///
+    /// 写入的总字节数
+    pub async fn download_to_writer<W>(
+        &self,
+        item: &MediaItem,
+        quality: Option<(ImageQuality, VideoQuality)>,
+        writer: &mut W,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let quality = quality.unwrap_or_else(|| self.quality_for(item));
+        let (response, progress, start_time) = self.start_download(item, quality).await?;
+        self.stream_to_writer(response, writer, progress, start_time)
+            .await
+    }
+
+    /// 解析下载 URL 并发起请求，返回成功的响应以及已初始化的进度
+    async fn start_download(
+        &self,
+        item: &MediaItem,
+        (image_quality, video_quality): (ImageQuality, VideoQuality),
+    ) -> Result<(reqwest::Response, DownloadProgress, Instant)> {
+        if self.config.require_attribution
+            && (item.author.trim().is_empty() || item.source_url.trim().is_empty())
+        {
+            return Err(MediaError::InvalidParameter(
+                "missing attribution".to_string(),
+            ));
+        }
+
+        let start_time = Instant::now();
+        let mut progress = DownloadProgress::new(item);
 
         // 通知: 开始
         progress.state = DownloadState::Starting;
         self.notify_progress(&progress);
 
         // 根据质量偏好确定 URL
-        let url = match item.media_type {
-            MediaType::Image => self.get_image_url(item)?,
-            MediaType::Video => self.get_video_url(item)?,
-        };
+        let url = self.rewrite_url(item.preferred_url(image_quality, video_quality)?);
 
-        // 生成文件名
-        let filename = self.generate_filename(item);
-        let output_path = Path::new(&self.config.output_dir).join(&filename);
-
-        // 确保输出目录存在
-        tokio::fs::create_dir_all(&self.config.output_dir).await?;
+        if self.config.preflight {
+            if let Err(e) = self.preflight_check(&url, &item.media_type).await {
+                progress.state = DownloadState::Failed(e.to_string());
+                self.notify_progress(&progress);
+                return Err(e);
+            }
+        }
 
         // 开始下载
         progress.state = DownloadState::Downloading;
@@ -278,19 +1200,104 @@ impl MediaDownloader {
         if !response.status().is_success() {
             progress.state = DownloadState::Failed(format!("HTTP {}", response.status()));
             self.notify_progress(&progress);
-            return Err(MediaError::DownloadError(format!(
-                "HTTP {}: 下载失败",
-                response.status()
-            )));
+            return Err(map_download_status(response.status(), response.headers()));
         }
 
         // 从 Content-Length 头获取总大小
         progress.total_bytes = response.content_length();
 
+        Ok((response, progress, start_time))
+    }
+
+    /// 创建（或截断）输出文件，Unix 上按 `file_mode` 设置新文件的权限
+    async fn create_output_file(&self, path: &Path) -> Result<File> {
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        if let Some(mode) = self.config.file_mode {
+            options.mode(mode);
+        }
+        Ok(options.open(path).await?)
+    }
+
+    /// 从媒体项所属的提供商重新获取媒体项，以取得新的下载链接
+    async fn refetch_item(&self, item: &MediaItem) -> Option<MediaItem> {
+        let kind = item.provider_kind();
+        let provider = self.providers.iter().find(|p| {
+            p.name() == item.provider || (kind.is_some() && p.name().parse().ok() == kind)
+        })?;
+        match provider.get_media(&item.id, item.media_type.clone()).await {
+            Ok(fresh) => Some(fresh),
+            Err(e) => {
+                warn!(
+                    "重新获取 {} 的媒体项 {} 失败: {}",
+                    item.provider, item.id, e
+                );
+                None
+            }
+        }
+    }
+
+    /// 发送 `HEAD` 请求，确认链接可用且 `Content-Type` 与媒体类型相符
+    ///
+    /// 请求失败或服务器以 405/501 拒绝 `HEAD` 时跳过预检；
+    /// 响应中没有 `Content-Type` 时只检查状态码
+    async fn preflight_check(&self, url: &str, media_type: &MediaType) -> Result<()> {
+        let response = match self.http_client.head(url).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("HEAD 预检请求失败，跳过预检: {}", e);
+                return Ok(());
+            }
+        };
+
+        let status = response.status();
+        if status == reqwest::StatusCode::METHOD_NOT_ALLOWED
+            || status == reqwest::StatusCode::NOT_IMPLEMENTED
+        {
+            return Ok(());
+        }
+        if !status.is_success() {
+            return Err(MediaError::DownloadError(format!(
+                "HTTP {}: 预检失败 {}",
+                status, url
+            )));
+        }
+
+        let expected = match media_type {
+            MediaType::Image => "image/",
+            MediaType::Video => "video/",
+        };
+        if let Some(content_type) = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+        {
+            if !content_type.to_ascii_lowercase().starts_with(expected) {
+                return Err(MediaError::DownloadError(format!(
+                    "预检失败: 期望 {}*，实际 Content-Type 为 {} ({})",
+                    expected, content_type, url
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 将响应体分块写入 `writer` 并跟踪进度，返回写入的总字节数
+    async fn stream_to_writer<W>(
+        &self,
+        response: reqwest::Response,
+        writer: &mut W,
+        mut progress: DownloadProgress,
+        start_time: Instant,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
         // 下载并跟踪进度
         let mut downloaded: u64 = 0;
         let mut last_update = Instant::now();
-        let mut file = File::create(&output_path).await?;
         let mut stream = response.bytes_stream();
 
         use futures::StreamExt;
@@ -299,7 +1306,7 @@ impl MediaDownloader {
             let chunk_len = chunk.len() as u64;
 
             // 写入块
-            file.write_all(&chunk).await?;
+            writer.write_all(&chunk).await?;
             downloaded += chunk_len;
 
             // 更新进度
@@ -314,8 +1321,8 @@ impl MediaDownloader {
             progress.calculate_percentage();
             progress.calculate_eta();
 
-            // 节流更新（每 100ms）
-            if last_update.elapsed().as_millis() >= 100 {
+            // 按 progress_interval 节流更新
+            if last_update.elapsed() >= self.config.progress_interval {
                 self.notify_progress(&progress);
                 last_update = Instant::now();
             }
@@ -326,18 +1333,17 @@ impl MediaDownloader {
         progress.elapsed_secs = start_time.elapsed().as_secs_f64();
         progress.calculate_percentage();
 
-        // 写入磁盘
+        // 写入
         progress.state = DownloadState::Writing;
         self.notify_progress(&progress);
 
-        file.flush().await?;
-        drop(file);
+        writer.flush().await?;
 
         // 完成
         progress.state = DownloadState::Completed;
         self.notify_progress(&progress);
 
-        Ok(output_path.to_string_lossy().to_string())
+        Ok(downloaded)
     }
 
     /// 如果配置了进度回调，则通知进度
@@ -348,27 +1354,160 @@ impl MediaDownloader {
     }
 
     /// 并发批量下载多个媒体项，并跟踪整体进度
+    ///
+    /// 如果配置了 `manifest_path`，清单中已记录的媒体项会被跳过并直接返回记录的路径，
+    /// 新完成的媒体项会在完成时追加到清单中
+    ///
+    /// 返回结果与输入一一对应：无论各下载完成的先后顺序如何，`results[i]` 始终是 `items[i]` 的结果，
+    /// 调用方可以据此将失败映射回对应的 `MediaItem`
     pub async fn download_items(&self, items: &[MediaItem]) -> Vec<Result<String>> {
+        let manifest = match self.load_manifest().await {
+            Ok(manifest) => manifest,
+            Err(message) => {
+                return items
+                    .iter()
+                    .map(|_| Err(MediaError::DownloadError(message.clone())))
+                    .collect();
+            }
+        };
+
         let semaphore = Arc::new(tokio::sync::Semaphore::new(self.config.max_concurrent));
+        let retry_budget = Arc::new(AtomicUsize::new(self.config.retry_budget));
 
         let futures: Vec<_> = items
             .iter()
             .map(|item| {
-                let semaphore = Arc::clone(&semaphore);
-                let item = item.clone();
-                let downloader = self.clone();
-
-                async move {
-                    let _permit = semaphore.acquire().await.unwrap();
-                    downloader.download_item(&item).await
-                }
+                self.clone().download_tracked(
+                    item.clone(),
+                    Arc::clone(&semaphore),
+                    manifest.clone(),
+                    Arc::clone(&retry_budget),
+                )
             })
             .collect();
 
         join_all(futures).await
     }
 
+    /// 将每个媒体项的下载作为独立任务派发到 `JoinSet` 中
+    ///
+    /// 与 [`download_items`](Self::download_items) 使用相同的并发限制与下载清单，
+    /// 但由调用方掌控任务：可以逐个等待、`abort_all()` 取消，或接入自己的监督逻辑。
+    /// 任务完成顺序不保证与输入一致。被取消的任务会删除其未写完的文件。
+    pub async fn spawn_downloads(&self, items: &[MediaItem]) -> JoinSet<Result<String>> {
+        let mut set = JoinSet::new();
+        let manifest = match self.load_manifest().await {
+            Ok(manifest) => manifest,
+            Err(message) => {
+                for _ in items {
+                    let message = message.clone();
+                    set.spawn(async move { Err(MediaError::DownloadError(message)) });
+                }
+                return set;
+            }
+        };
+
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(self.config.max_concurrent));
+        let retry_budget = Arc::new(AtomicUsize::new(self.config.retry_budget));
+        for item in items {
+            set.spawn(self.clone().download_tracked(
+                item.clone(),
+                Arc::clone(&semaphore),
+                manifest.clone(),
+                Arc::clone(&retry_budget),
+            ));
+        }
+        set
+    }
+
+    /// 加载配置中的下载清单（如果有），失败时返回错误信息
+    ///
+    /// 错误信息会复制到每个媒体项的结果中，因此这里返回 `String` 而不是 `MediaError`
+    async fn load_manifest(&self) -> std::result::Result<Option<Arc<DownloadManifest>>, String> {
+        match &self.config.manifest_path {
+            Some(path) => match DownloadManifest::load(path).await {
+                Ok(manifest) => Ok(Some(Arc::new(manifest))),
+                Err(e) => Err(format!("无法加载下载清单: {}", e)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// 在并发限制下下载单个媒体项，并与下载清单同步
+    ///
+    /// 传输层失败与错误状态分别按 `transport_retries` 和 `status_retries` 重试，
+    /// 每次重试都会从整批共享的 `retry_budget` 中扣除一次
+    async fn download_tracked(
+        self,
+        item: MediaItem,
+        semaphore: Arc<tokio::sync::Semaphore>,
+        manifest: Option<Arc<DownloadManifest>>,
+        retry_budget: Arc<AtomicUsize>,
+    ) -> Result<String> {
+        if let Some(entry) = manifest
+            .as_ref()
+            .and_then(|m| m.get(&item.provider, &item.id))
+        {
+            return Ok(entry.path.clone());
+        }
+
+        let _permit = semaphore.acquire().await.unwrap();
+        let (mut transport_retries, mut status_retries) = (0, 0);
+        let path = loop {
+            let e = match self.download_item(&item).await {
+                Ok(path) => break path,
+                Err(e) => e,
+            };
+            let (policy, retries) = match RetryKind::of(&e) {
+                Some(RetryKind::Transport) => {
+                    (self.config.transport_retries, &mut transport_retries)
+                }
+                Some(RetryKind::Status) => (self.config.status_retries, &mut status_retries),
+                None => return Err(e),
+            };
+            if *retries >= policy.max_retries || !Self::take_retry(&retry_budget) {
+                return Err(e);
+            }
+            *retries += 1;
+            warn!("下载 {} 失败，第 {} 次重试: {}", item.id, retries, e);
+            // 服务器给出 `Retry-After` 时至少等待该时长
+            let delay = e
+                .retry_after()
+                .map_or(policy.delay, |d| d.max(policy.delay));
+            tokio::time::sleep(delay).await;
+        };
+
+        if manifest.is_some() || self.config.write_sidecars {
+            let mut entry = ManifestEntry {
+                id: item.id.clone(),
+                provider: item.provider.clone(),
+                media_type: item.media_type.clone(),
+                path: path.clone(),
+                bytes: tokio::fs::metadata(&path).await?.len(),
+                sha256: None,
+            };
+            if let Some(manifest) = &manifest {
+                manifest.append(&entry).await?;
+            }
+            if self.config.write_sidecars {
+                entry.sha256 = Some(sha256_file(Path::new(&path)).await?);
+                write_sidecar(Path::new(&path), &entry).await?;
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// 从共享的重试预算中扣除一次，预算已耗尽时返回 `false`
+    fn take_retry(budget: &AtomicUsize) -> bool {
+        budget
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
     /// Download multiple items with overall batch progress callback
+    ///
+    /// Results keep the input order, as with [`MediaDownloader::download_items`]
     pub async fn download_items_with_batch_progress<F>(
         &self,
         items: &[MediaItem],
@@ -389,55 +1528,295 @@ impl MediaDownloader {
 
         config.progress_callback = Some(Arc::new(move |progress: DownloadProgress| {
             let mut batch = batch_progress_clone.lock().unwrap();
+            batch.update(progress);
+            batch_callback_clone(batch.clone());
+        }));
 
-            // Update item progress
-            if let Some(pos) = batch
-                .item_progress
-                .iter()
-                .position(|p| p.item_id == progress.item_id)
-            {
-                batch.item_progress[pos] = progress.clone();
-            } else {
-                batch.item_progress.push(progress.clone());
+        let downloader_with_callback = MediaDownloader {
+            providers: self.providers.clone(),
+            config,
+            http_client: self.http_client.clone(),
+            search_cache: self.search_cache.clone(),
+            temp_dir: self.temp_dir.clone(),
+            circuit_breaker: Arc::clone(&self.circuit_breaker),
+        };
+
+        downloader_with_callback.download_items(items).await
+    }
+
+    /// 批量下载多个媒体项，并在整批结束后调用一次 `on_complete`
+    ///
+    /// 与进度回调不同，`on_complete` 只在所有媒体项都结束（成功或失败）后收到一份 [`BatchSummary`]，
+    /// 适合服务端在整批完成时发送通知。返回结果与 [`download_items`](Self::download_items) 相同
+    pub async fn download_items_with_completion<F>(
+        &self,
+        items: &[MediaItem],
+        on_complete: F,
+    ) -> Vec<Result<String>>
+    where
+        F: FnOnce(BatchSummary),
+    {
+        let started = Instant::now();
+        let results = self.download_items(items).await;
+
+        let mut summary = BatchSummary {
+            total: items.len(),
+            ..Default::default()
+        };
+        for result in &results {
+            match result {
+                Ok(path) => {
+                    summary.succeeded += 1;
+                    // 文件可能已被调用方移走，此时不计入字节数
+                    if let Ok(metadata) = tokio::fs::metadata(path).await {
+                        summary.total_bytes += metadata.len();
+                    }
+                    summary.paths.push(path.clone());
+                }
+                Err(_) => summary.failed += 1,
             }
+        }
+        summary.duration = started.elapsed();
+
+        on_complete(summary);
+        results
+    }
 
-            // Update counters
-            match progress.state {
-                DownloadState::Downloading => {
-                    batch.downloading_items = batch
-                        .item_progress
-                        .iter()
-                        .filter(|p| matches!(p.state, DownloadState::Downloading))
-                        .count();
+    /// 导出一组媒体项的署名信息，供法务审核等用途
+    ///
+    /// 每个媒体项一行（或一个 JSON 对象），字段依次为
+    /// `id, provider, author, author_url, source_url, license, attribution, local_path`。
+    /// `license` 来自 [`MediaItem::license`]，未知提供商为空；
+    /// `attribution` 来自 [`MediaItem::attribution_text`]；
+    /// `local_path` 是 [`download_item`](Self::download_item) 为该媒体项写入的路径
+    pub async fn export_attributions(
+        &self,
+        items: &[MediaItem],
+        format: ExportFormat,
+        out: &Path,
+    ) -> Result<()> {
+        let records: Vec<_> = items
+            .iter()
+            .map(|item| AttributionRecord {
+                id: &item.id,
+                provider: &item.provider,
+                author: &item.author,
+                author_url: &item.author_url,
+                source_url: &item.source_url,
+                license: item.license().unwrap_or_default(),
+                attribution: item.attribution_text(),
+                local_path: self.output_path(item).to_string_lossy().to_string(),
+            })
+            .collect();
+
+        let contents = match format {
+            ExportFormat::Csv => {
+                let mut csv = AttributionRecord::CSV_HEADER.join(",");
+                csv.push('\n');
+                for record in &records {
+                    csv.push_str(&record.csv_row());
+                    csv.push('\n');
                 }
-                DownloadState::Completed => {
-                    batch.completed_items = batch
-                        .item_progress
-                        .iter()
-                        .filter(|p| matches!(p.state, DownloadState::Completed))
-                        .count();
+                csv
+            }
+            ExportFormat::Json => serde_json::to_string_pretty(&records)
+                .map_err(|e| MediaError::DownloadError(format!("无法序列化署名信息: {}", e)))?,
+        };
+
+        tokio::fs::write(out, contents).await?;
+        Ok(())
+    }
+
+    /// 估算一组媒体项的总下载大小
+    ///
+    /// 以 `max_concurrent` 为并发上限，对每个媒体项按质量偏好选出的 URL 发送 `HEAD` 请求，
+    /// 累加 `Content-Length`。
+    ///
+    /// # 返回
+    ///
+    /// `(已知总字节数, 未知大小的媒体项数量)`，请求失败或没有 `Content-Length` 的媒体项计入未知
+    pub async fn estimate_total_size(&self, items: &[MediaItem]) -> (u64, usize) {
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(
+            self.config.max_concurrent.max(1),
+        ));
+
+        let futures: Vec<_> = items
+            .iter()
+            .map(|item| {
+                let semaphore = Arc::clone(&semaphore);
+
+                async move {
+                    let (image_quality, video_quality) = self.quality_for(item);
+                    let url =
+                        self.rewrite_url(item.preferred_url(image_quality, video_quality).ok()?);
+
+                    let _permit = semaphore.acquire().await.ok()?;
+                    let response = self.http_client.head(&url).send().await.ok()?;
+                    if !response.status().is_success() {
+                        return None;
+                    }
+
+                    // HEAD 响应没有响应体，不能使用 `content_length()`，需直接读取响应头
+                    response
+                        .headers()
+                        .get(reqwest::header::CONTENT_LENGTH)?
+                        .to_str()
+                        .ok()?
+                        .parse::<u64>()
+                        .ok()
                 }
-                DownloadState::Failed(_) => {
-                    batch.failed_items = batch
-                        .item_progress
-                        .iter()
-                        .filter(|p| matches!(p.state, DownloadState::Failed(_)))
-                        .count();
+            })
+            .collect();
+
+        join_all(futures)
+            .await
+            .into_iter()
+            .fold((0, 0), |(total, unknown), size| match size {
+                Some(bytes) => (total + bytes, unknown),
+                None => (total, unknown + 1),
+            })
+    }
+
+    /// 校验目录中带附属记录的文件，并重新下载与记录不一致的文件
+    ///
+    /// 对 `dir` 中的每个 `.json` 附属记录（见 [`DownloadConfig::write_sidecars`]），
+    /// 比较对应文件的大小，以及记录了 SHA-256 时的哈希。文件缺失或不一致时，
+    /// 按记录中的提供商、ID 与媒体类型重新获取媒体项并下载到原位置，然后更新附属记录。
+    pub async fn verify_dir(&self, dir: &Path) -> Result<VerifyReport> {
+        let mut report = VerifyReport::default();
+
+        for (path, entry) in read_sidecars(dir).await? {
+            if matches_record(&path, &entry).await {
+                report.verified += 1;
+                continue;
+            }
+
+            match self.repair_file(&path, &entry).await {
+                Ok(()) => report.repaired += 1,
+                Err(e) => {
+                    error!("重新下载 {} 失败: {}", path.display(), e);
+                    report.failed += 1;
                 }
-                _ => {}
             }
+        }
 
-            batch.calculate_overall_percentage();
-            batch_callback_clone(batch.clone());
-        }));
+        Ok(report)
+    }
 
-        let downloader_with_callback = MediaDownloader {
-            providers: self.providers.clone(),
-            config,
-            http_client: self.http_client.clone(),
+    /// 重新下载附属记录对应的媒体项，并用新文件的大小与哈希更新附属记录
+    async fn repair_file(&self, path: &Path, entry: &ManifestEntry) -> Result<()> {
+        let provider = self
+            .providers
+            .iter()
+            .find(|p| p.name() == entry.provider)
+            .ok_or_else(|| MediaError::UnknownProvider(entry.provider.clone()))?;
+        let item = provider
+            .get_media(&entry.id, entry.media_type.clone())
+            .await?;
+
+        self.download_item_to_path(&item, path).await?;
+
+        let sha256 = match entry.sha256 {
+            Some(_) => Some(sha256_file(path).await?),
+            None => None,
         };
+        let repaired = ManifestEntry {
+            path: path.to_string_lossy().to_string(),
+            bytes: tokio::fs::metadata(path).await?.len(),
+            sha256,
+            ..entry.clone()
+        };
+        write_sidecar(path, &repaired).await
+    }
 
-        downloader_with_callback.download_items(items).await
+    /// 下载所有媒体项的缩略图并拼接为一张联系表（contact sheet）PNG 图片
+    ///
+    /// 每个单元格的尺寸取所有缩略图中最大的宽和高，缩略图放在单元格左上角。
+    /// 缩略图下载或解码失败的媒体项保留为空白单元格。
+    ///
+    /// # 参数
+    ///
+    /// * `items` - 媒体项
+    /// * `cols` - 每行的单元格数
+    /// * `out` - 输出 PNG 文件路径
+    #[cfg(feature = "contact-sheet")]
+    pub async fn contact_sheet(&self, items: &[MediaItem], cols: usize, out: &Path) -> Result<()> {
+        use image::{ImageFormat, Rgba, RgbaImage};
+
+        let cols = cols.max(1);
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(
+            self.config.max_concurrent.max(1),
+        ));
+
+        let futures: Vec<_> = items
+            .iter()
+            .map(|item| {
+                let semaphore = Arc::clone(&semaphore);
+
+                async move {
+                    let _permit = semaphore.acquire().await.ok()?;
+                    let response = self
+                        .http_client
+                        .get(self.rewrite_url(item.urls.thumbnail.clone()))
+                        .send()
+                        .await
+                        .ok()?;
+                    if !response.status().is_success() {
+                        return None;
+                    }
+                    let bytes = response.bytes().await.ok()?;
+                    image::load_from_memory(&bytes).ok()
+                }
+            })
+            .collect();
+
+        let thumbnails = join_all(futures).await;
+
+        let cell_width = thumbnails.iter().flatten().map(|t| t.width()).max();
+        let cell_height = thumbnails.iter().flatten().map(|t| t.height()).max();
+        let (Some(cell_width), Some(cell_height)) = (cell_width, cell_height) else {
+            return Err(MediaError::DownloadError("没有可用的缩略图".to_string()));
+        };
+
+        let rows = thumbnails.len().div_ceil(cols);
+        let mut sheet = RgbaImage::from_pixel(
+            cell_width * cols as u32,
+            cell_height * rows as u32,
+            Rgba([255, 255, 255, 255]),
+        );
+
+        for (index, thumbnail) in thumbnails.iter().enumerate() {
+            if let Some(thumbnail) = thumbnail {
+                let x = (index % cols) as i64 * cell_width as i64;
+                let y = (index / cols) as i64 * cell_height as i64;
+                image::imageops::overlay(&mut sheet, &thumbnail.to_rgba8(), x, y);
+            }
+        }
+
+        let mut png = std::io::Cursor::new(Vec::new());
+        sheet
+            .write_to(&mut png, ImageFormat::Png)
+            .map_err(|e| MediaError::DownloadError(format!("生成联系表失败: {}", e)))?;
+
+        if let Some(parent) = out.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(out, png.into_inner()).await?;
+
+        Ok(())
+    }
+
+    /// 检查是否有提供商能解析该 ID，不会下载媒体
+    ///
+    /// 依次调用各提供商的 `get_media`，任一提供商返回成功即为 `true`；
+    /// 与 [`download_by_id`](Self::download_by_id) 相同，提供商返回的错误视为未找到
+    pub async fn exists(&self, id: &str, media_type: MediaType) -> bool {
+        for provider in &self.providers {
+            if provider.get_media(id, media_type.clone()).await.is_ok() {
+                return true;
+            }
+        }
+        false
     }
 
     /// 根据 ID 下载媒体
@@ -463,6 +1842,56 @@ impl MediaDownloader {
         )))
     }
 
+    /// 根据 ID 批量下载媒体，结果与输入一一对应
+    ///
+    /// 与逐个调用 [`download_by_id`](Self::download_by_id) 相同，按提供商顺序查找每个 ID，
+    /// 但按提供商和媒体类型分组，通过 [`MediaProvider::get_media_batch`] 一次查询一组 ID
+    /// （例如 Pixabay 的多个 ID 合并为一次请求），前一个提供商找不到的 ID 再交给下一个提供商。
+    /// 找到的媒体项通过 [`download_items`](Self::download_items) 并发下载。
+    /// 所有提供商都找不到的 ID 返回最后一个提供商的错误
+    pub async fn download_by_ids(&self, ids: &[(String, MediaType)]) -> Vec<Result<String>> {
+        let mut lookups: Vec<Result<MediaItem>> = ids
+            .iter()
+            .map(|(id, _)| {
+                Err(MediaError::DownloadError(format!(
+                    "未找到 ID 为 {} 的媒体",
+                    id
+                )))
+            })
+            .collect();
+
+        for provider in &self.providers {
+            for media_type in [MediaType::Image, MediaType::Video] {
+                let pending: Vec<usize> = (0..ids.len())
+                    .filter(|&i| lookups[i].is_err() && ids[i].1 == media_type)
+                    .collect();
+                if pending.is_empty() {
+                    continue;
+                }
+
+                let batch: Vec<String> = pending.iter().map(|&i| ids[i].0.clone()).collect();
+                let results = provider.get_media_batch(&batch, media_type).await;
+                for (i, result) in pending.into_iter().zip(results) {
+                    lookups[i] = result;
+                }
+            }
+        }
+
+        let found: Vec<MediaItem> = lookups
+            .iter()
+            .filter_map(|lookup| lookup.as_ref().ok().cloned())
+            .collect();
+        let mut downloads = self.download_items(&found).await.into_iter();
+
+        lookups
+            .into_iter()
+            .map(|lookup| match lookup {
+                Ok(_) => downloads.next().expect("每个找到的媒体项都有下载结果"),
+                Err(e) => Err(e),
+            })
+            .collect()
+    }
+
     /// 批量下载媒体项
     pub async fn download_batch(
         &self,
@@ -501,66 +1930,26 @@ impl MediaDownloader {
         Ok(successful_downloads)
     }
 
-    /// 根据质量偏好获取图片 URL
-    fn get_image_url(&self, item: &MediaItem) -> Result<String> {
-        match self.config.image_quality {
-            ImageQuality::Thumbnail => Ok(item.urls.thumbnail.clone()),
-            ImageQuality::Medium => item
-                .urls
-                .medium
-                .clone()
-                .or_else(|| item.urls.large.clone())
-                .or_else(|| Some(item.urls.thumbnail.clone()))
-                .ok_or_else(|| MediaError::InvalidQuality("没有可用的中等质量".to_string())),
-            ImageQuality::Large => item
-                .urls
-                .large
-                .clone()
-                .or_else(|| item.urls.medium.clone())
-                .or_else(|| Some(item.urls.thumbnail.clone()))
-                .ok_or_else(|| MediaError::InvalidQuality("没有可用的大尺寸质量".to_string())),
-            ImageQuality::Original => item
-                .urls
-                .original
-                .clone()
-                .or_else(|| item.urls.large.clone())
-                .or_else(|| item.urls.medium.clone())
-                .or_else(|| Some(item.urls.thumbnail.clone()))
-                .ok_or_else(|| MediaError::InvalidQuality("没有可用的原始质量".to_string())),
-        }
-    }
-
-    /// 根据质量偏好获取视频 URL
-    fn get_video_url(&self, item: &MediaItem) -> Result<String> {
-        let video_files = item
-            .urls
-            .video_files
-            .as_ref()
-            .ok_or_else(|| MediaError::InvalidQuality("没有可用的视频文件".to_string()))?;
-
-        let quality_str = self.config.video_quality.as_str();
-
-        // 尝试查找精确的质量匹配
-        if let Some(file) = video_files.iter().find(|f| f.quality == quality_str) {
-            return Ok(file.url.clone());
-        }
+    /// 媒体项所用的 `(图片质量, 视频质量)`：优先使用其提供商的覆盖配置，否则使用全局配置
+    fn quality_for(&self, item: &MediaItem) -> (ImageQuality, VideoQuality) {
+        self.config
+            .provider_quality
+            .get(&item.provider)
+            .copied()
+            .unwrap_or((self.config.image_quality, self.config.video_quality))
+    }
 
-        // 尝试按分辨率查找
-        let min_width = self.config.video_quality.min_width();
-        if let Some(file) = video_files
-            .iter()
-            .filter(|f| f.width >= min_width)
-            .min_by_key(|f| f.width)
-        {
-            return Ok(file.url.clone());
+    /// 按配置的 `url_rewriter` 改写下载 URL，未配置时原样返回
+    fn rewrite_url(&self, url: String) -> String {
+        match &self.config.url_rewriter {
+            Some(rewrite) => rewrite(&url),
+            None => url,
         }
+    }
 
-        // 回退到最大可用
-        video_files
-            .iter()
-            .max_by_key(|f| f.width)
-            .map(|f| f.url.clone())
-            .ok_or_else(|| MediaError::InvalidQuality("未找到合适的视频质量".to_string()))
+    /// 媒体项在 `output_dir` 中的下载路径
+    fn output_path(&self, item: &MediaItem) -> PathBuf {
+        Path::new(&self.config.output_dir).join(self.generate_filename(item))
     }
 
     /// 为媒体项生成文件名
@@ -585,23 +1974,88 @@ impl MediaDownloader {
                 sanitized_title
             };
 
-            format!(
-                "{}_{}_{}.{}",
-                item.provider.to_lowercase(),
-                sanitized,
-                item.id,
-                extension
-            )
+            let prefix = format!("{}_", item.provider.to_lowercase());
+            let suffix = format!("_{}.{}", item.id, extension);
+            let budget = MAX_FILENAME_BYTES.saturating_sub(prefix.len() + suffix.len());
+            format!("{}{}{}", prefix, truncate_title(&sanitized, budget), suffix)
         }
     }
 }
 
-impl Clone for MediaDownloader {
+/// 使用提供商搜索，设置了 `params.offset` 时换算为页码并裁剪结果
+async fn search_provider(
+    provider: &dyn MediaProvider,
+    params: &SearchParams,
+) -> Result<SearchResult> {
+    let Some(offset) = params.offset else {
+        return provider.search_with_params(params).await;
+    };
+
+    let mut page_params = params.clone();
+    let mut per_page = params.limit.max(1);
+    page_params.page = offset / per_page + 1;
+    let mut result = provider.search_with_params(&page_params).await?;
+
+    // 提供商截断了 per_page 时，按实际生效的值重新换算页码
+    if result.per_page > 0 && result.per_page != per_page {
+        per_page = result.per_page;
+        let page = offset / per_page + 1;
+        if page != page_params.page {
+            page_params.page = page;
+            result = provider.search_with_params(&page_params).await?;
+        }
+    }
+
+    let skip = ((offset % per_page) as usize).min(result.items.len());
+    result.items.drain(..skip);
+
+    // 偏移量不在页首或 per_page 被截断时，剩余结果可能不足 limit 个，继续取后续页补足
+    while result.items.len() < params.limit as usize && page_params.page < result.total_pages {
+        page_params.page += 1;
+        let next = provider.search_with_params(&page_params).await?;
+        if next.items.is_empty() {
+            break;
+        }
+        result.items.extend(next.items);
+    }
+    result.items.truncate(params.limit as usize);
+    Ok(result)
+}
+
+/// 将标题截断到不超过 `max_bytes` 字节，截断时在字符边界处截断并追加省略号
+fn truncate_title(title: &str, max_bytes: usize) -> String {
+    if title.len() <= max_bytes {
+        return title.to_string();
+    }
+    let Some(mut end) = max_bytes.checked_sub(TITLE_ELLIPSIS.len()) else {
+        return String::new();
+    };
+    while !title.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &title[..end], TITLE_ELLIPSIS)
+}
+
+/// 确认可用空间能容纳 `needed` 字节的文件并保留 `margin` 字节余量
+#[cfg(feature = "disk-space")]
+fn ensure_free_space(available: u64, needed: u64, margin: u64) -> Result<()> {
+    if available < needed.saturating_add(margin) {
+        return Err(MediaError::DownloadError(
+            "insufficient disk space".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl Clone for MediaDownloader {
     fn clone(&self) -> Self {
         Self {
             providers: self.providers.clone(),
             config: self.config.clone(),
             http_client: self.http_client.clone(),
+            search_cache: self.search_cache.clone(),
+            temp_dir: self.temp_dir.clone(),
+            circuit_breaker: Arc::clone(&self.circuit_breaker),
         }
     }
 }
@@ -611,3 +2065,1773 @@ impl Default for MediaDownloader {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MediaMetadata, MediaUrls};
+    use futures::future::BoxFuture;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn image_item(id: &str, url: String) -> MediaItem {
+        MediaItem {
+            id: id.to_string(),
+            media_type: MediaType::Image,
+            title: format!("item{}", id),
+            description: String::new(),
+            tags: vec![],
+            author: "Alice".to_string(),
+            author_url: String::new(),
+            source_url: String::new(),
+            provider: "Pixabay".to_string(),
+            urls: MediaUrls {
+                thumbnail: url.clone(),
+                medium: None,
+                large: Some(url),
+                original: None,
+                video_files: None,
+            },
+            metadata: MediaMetadata {
+                width: 640,
+                height: 480,
+                size: None,
+                duration: None,
+                views: 0,
+                downloads: 0,
+                likes: 0,
+                avg_color: None,
+            },
+        }
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("fusion-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_manifest_skips_recorded_items_on_rerun() {
+        let server = MockServer::start().await;
+        // 第一个媒体项只允许被下载一次，重新运行时必须从清单中跳过
+        Mock::given(method("GET"))
+            .and(path("/1.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"first".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // 第二个媒体项第一次运行时失败，模拟下载中断
+        Mock::given(method("GET"))
+            .and(path("/2.jpg"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"second".to_vec()))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("manifest");
+        let manifest_path = dir.join("manifest.jsonl");
+        let config = DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            manifest_path: Some(manifest_path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let downloader = MediaDownloader::new().with_config(config);
+        let items = vec![
+            image_item("1", format!("{}/1.jpg", server.uri())),
+            image_item("2", format!("{}/2.jpg", server.uri())),
+        ];
+
+        let first_run = downloader.download_items(&items).await;
+        assert!(first_run[0].is_ok());
+        assert!(first_run[1].is_err());
+
+        let manifest = std::fs::read_to_string(&manifest_path).unwrap();
+        let entries: Vec<ManifestEntry> = manifest
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "1");
+        assert_eq!(entries[0].bytes, 5);
+
+        let second_run = downloader.download_items(&items).await;
+        assert_eq!(second_run[0].as_ref().unwrap(), &entries[0].path);
+        assert!(second_run[1].is_ok());
+
+        let manifest = std::fs::read_to_string(&manifest_path).unwrap();
+        assert_eq!(manifest.lines().count(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_verify_dir_repairs_truncated_file() {
+        let server = MockServer::start().await;
+        for name in ["1.jpg", "2.jpg"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{}", name)))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(b"0123456789".to_vec()))
+                .mount(&server)
+                .await;
+        }
+
+        let dir = temp_dir("verify");
+        let downloader = MediaDownloader::new()
+            .add_provider(Arc::new(refreshing_provider(format!(
+                "{}/2.jpg",
+                server.uri()
+            ))))
+            .with_config(DownloadConfig {
+                output_dir: dir.to_string_lossy().to_string(),
+                write_sidecars: true,
+                ..Default::default()
+            });
+        let items = vec![
+            image_item("1", format!("{}/1.jpg", server.uri())),
+            image_item("2", format!("{}/2.jpg", server.uri())),
+        ];
+        let paths: Vec<_> = downloader
+            .download_items(&items)
+            .await
+            .into_iter()
+            .map(|r| r.unwrap())
+            .collect();
+
+        // 截断第二个文件；第三个文件的提供商未配置，无法修复
+        std::fs::write(&paths[1], b"012").unwrap();
+        let orphan = dir.join("orphan_3.jpg");
+        std::fs::write(&orphan, b"012").unwrap();
+        write_sidecar(
+            &orphan,
+            &ManifestEntry {
+                id: "3".to_string(),
+                provider: "Missing".to_string(),
+                media_type: MediaType::Image,
+                path: orphan.to_string_lossy().to_string(),
+                bytes: 10,
+                sha256: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let report = downloader.verify_dir(&dir).await.unwrap();
+        assert_eq!(
+            report,
+            VerifyReport {
+                verified: 1,
+                repaired: 1,
+                failed: 1,
+            }
+        );
+        assert_eq!(std::fs::read(&paths[1]).unwrap(), b"0123456789");
+
+        let report = downloader.verify_dir(&dir).await.unwrap();
+        assert_eq!((report.verified, report.repaired), (2, 0));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_total_size() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/1.jpg"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "1000"))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/2.jpg"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "2500"))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/3.jpg"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let items = vec![
+            image_item("1", format!("{}/1.jpg", server.uri())),
+            image_item("2", format!("{}/2.jpg", server.uri())),
+            image_item("3", format!("{}/3.jpg", server.uri())),
+        ];
+
+        let (total, unknown) = MediaDownloader::new().estimate_total_size(&items).await;
+        assert_eq!(total, 3500);
+        assert_eq!(unknown, 1);
+    }
+
+    #[tokio::test]
+    async fn test_download_to_writer_streams_into_memory() {
+        let server = MockServer::start().await;
+        let body: Vec<u8> = (0..=255u8).cycle().take(64 * 1024).collect();
+        Mock::given(method("GET"))
+            .and(path("/original.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+            .mount(&server)
+            .await;
+
+        let mut item = image_item("1", format!("{}/large.jpg", server.uri()));
+        item.urls.original = Some(format!("{}/original.jpg", server.uri()));
+
+        let mut sink: Vec<u8> = Vec::new();
+        let written = MediaDownloader::new()
+            .download_to_writer(
+                &item,
+                Some((ImageQuality::Original, VideoQuality::Large)),
+                &mut sink,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(written, body.len() as u64);
+        assert_eq!(sink, body);
+    }
+
+    #[cfg(feature = "contact-sheet")]
+    #[tokio::test]
+    async fn test_contact_sheet_dimensions() {
+        use image::{ImageFormat, Rgba, RgbaImage};
+
+        fn png(width: u32, height: u32) -> Vec<u8> {
+            let mut bytes = std::io::Cursor::new(Vec::new());
+            RgbaImage::from_pixel(width, height, Rgba([255, 0, 0, 255]))
+                .write_to(&mut bytes, ImageFormat::Png)
+                .unwrap();
+            bytes.into_inner()
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/a.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(png(4, 3)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/b.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(png(2, 5)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/missing.png"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let items = vec![
+            image_item("a", format!("{}/a.png", server.uri())),
+            image_item("b", format!("{}/b.png", server.uri())),
+            image_item("c", format!("{}/missing.png", server.uri())),
+        ];
+
+        let dir = temp_dir("contact-sheet");
+        let out = dir.join("sheet.png");
+        MediaDownloader::new()
+            .contact_sheet(&items, 2, &out)
+            .await
+            .unwrap();
+
+        // 单元格为 4x5，两列两行（第三项缩略图缺失，保留为空白）
+        let sheet = image::open(&out).unwrap();
+        assert_eq!((sheet.width(), sheet.height()), (8, 10));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(feature = "contact-sheet")]
+    #[tokio::test]
+    async fn test_contact_sheet_uses_url_rewriter() {
+        use image::{ImageFormat, Rgba, RgbaImage};
+
+        let mut png = std::io::Cursor::new(Vec::new());
+        RgbaImage::from_pixel(3, 2, Rgba([0, 0, 255, 255]))
+            .write_to(&mut png, ImageFormat::Png)
+            .unwrap();
+
+        let mirror = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/pixabay/thumb.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(png.into_inner()))
+            .expect(1)
+            .mount(&mirror)
+            .await;
+
+        let mirror_uri = mirror.uri();
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            url_rewriter: Some(Arc::new(move |url: &str| {
+                url.replace(
+                    "https://pixabay.example",
+                    &format!("{}/pixabay", mirror_uri),
+                )
+            })),
+            ..Default::default()
+        });
+        let items = vec![image_item(
+            "a",
+            "https://pixabay.example/thumb.png".to_string(),
+        )];
+
+        let dir = temp_dir("contact-sheet-rewriter");
+        let out = dir.join("sheet.png");
+        downloader.contact_sheet(&items, 1, &out).await.unwrap();
+        let sheet = image::open(&out).unwrap();
+        assert_eq!((sheet.width(), sheet.height()), (3, 2));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// 模拟提供商的搜索函数，接收按 `name` 与 `total` 生成的默认结果页
+    type MockSearch =
+        Arc<dyn Fn(SearchResult) -> BoxFuture<'static, Result<SearchResult>> + Send + Sync>;
+    /// 模拟提供商的按 ID 查询函数
+    type MockGetMedia = Arc<dyn Fn(&str) -> Result<MediaItem> + Send + Sync>;
+
+    /// 可配置的模拟提供商
+    ///
+    /// 默认每次搜索返回只含一个媒体项（ID 为 `name`）的单页结果，`total` 为报告的总数；
+    /// 按 ID 查询总是成功。`on_search` 可修改或替换默认结果页，`on_get_media` 替换查询行为。
+    /// 图片与视频搜索行为相同，`calls` 记录两者的调用总次数
+    struct MockProvider {
+        name: &'static str,
+        total: u32,
+        rate_limit: Option<RateLimitInfo>,
+        calls: Arc<AtomicUsize>,
+        search: Option<MockSearch>,
+        get_media: Option<MockGetMedia>,
+    }
+
+    impl MockProvider {
+        fn new(name: &'static str) -> Self {
+            Self {
+                name,
+                total: 1,
+                rate_limit: None,
+                calls: Arc::new(AtomicUsize::new(0)),
+                search: None,
+                get_media: None,
+            }
+        }
+
+        fn on_search(
+            self,
+            search: impl Fn(SearchResult) -> Result<SearchResult> + Send + Sync + 'static,
+        ) -> Self {
+            self.on_search_async(move |page| futures::future::ready(search(page)))
+        }
+
+        fn on_search_async<F, Fut>(mut self, search: F) -> Self
+        where
+            F: Fn(SearchResult) -> Fut + Send + Sync + 'static,
+            Fut: std::future::Future<Output = Result<SearchResult>> + Send + 'static,
+        {
+            self.search = Some(Arc::new(move |page| Box::pin(search(page))));
+            self
+        }
+
+        fn on_get_media(
+            mut self,
+            get_media: impl Fn(&str) -> Result<MediaItem> + Send + Sync + 'static,
+        ) -> Self {
+            self.get_media = Some(Arc::new(get_media));
+            self
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl MediaProvider for MockProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn rate_limit(&self) -> Option<RateLimitInfo> {
+            self.rate_limit
+        }
+
+        async fn search_images(&self, _query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let default = SearchResult {
+                total: self.total,
+                total_hits: 1,
+                page,
+                per_page: limit,
+                total_pages: 1,
+                items: vec![image_item(self.name, String::new())],
+                provider: self.name.to_string(),
+                client_side_filtered: false,
+                duration: Duration::ZERO,
+            };
+            match &self.search {
+                Some(search) => search(default).await,
+                None => Ok(default),
+            }
+        }
+
+        async fn search_videos(&self, query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+            self.search_images(query, limit, page).await
+        }
+
+        async fn get_media(&self, id: &str, _media_type: MediaType) -> Result<MediaItem> {
+            match &self.get_media {
+                Some(get_media) => get_media(id),
+                None => Ok(image_item(id, String::new())),
+            }
+        }
+    }
+
+    fn counting_provider(name: &'static str) -> (Arc<dyn MediaProvider>, Arc<AtomicUsize>) {
+        let provider = MockProvider::new(name);
+        let calls = Arc::clone(&provider.calls);
+        (Arc::new(provider), calls)
+    }
+
+    /// 搜索与查询均返回 `HTTP 500` 的模拟提供商
+    fn failing_provider() -> MockProvider {
+        MockProvider::new("Failing")
+            .on_search(|_| Err(MediaError::DownloadError("HTTP 500".to_string())))
+            .on_get_media(|_| Err(MediaError::DownloadError("HTTP 500".to_string())))
+    }
+
+    /// 不支持搜索、按 ID 查询时返回指向 `url` 的媒体项的模拟提供商
+    fn refreshing_provider(url: String) -> MockProvider {
+        MockProvider::new("Pixabay")
+            .on_search(|_| Err(MediaError::DownloadError("not supported".to_string())))
+            .on_get_media(move |id| Ok(image_item(id, url.clone())))
+    }
+
+    /// 每次搜索耗时 50ms，并记录同时进行的搜索数及其峰值的模拟提供商
+    fn slow_provider(
+        name: &'static str,
+        in_flight: &Arc<AtomicUsize>,
+        peak: &Arc<AtomicUsize>,
+    ) -> MockProvider {
+        let in_flight = Arc::clone(in_flight);
+        let peak = Arc::clone(peak);
+        MockProvider::new(name).on_search_async(move |page| {
+            let in_flight = Arc::clone(&in_flight);
+            let peak = Arc::clone(&peak);
+            async move {
+                let running = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(running, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(page)
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_search_cache_skips_partial_results() {
+        let (healthy, healthy_calls) = counting_provider("Healthy");
+        let downloader = MediaDownloader::new()
+            .with_search_cache()
+            .add_provider(healthy)
+            .add_provider(Arc::new(failing_provider()));
+        let params = SearchParams::new("cats", MediaType::Image);
+
+        for _ in 0..2 {
+            let result = downloader.search(params.clone()).await.unwrap();
+            assert!(result.provider_errors.contains_key("Failing"));
+        }
+        assert_eq!(healthy_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_cache_limits() {
+        let (provider, calls) = counting_provider("Counting");
+        let downloader = MediaDownloader::new()
+            .with_search_cache_limits(2, Duration::from_secs(60))
+            .add_provider(provider);
+        let search = |query: &str| downloader.search(SearchParams::new(query, MediaType::Image));
+
+        search("a").await.unwrap();
+        search("b").await.unwrap();
+        search("c").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // 容量为 2，最早写入的 "a" 已被移除
+        search("c").await.unwrap();
+        search("b").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        search("a").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        let (provider, calls) = counting_provider("Counting");
+        let downloader = MediaDownloader::new()
+            .with_search_cache_limits(2, Duration::ZERO)
+            .add_provider(provider);
+        let params = SearchParams::new("cats", MediaType::Image);
+        downloader.search(params.clone()).await.unwrap();
+        downloader.search(params).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_cache_misses_after_provider_added() {
+        use std::sync::atomic::Ordering;
+
+        let (first, first_calls) = counting_provider("First");
+        let (second, second_calls) = counting_provider("Second");
+        let params = SearchParams::new("cats", MediaType::Image);
+
+        let downloader = MediaDownloader::new()
+            .with_search_cache()
+            .add_provider(first);
+        downloader.search(params.clone()).await.unwrap();
+        downloader.search(params.clone()).await.unwrap();
+        assert_eq!(first_calls.load(Ordering::SeqCst), 1);
+
+        let downloader = downloader.add_provider(second);
+        let result = downloader.search(params).await.unwrap();
+        assert_eq!(first_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(second_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(result.items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_temp_dir_removed_on_drop() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/1.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"temp".to_vec()))
+            .mount(&server)
+            .await;
+
+        let downloader = MediaDownloader::temp().unwrap();
+        let dir = downloader.temp_dir().unwrap().to_path_buf();
+
+        let item = image_item("1", format!("{}/1.jpg", server.uri()));
+        let file = downloader.download_item(&item).await.unwrap();
+        assert!(Path::new(&file).starts_with(&dir));
+        assert!(Path::new(&file).exists());
+
+        drop(downloader);
+        assert!(!dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_preflight_rejects_html_before_streaming() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/dead.jpg"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-type", "text/html"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/dead.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("preflight");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            preflight: true,
+            ..Default::default()
+        });
+
+        let item = image_item("1", format!("{}/dead.jpg", server.uri()));
+        let err = downloader.download_item(&item).await.unwrap_err();
+        assert!(matches!(err, MediaError::DownloadError(_)));
+        assert!(err.to_string().contains("text/html"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_require_attribution() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/1.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"img".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("attribution");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            require_attribution: true,
+            ..Default::default()
+        });
+
+        // 缺少 source_url，下载前即被拒绝
+        let unattributed = image_item("1", format!("{}/1.jpg", server.uri()));
+        let err = downloader.download_item(&unattributed).await.unwrap_err();
+        assert!(
+            matches!(err, MediaError::InvalidParameter(ref msg) if msg == "missing attribution")
+        );
+
+        let mut attributed = unattributed.clone();
+        attributed.source_url = "https://pixabay.com/photos/1/".to_string();
+        let file = downloader.download_item(&attributed).await.unwrap();
+        assert!(Path::new(&file).exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_download_items_results_match_input_order() {
+        let server = MockServer::start().await;
+        for (name, status) in [("a.jpg", 200), ("b.jpg", 404), ("c.jpg", 200)] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{}", name)))
+                .respond_with(ResponseTemplate::new(status).set_body_bytes(b"img".to_vec()))
+                .mount(&server)
+                .await;
+        }
+
+        let dir = temp_dir("ordering");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            ..Default::default()
+        });
+        let items = vec![
+            image_item("1", format!("{}/a.jpg", server.uri())),
+            image_item("2", format!("{}/b.jpg", server.uri())),
+            image_item("3", format!("{}/c.jpg", server.uri())),
+        ];
+
+        for results in [
+            downloader.download_items(&items).await,
+            downloader
+                .download_items_with_batch_progress(&items, |_| {})
+                .await,
+        ] {
+            assert_eq!(results.len(), 3);
+            let expected = |i: usize| downloader.generate_filename(&items[i]);
+            assert!(results[0].as_ref().unwrap().ends_with(&expected(0)));
+            assert!(results[1].is_err());
+            assert!(results[2].as_ref().unwrap().ends_with(&expected(2)));
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_download_client_does_not_negotiate_compression() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"0123456789".to_vec()))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("no-compression");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            ..Default::default()
+        });
+        downloader
+            .download_item(&image_item("1", format!("{}/1.jpg", server.uri())))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.get("accept-encoding").is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_on_complete_fires_once_with_summary() {
+        let server = MockServer::start().await;
+        for (name, status) in [("ok.jpg", 200), ("missing.jpg", 404)] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{}", name)))
+                .respond_with(ResponseTemplate::new(status).set_body_bytes(b"image".to_vec()))
+                .mount(&server)
+                .await;
+        }
+
+        let dir = temp_dir("on-complete");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            ..Default::default()
+        });
+        let items = vec![
+            image_item("1", format!("{}/ok.jpg", server.uri())),
+            image_item("2", format!("{}/missing.jpg", server.uri())),
+        ];
+
+        let summaries = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&summaries);
+        let results = downloader
+            .download_items_with_completion(&items, move |summary| {
+                sink.lock().unwrap().push(summary)
+            })
+            .await;
+
+        let summaries = summaries.lock().unwrap();
+        assert_eq!(summaries.len(), 1);
+        let summary = &summaries[0];
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.total_bytes, 5);
+        assert_eq!(summary.paths, vec![results[0].as_ref().unwrap().clone()]);
+        assert!(results[1].is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_url_rewriter_redirects_download() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/pexels/photos/1.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"mirrored".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("url-rewriter");
+        let mirror = format!("{}/pexels", server.uri());
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            url_rewriter: Some(Arc::new(move |url: &str| {
+                url.replace("https://images.pexels.com", &mirror)
+            })),
+            ..Default::default()
+        });
+        let item = image_item("1", "https://images.pexels.com/photos/1.jpg".to_string());
+
+        let path = downloader.download_item(&item).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"mirrored");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_export_attributions() {
+        let dir = temp_dir("attributions");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            ..Default::default()
+        });
+        let mut second = image_item("2", "https://example.com/2.jpg".to_string());
+        second.author = "Doe, Jane".to_string();
+        let items = vec![
+            image_item("1", "https://example.com/1.jpg".to_string()),
+            second,
+        ];
+
+        let csv_path = dir.join("attributions.csv");
+        downloader
+            .export_attributions(&items, ExportFormat::Csv, &csv_path)
+            .await
+            .unwrap();
+        let csv = std::fs::read_to_string(&csv_path).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "id,provider,author,author_url,source_url,license,attribution,local_path"
+        );
+        assert_eq!(lines.len(), 3);
+        assert!(lines[2].starts_with("2,Pixabay,\"Doe, Jane\","));
+        assert!(lines[2].ends_with(&*downloader.output_path(&items[1]).to_string_lossy()));
+
+        let json_path = dir.join("attributions.json");
+        downloader
+            .export_attributions(&items, ExportFormat::Json, &json_path)
+            .await
+            .unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        let records = json.as_array().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["id"], "1");
+        assert_eq!(records[1]["author"], "Doe, Jane");
+        assert_eq!(records[0]["license"], items[0].license().unwrap());
+        assert_eq!(records[1]["attribution"], "Image by Doe, Jane from Pixabay");
+        assert_eq!(records[0].as_object().unwrap().len(), 8);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_download_by_ids_batches_pixabay_lookup() {
+        use crate::pixabay_provider::PixabayProvider;
+        use wiremock::matchers::query_param;
+
+        let server = MockServer::start().await;
+        let image = |id: u64| {
+            serde_json::json!({
+                "id": id,
+                "pageURL": format!("https://pixabay.com/photos/{id}/"),
+                "type": "photo",
+                "tags": "mountain",
+                "previewURL": format!("{}/files/{id}_preview.jpg", server.uri()),
+                "previewWidth": 150,
+                "previewHeight": 100,
+                "webformatURL": format!("{}/files/{id}_web.jpg", server.uri()),
+                "webformatWidth": 640,
+                "webformatHeight": 427,
+                "largeImageURL": format!("{}/files/{id}_large.jpg", server.uri()),
+                "imageWidth": 1920,
+                "imageHeight": 1280,
+                "imageSize": 1000,
+                "views": 1,
+                "downloads": 1,
+                "likes": 1,
+                "comments": 0,
+                "user_id": 1,
+                "user": "tester",
+                "userImageURL": ""
+            })
+        };
+        Mock::given(method("GET"))
+            .and(path("/api/"))
+            .and(query_param("id", "10,20,30"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total": 2,
+                "totalHits": 2,
+                "hits": [image(10), image(30)]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/10_large.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"ten".to_vec()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/30_large.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"thirty".to_vec()))
+            .mount(&server)
+            .await;
+
+        let client = pixabay_sdk::Pixabay::new("test_key".to_string())
+            .with_base_urls(
+                format!("{}/api/", server.uri()),
+                format!("{}/api/videos/", server.uri()),
+            )
+            .unwrap();
+        let dir = temp_dir("download-by-ids");
+        let downloader = MediaDownloader::new()
+            .add_provider(Arc::new(PixabayProvider::with_client(client)))
+            .with_config(DownloadConfig {
+                output_dir: dir.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+
+        let ids: Vec<_> = ["10", "20", "30"]
+            .into_iter()
+            .map(|id| (id.to_string(), MediaType::Image))
+            .collect();
+        let results = downloader.download_by_ids(&ids).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(std::fs::read(results[0].as_ref().unwrap()).unwrap(), b"ten");
+        assert!(results[1].is_err());
+        assert_eq!(
+            std::fs::read(results[2].as_ref().unwrap()).unwrap(),
+            b"thirty"
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_skips_failing_provider() {
+        use std::sync::atomic::Ordering;
+
+        let failing = failing_provider();
+        let failing_calls = Arc::clone(&failing.calls);
+        let (healthy, healthy_calls) = counting_provider("Healthy");
+
+        let downloader = MediaDownloader::new()
+            .with_config(DownloadConfig {
+                circuit_breaker: Some(CircuitBreakerConfig {
+                    failure_threshold: 2,
+                    window: std::time::Duration::from_secs(60),
+                    cooldown: std::time::Duration::from_secs(60),
+                }),
+                ..Default::default()
+            })
+            .add_provider(Arc::new(failing))
+            .add_provider(healthy);
+        let params = SearchParams::new("cats", MediaType::Image);
+
+        for _ in 0..2 {
+            let result = downloader.search(params.clone()).await.unwrap();
+            assert!(result.provider_errors["Failing"].contains("HTTP 500"));
+        }
+
+        // 达到阈值后熔断，冷却期内不再调用该提供商
+        for _ in 0..3 {
+            let result = downloader.search(params.clone()).await.unwrap();
+            assert_eq!(result.provider_errors["Failing"], "circuit open");
+        }
+        assert_eq!(failing_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(healthy_calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_reports_open_circuit_as_provider_unavailable() {
+        let downloader = MediaDownloader::new()
+            .with_config(DownloadConfig {
+                circuit_breaker: Some(CircuitBreakerConfig {
+                    failure_threshold: 1,
+                    window: Duration::from_secs(60),
+                    cooldown: Duration::from_secs(60),
+                }),
+                ..Default::default()
+            })
+            .add_provider(counting_provider("Healthy").0)
+            .add_provider(Arc::new(failing_provider()));
+        let params = SearchParams::new("cats", MediaType::Image);
+
+        downloader.search(params.clone()).await.unwrap();
+        assert!(matches!(
+            downloader.search_strict(params).await,
+            Err(MediaError::ProviderUnavailable(name)) if name == "Failing"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_scoped_to_provider_subset() {
+        use std::sync::atomic::Ordering;
+
+        let (pexels, pexels_calls) = counting_provider("Pexels");
+        let (pixabay, pixabay_calls) = counting_provider("Pixabay");
+        let downloader = MediaDownloader::new()
+            .add_provider(pexels)
+            .add_provider(pixabay);
+
+        let params =
+            SearchParams::new("cats", MediaType::Image).with_providers(["Pexels", "Unsplash"]);
+        let result = downloader.search(params).await.unwrap();
+
+        assert_eq!(pexels_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(pixabay_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(result.provider_results.len(), 1);
+        assert_eq!(result.provider_results[0].provider, "Pexels");
+        assert!(result.provider_errors.contains_key("Unsplash"));
+    }
+
+    #[test]
+    fn test_original_quality_falls_back_without_original() {
+        let mut item = image_item("1", "https://example.com/large.jpg".to_string());
+        assert!(!item.has_original());
+        assert_eq!(
+            item.preferred_url(ImageQuality::Original, VideoQuality::Large)
+                .unwrap(),
+            "https://example.com/large.jpg"
+        );
+
+        item.urls.original = Some("https://example.com/original.jpg".to_string());
+        assert!(item.has_original());
+        assert_eq!(
+            item.preferred_url(ImageQuality::Original, VideoQuality::Large)
+                .unwrap(),
+            "https://example.com/original.jpg"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spawn_downloads_abort_removes_partial_file() {
+        use std::time::Duration;
+        use tokio::net::TcpListener;
+
+        // 只发送一部分响应体，然后一直挂起
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 1000\r\n\r\npartial")
+                .await
+                .unwrap();
+            socket.flush().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+
+        let dir = temp_dir("spawn-abort");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            ..Default::default()
+        });
+        let item = image_item("1", format!("http://{}/1.jpg", addr));
+        let path = dir.join(downloader.generate_filename(&item));
+
+        let mut set = downloader.spawn_downloads(&[item]).await;
+
+        // 等待部分内容写入磁盘
+        for _ in 0..100 {
+            if std::fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(path.exists());
+
+        set.abort_all();
+        while let Some(result) = set.join_next().await {
+            assert!(result.unwrap_err().is_cancelled());
+        }
+        assert!(!path.exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_push_provider_mutates_in_place() {
+        let mut downloader = MediaDownloader::new();
+        assert!(downloader.providers().is_empty());
+
+        let (first, _) = counting_provider("First");
+        let (second, _) = counting_provider("Second");
+        downloader.push_provider(first);
+        downloader.push_provider(second);
+
+        let names: Vec<&str> = downloader.providers().iter().map(|p| p.name()).collect();
+        assert_eq!(names, vec!["First", "Second"]);
+    }
+
+    #[tokio::test]
+    async fn test_download_refetches_expired_url() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/expired.jpg"))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/fresh.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes("fresh"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("refetch-expired");
+        let downloader = MediaDownloader::new()
+            .add_provider(Arc::new(refreshing_provider(format!(
+                "{}/fresh.jpg",
+                server.uri()
+            ))))
+            .with_config(DownloadConfig {
+                output_dir: dir.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+
+        let item = image_item("1", format!("{}/expired.jpg", server.uri()));
+        let path = downloader.download_item(&item).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"fresh");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expired_url_without_provider_is_reported() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("expired-no-provider");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            ..Default::default()
+        });
+
+        let item = image_item("1", format!("{}/expired.jpg", server.uri()));
+        assert!(matches!(
+            downloader.download_item(&item).await,
+            Err(MediaError::UrlExpired(403))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_search_strict_fails_on_provider_error() {
+        let (working, _) = counting_provider("Working");
+        let failing = Arc::new(failing_provider());
+        let downloader = MediaDownloader::new()
+            .add_provider(working)
+            .add_provider(failing);
+        let params = SearchParams::new("cats", MediaType::Image);
+
+        let partial = downloader.search(params.clone()).await.unwrap();
+        assert_eq!(partial.items.len(), 1);
+        assert!(partial.provider_errors.contains_key("Failing"));
+
+        assert!(matches!(
+            downloader.search_strict(params).await,
+            Err(MediaError::DownloadError(msg)) if msg == "HTTP 500"
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_file_mode_applied_to_download() {
+        use std::os::unix::fs::PermissionsExt;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes("data"))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("file-mode");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            // 不含组/其他用户写权限，不受常见 umask 影响
+            file_mode: Some(0o640),
+            ..Default::default()
+        });
+
+        let item = image_item("1", format!("{}/1.jpg", server.uri()));
+        let path = downloader.download_item(&item).await.unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_next_page_fills_search_cache() {
+        use std::sync::atomic::Ordering;
+
+        let (provider, calls) = counting_provider("Paged");
+        let downloader = MediaDownloader::new()
+            .add_provider(provider)
+            .with_search_cache();
+        let params = SearchParams::new("cats", MediaType::Image);
+
+        let mut first = downloader.search(params.clone()).await.unwrap();
+        assert!(downloader.prefetch_next_page(&params, &first).is_none());
+
+        // 模拟提供商只报告一页，这里假设还有下一页
+        first.provider_results[0].total_pages = 2;
+        let handle = downloader.prefetch_next_page(&params, &first).unwrap();
+        handle.await.unwrap().unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let second = downloader.search(params.page(2)).await.unwrap();
+        assert_eq!(second.page, 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_next_page_downloads_thumbnails() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/thumb/2.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"thumb2".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // 共两页，每页结果的缩略图指向 `/thumb/<页码>.jpg`
+        let base = server.uri();
+        let thumbnails = MockProvider::new("Thumbs").on_search(move |mut page| {
+            page.items[0].id = page.page.to_string();
+            page.items[0].urls.thumbnail = format!("{}/thumb/{}.jpg", base, page.page);
+            page.total_pages = 2;
+            Ok(page)
+        });
+
+        let dir = temp_dir("prefetch_thumbnails");
+        let downloader = MediaDownloader::new()
+            .with_config(DownloadConfig {
+                output_dir: dir.to_string_lossy().into_owned(),
+                prefetch_thumbnails: true,
+                ..Default::default()
+            })
+            .add_provider(Arc::new(thumbnails))
+            .with_search_cache();
+        let params = SearchParams::new("cats", MediaType::Image);
+
+        let first = downloader.search(params.clone()).await.unwrap();
+        let second = downloader
+            .prefetch_next_page(&params, &first)
+            .unwrap()
+            .await
+            .unwrap()
+            .unwrap();
+
+        let thumbnail = downloader.thumbnail_path(&second.items[0]);
+        assert!(thumbnail.starts_with(dir.join("thumbnails")));
+        assert_eq!(std::fs::read(&thumbnail).unwrap(), b"thumb2");
+
+        // 缩略图已存在时再次预取不会重复请求
+        downloader
+            .prefetch_next_page(&params, &first)
+            .unwrap()
+            .await
+            .unwrap()
+            .unwrap();
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_search_rejects_empty_query() {
+        use std::sync::atomic::Ordering;
+
+        let (provider, calls) = counting_provider("Counting");
+        let downloader = MediaDownloader::new().add_provider(provider);
+        for query in ["", "  \n"] {
+            assert!(matches!(
+                downloader.search(SearchParams::new(query, MediaType::Image)).await,
+                Err(MediaError::InvalidParameter(msg)) if msg == "empty query"
+            ));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_search_params_from_pixabay_image_params() {
+        let pixabay = pixabay_sdk::SearchImageParams::new()
+            .query("mountains")
+            .per_page(50)
+            .page(3)
+            .min_width(1920)
+            .orientation(pixabay_sdk::Orientation::Horizontal)
+            .category(pixabay_sdk::Category::Nature)
+            .safesearch(true);
+
+        let params = SearchParams::from(pixabay);
+        assert_eq!(params.query, "mountains");
+        assert_eq!(params.limit, 50);
+        assert_eq!(params.page, 3);
+        assert_eq!(params.media_type, MediaType::Image);
+        assert_eq!(params.min_width, Some(1920));
+        assert_eq!(params.min_height, None);
+        assert_eq!(params.providers, None);
+    }
+
+    #[test]
+    fn test_search_params_from_pixabay_video_params_uses_defaults() {
+        let pixabay = pixabay_sdk::SearchVideoParams::new()
+            .query("waves")
+            .video_type(pixabay_sdk::VideoType::Film);
+
+        let params = SearchParams::from(pixabay);
+        let defaults = SearchParams::new("waves", MediaType::Video);
+        assert_eq!(params.query, "waves");
+        assert_eq!(params.media_type, MediaType::Video);
+        assert_eq!(params.limit, defaults.limit);
+        assert_eq!(params.page, defaults.page);
+    }
+
+    #[tokio::test]
+    async fn test_search_all_types_shares_concurrency_limit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let first = slow_provider("First", &in_flight, &peak);
+        let second = slow_provider("Second", &in_flight, &peak);
+        let calls = [Arc::clone(&first.calls), Arc::clone(&second.calls)];
+        let downloader = MediaDownloader::new()
+            .with_config(DownloadConfig {
+                max_concurrent_searches: Some(2),
+                ..Default::default()
+            })
+            .add_provider(Arc::new(first))
+            .add_provider(Arc::new(second));
+
+        let (images, videos) = downloader
+            .search_all_types(SearchParams::new("cats", MediaType::Image))
+            .await
+            .unwrap();
+
+        assert_eq!(images.items.len(), 2);
+        assert_eq!(videos.items.len(), 2);
+        let calls: usize = calls.iter().map(|c| c.load(Ordering::SeqCst)).sum();
+        assert_eq!(calls, 4);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_set_output_dir_applies_to_next_download() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/1.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"image".to_vec()))
+            .mount(&server)
+            .await;
+        let first_dir = temp_dir("set-output-first");
+        let second_dir = temp_dir("set-output-second");
+        let item = image_item("1", format!("{}/1.jpg", server.uri()));
+
+        let mut downloader = MediaDownloader::new()
+            .with_config(DownloadConfig {
+                output_dir: first_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            })
+            .add_provider(counting_provider("First").0);
+        let first = downloader.download_item(&item).await.unwrap();
+        assert!(Path::new(&first).starts_with(&first_dir));
+
+        downloader.set_output_dir(second_dir.to_string_lossy());
+        let second = downloader.download_item(&item).await.unwrap();
+        assert!(Path::new(&second).starts_with(&second_dir));
+        assert!(Path::new(&second).exists());
+        assert_eq!(downloader.providers().len(), 1);
+
+        let _ = std::fs::remove_dir_all(&first_dir);
+        let _ = std::fs::remove_dir_all(&second_dir);
+    }
+
+    #[tokio::test]
+    async fn test_exists_checks_all_providers() {
+        let downloader = MediaDownloader::new()
+            .add_provider(Arc::new(failing_provider()))
+            .add_provider(Arc::new(
+                MockProvider::new("Known")
+                    .on_search(|_| Err(MediaError::InvalidParameter("not supported".to_string())))
+                    .on_get_media(|id| match id {
+                        "42" => Ok(image_item(id, String::new())),
+                        _ => Err(MediaError::DownloadError("HTTP 404".to_string())),
+                    }),
+            ));
+
+        assert!(downloader.exists("42", MediaType::Image).await);
+        assert!(!downloader.exists("43", MediaType::Image).await);
+        assert!(!MediaDownloader::new().exists("42", MediaType::Image).await);
+    }
+
+    #[tokio::test]
+    async fn test_max_per_provider_balances_merged_items() {
+        // 每页返回 10 个结果，报告共 100 个结果、10 页
+        let many_items = |name: &'static str| {
+            Arc::new(MockProvider::new(name).on_search(move |mut page| {
+                page.items = (0..10)
+                    .map(|i| image_item(&format!("{}-{}", name, i), String::new()))
+                    .collect();
+                page.total = 100;
+                page.total_hits = 100;
+                page.total_pages = 10;
+                Ok(page)
+            }))
+        };
+        let downloader = MediaDownloader::new()
+            .add_provider(many_items("First"))
+            .add_provider(many_items("Second"));
+
+        let result = downloader
+            .search(SearchParams::new("cats", MediaType::Image).max_per_provider(3))
+            .await
+            .unwrap();
+
+        assert_eq!(result.items.len(), 6);
+        for name in ["First", "Second"] {
+            let count = result
+                .items
+                .iter()
+                .filter(|item| item.id.starts_with(name))
+                .count();
+            assert_eq!(count, 3);
+        }
+        assert_eq!(result.total, 200);
+        assert_eq!(result.total_hits, 200);
+    }
+
+    #[tokio::test]
+    async fn test_search_records_provider_durations() {
+        let sleep = Duration::from_millis(50);
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let slow = Arc::new(slow_provider("Slow", &in_flight, &peak));
+        let downloader = MediaDownloader::new()
+            .add_provider(slow)
+            .add_provider(counting_provider("Fast").0);
+
+        let result = downloader
+            .search(SearchParams::new("cats", MediaType::Image))
+            .await
+            .unwrap();
+
+        let slow_result = result
+            .provider_results
+            .iter()
+            .find(|r| r.provider == "Slow")
+            .unwrap();
+        assert!(slow_result.duration >= sleep);
+        assert!(result.search_duration >= slow_result.duration);
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_shared_across_batch() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("retry-budget");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            status_retries: RetryPolicy::new(3, Duration::ZERO),
+            retry_budget: 2,
+            ..Default::default()
+        });
+        let items: Vec<_> = (0..4)
+            .map(|i| image_item(&i.to_string(), format!("{}/{}.jpg", server.uri(), i)))
+            .collect();
+
+        let results = downloader.download_items(&items).await;
+        assert!(results.iter().all(|r| r.is_err()));
+        // 每个媒体项各请求一次，外加整批共享的 2 次重试
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), items.len() + 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_duplicate_provider_names_are_ignored() {
+        use crate::pixabay_provider::PixabayProvider;
+
+        let downloader = MediaDownloader::new()
+            .add_provider(Arc::new(PixabayProvider::new("first_key".to_string())))
+            .add_provider(Arc::new(PixabayProvider::new("second_key".to_string())))
+            .add_provider_by_name_and_apikey("pixabay", "third_key");
+
+        assert_eq!(downloader.providers().len(), 1);
+        assert_eq!(downloader.providers()[0].name(), "Pixabay");
+    }
+
+    #[tokio::test]
+    async fn test_provider_quality_overrides_global_quality() {
+        let server = MockServer::start().await;
+        for name in ["original.jpg", "medium.jpg"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{}", name)))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(b"img".to_vec()))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let with_urls = |id: &str, provider: &str| {
+            let mut item = image_item(id, format!("{}/large.jpg", server.uri()));
+            item.provider = provider.to_string();
+            item.urls.medium = Some(format!("{}/medium.jpg", server.uri()));
+            item.urls.original = Some(format!("{}/original.jpg", server.uri()));
+            item
+        };
+
+        let dir = temp_dir("provider-quality");
+        let downloader = MediaDownloader::new()
+            .with_config(DownloadConfig {
+                output_dir: dir.to_string_lossy().to_string(),
+                ..Default::default()
+            })
+            .with_provider_config("Pexels", ImageQuality::Original, VideoQuality::Large)
+            .with_provider_config("Pixabay", ImageQuality::Medium, VideoQuality::Medium);
+
+        let items = vec![with_urls("1", "Pexels"), with_urls("2", "Pixabay")];
+        let results = downloader.download_items(&items).await;
+        assert!(results.iter().all(|r| r.is_ok()));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(feature = "disk-space")]
+    #[tokio::test]
+    async fn test_free_space_check_aborts_download() {
+        assert!(ensure_free_space(10_000, 4_096, 1_024).is_ok());
+        assert!(matches!(
+            ensure_free_space(1_024, 4_096, 0),
+            Err(MediaError::DownloadError(msg)) if msg == "insufficient disk space"
+        ));
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/big.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 4096]))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("free-space");
+        // 余量大于任何真实磁盘，预检必然失败
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            min_free_bytes: Some(u64::MAX / 2),
+            ..Default::default()
+        });
+        let item = image_item("1", format!("{}/big.jpg", server.uri()));
+
+        let err = downloader.download_item(&item).await.unwrap_err();
+        assert!(matches!(err, MediaError::DownloadError(msg) if msg == "insufficient disk space"));
+        assert!(std::fs::read_dir(&dir).unwrap().next().is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_progress_callbacks_respect_interval() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/big.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 512 * 1024]))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("progress-interval");
+        let item = image_item("1", format!("{}/big.jpg", server.uri()));
+        for interval in [Duration::from_millis(50), Duration::ZERO] {
+            let seen = Arc::new(Mutex::new(Vec::new()));
+            let recorder = Arc::clone(&seen);
+            let downloader = MediaDownloader::new().with_config(DownloadConfig {
+                output_dir: dir.to_string_lossy().to_string(),
+                progress_interval: interval,
+                progress_callback: Some(Arc::new(move |progress: DownloadProgress| {
+                    if progress.state == DownloadState::Downloading {
+                        recorder.lock().unwrap().push(Instant::now());
+                    }
+                })),
+                ..Default::default()
+            });
+            downloader.download_item(&item).await.unwrap();
+
+            let seen = seen.lock().unwrap();
+            if interval.is_zero() {
+                // 开始下载的通知之后，每个数据块都会回调
+                assert!(seen.len() >= 2);
+            } else {
+                assert!(seen.windows(2).all(|w| w[1] - w[0] >= interval));
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_count_sums_provider_totals() {
+        let limits = Arc::new(Mutex::new(Vec::new()));
+        let provider = |name, total| {
+            let limits = Arc::clone(&limits);
+            Arc::new(
+                MockProvider {
+                    total,
+                    ..MockProvider::new(name)
+                }
+                .on_search(move |page| {
+                    limits.lock().unwrap().push(page.per_page);
+                    Ok(page)
+                }),
+            )
+        };
+        let downloader = MediaDownloader::new()
+            .add_provider(provider("First", 1234))
+            .add_provider(provider("Second", 56));
+
+        let params = SearchParams::new("cats", MediaType::Image).per_page(80);
+        assert_eq!(downloader.count(params).await.unwrap(), 1290);
+        assert_eq!(*limits.lock().unwrap(), vec![3, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_custom_pool_settings_download() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/pooled.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"pooled".to_vec()))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("pool-settings");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            max_concurrent: 3,
+            pool_max_idle_per_host: 6,
+            pool_idle_timeout: Some(Duration::from_secs(30)),
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            ..Default::default()
+        });
+        assert_eq!(downloader.config().pool_max_idle_per_host, 6);
+
+        let items: Vec<_> = (1..=3)
+            .map(|i| image_item(&i.to_string(), format!("{}/pooled.jpg", server.uri())))
+            .collect();
+        let results = downloader.download_items(&items).await;
+        for result in results {
+            let path = result.unwrap();
+            assert_eq!(std::fs::read(path).unwrap(), b"pooled");
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rate_limits_collects_provider_state() {
+        let info = RateLimitInfo {
+            limit: 100,
+            remaining: 42,
+            reset_secs: 30,
+        };
+        let downloader = MediaDownloader::new()
+            .add_provider(Arc::new(MockProvider {
+                rate_limit: Some(info),
+                ..MockProvider::new("Pixabay")
+            }))
+            .add_provider(Arc::new(MockProvider::new("Silent")));
+
+        let limits = downloader.rate_limits();
+        assert_eq!(limits.len(), 1);
+        assert_eq!(limits["Pixabay"], info);
+    }
+
+    #[test]
+    fn test_long_titles_are_truncated_to_filename_limit() {
+        let downloader = MediaDownloader::new();
+        for title in ["a".repeat(300), "山".repeat(300)] {
+            let mut item = image_item("123456", String::new());
+            item.title = title;
+
+            let filename = downloader.generate_filename(&item);
+            assert!(filename.len() <= MAX_FILENAME_BYTES, "{}", filename.len());
+            assert!(filename.starts_with("pixabay_"));
+            assert!(filename.ends_with(&format!("{}_123456.jpg", TITLE_ELLIPSIS)));
+        }
+
+        let mut item = image_item("1", String::new());
+        item.title = "short".to_string();
+        assert_eq!(downloader.generate_filename(&item), "pixabay_short_1.jpg");
+    }
+
+    #[tokio::test]
+    async fn test_offset_starts_at_global_position() {
+        // 第二轮中提供商把 per_page 截断为 4，需要按实际值换算页码并跨多页补足
+        for max_per_page in [100, 4] {
+            // 按全局序号分页返回共 50 个结果，每页最多 max_per_page 个
+            let provider = MockProvider::new("Sequence").on_search(move |mut page| {
+                let total = 50;
+                page.per_page = page.per_page.min(max_per_page);
+                let start = (page.page - 1) * page.per_page;
+                let end = (start + page.per_page).min(total);
+                page.items = (start..end)
+                    .map(|i| image_item(&i.to_string(), String::new()))
+                    .collect();
+                page.total = total;
+                page.total_hits = total;
+                page.total_pages = total.div_ceil(page.per_page);
+                Ok(page)
+            });
+            let downloader = MediaDownloader::new().add_provider(Arc::new(provider));
+
+            let result = downloader
+                .search(
+                    SearchParams::new("cats", MediaType::Image)
+                        .offset(5)
+                        .limit(10),
+                )
+                .await
+                .unwrap();
+            let ids: Vec<_> = result.items.iter().map(|i| i.id.as_str()).collect();
+            let expected: Vec<String> = (5..15).map(|i| i.to_string()).collect();
+            assert_eq!(ids, expected, "max_per_page = {}", max_per_page);
+        }
+    }
+
+    /// 接受连接后立即关闭的 TCP 服务，返回其地址和已接受的连接数
+    async fn resetting_server() -> (String, Arc<AtomicUsize>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&accepted);
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                drop(socket);
+            }
+        });
+        (format!("http://{}", addr), accepted)
+    }
+
+    #[tokio::test]
+    async fn test_transport_failures_use_transport_retry_policy() {
+        let (uri, accepted) = resetting_server().await;
+
+        let dir = temp_dir("transport-retry");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            transport_retries: RetryPolicy::new(2, Duration::from_millis(10)),
+            status_retries: RetryPolicy::new(5, Duration::from_secs(60)),
+            ..Default::default()
+        });
+        let items = vec![image_item("1", format!("{}/1.jpg", uri))];
+
+        let results = downloader.download_items(&items).await;
+        assert!(matches!(results[0], Err(MediaError::HttpError(_))));
+        // 首次请求加 2 次传输层重试
+        assert_eq!(accepted.load(Ordering::SeqCst), 3);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses_use_status_retry_policy() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("status-retry");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            transport_retries: RetryPolicy::new(5, Duration::from_secs(60)),
+            status_retries: RetryPolicy::new(1, Duration::from_millis(10)),
+            ..Default::default()
+        });
+        let items = vec![image_item("1", format!("{}/1.jpg", server.uri()))];
+
+        let results = downloader.download_items(&items).await;
+        assert!(matches!(results[0], Err(MediaError::HttpStatus(503, None))));
+        // 首次请求加 1 次状态码重试
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_status_retry_honors_retry_after() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"image".to_vec()))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("retry-after");
+        let downloader = MediaDownloader::new().with_config(DownloadConfig {
+            output_dir: dir.to_string_lossy().to_string(),
+            status_retries: RetryPolicy::new(1, Duration::from_millis(10)),
+            ..Default::default()
+        });
+        let items = vec![image_item("1", format!("{}/1.jpg", server.uri()))];
+
+        let started = Instant::now();
+        let results = downloader.download_items(&items).await;
+        assert!(results[0].is_ok());
+        assert!(started.elapsed() >= Duration::from_secs(1));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
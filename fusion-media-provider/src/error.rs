@@ -1,6 +1,7 @@
 /*!
 错误处理模块 - 定义多媒体下载过程中可能出现的错误类型。
 */
+use std::time::Duration;
 use thiserror::Error;
 
 /// 多媒体下载错误枚举
@@ -39,7 +40,120 @@ pub enum MediaError {
 
     #[error("该提供商未启用")]
     ProviderNotEnabled(String),
+
+    #[error("提供商 {0} 已熔断，无法完成搜索")]
+    ProviderUnavailable(String),
+
+    #[error("无效的参数: {0}")]
+    InvalidParameter(String),
+
+    #[error("无法合并搜索结果: {0}")]
+    MergeError(String),
+
+    #[error("HTTP {0}: 媒体链接已失效或无权访问")]
+    UrlExpired(u16),
+
+    #[error("HTTP 404: 媒体不存在")]
+    NotFound,
+
+    #[error("HTTP {0}: 认证失败")]
+    AuthError(u16),
+
+    /// 附带响应的 `Retry-After`（如有）
+    #[error("HTTP 429: 请求过于频繁，已被限流")]
+    RateLimit(Option<Duration>),
+
+    /// 状态码以及响应的 `Retry-After`（如有，常见于 503）
+    #[error("HTTP {0}: 下载失败")]
+    HttpStatus(u16, Option<Duration>),
 }
 
 /// 操作结果类型别名
 pub type Result<T> = std::result::Result<T, MediaError>;
+
+/// 将下载请求的失败状态码映射为对应的 [`MediaError`]
+///
+/// 404 → `NotFound`，401 → `AuthError`，403 → `UrlExpired`（签名链接过期），
+/// 429 → `RateLimit`，其余状态 → `HttpStatus`；后两者会带上响应头中的 `Retry-After`
+pub(crate) fn map_download_status(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> MediaError {
+    match status {
+        reqwest::StatusCode::NOT_FOUND => MediaError::NotFound,
+        reqwest::StatusCode::UNAUTHORIZED => MediaError::AuthError(status.as_u16()),
+        reqwest::StatusCode::FORBIDDEN => MediaError::UrlExpired(status.as_u16()),
+        reqwest::StatusCode::TOO_MANY_REQUESTS => MediaError::RateLimit(retry_after(headers)),
+        status => MediaError::HttpStatus(status.as_u16(), retry_after(headers)),
+    }
+}
+
+/// 解析 `Retry-After` 响应头（秒数），缺失或为 HTTP 日期格式时返回 `None`
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let secs = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(secs))
+}
+
+impl MediaError {
+    /// 服务器通过 `Retry-After` 要求的最短重试等待时间
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            MediaError::RateLimit(retry_after) | MediaError::HttpStatus(_, retry_after) => {
+                *retry_after
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    use reqwest::StatusCode;
+
+    #[test]
+    fn test_map_download_status() {
+        let headers = HeaderMap::new();
+        assert!(matches!(
+            map_download_status(StatusCode::NOT_FOUND, &headers),
+            MediaError::NotFound
+        ));
+        assert!(matches!(
+            map_download_status(StatusCode::UNAUTHORIZED, &headers),
+            MediaError::AuthError(401)
+        ));
+        assert!(matches!(
+            map_download_status(StatusCode::TOO_MANY_REQUESTS, &headers),
+            MediaError::RateLimit(None)
+        ));
+        assert!(matches!(
+            map_download_status(StatusCode::INTERNAL_SERVER_ERROR, &headers),
+            MediaError::HttpStatus(500, None)
+        ));
+    }
+
+    #[test]
+    fn test_map_download_status_keeps_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        let error = map_download_status(StatusCode::TOO_MANY_REQUESTS, &headers);
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(7)));
+        let error = map_download_status(StatusCode::SERVICE_UNAVAILABLE, &headers);
+        assert!(matches!(error, MediaError::HttpStatus(503, Some(_))));
+
+        // HTTP 日期格式不解析
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2026 07:28:00 GMT"),
+        );
+        let error = map_download_status(StatusCode::TOO_MANY_REQUESTS, &headers);
+        assert_eq!(error.retry_after(), None);
+    }
+}
@@ -1,22 +1,29 @@
 /*!
 Poly Media Downloader - 多媒体下载库，支持从多个提供商（Pexels, Pixabay）搜索和下载图片及视频。
 */
+mod circuit_breaker;
 mod create_provider;
 mod downloader;
 mod error;
+mod manifest;
 mod media_provider;
 mod models;
+#[cfg(feature = "pexels")]
 mod pexels_provider;
 mod pixabay_provider;
 
-pub use downloader::{DownloadConfig, MediaDownloader, SearchParams};
+pub use circuit_breaker::CircuitBreakerConfig;
+pub use create_provider::{create_provider, create_provider_str, ProviderKind};
+pub use downloader::{DownloadConfig, MediaDownloader, RetryPolicy, SearchParams, UrlRewriter};
 pub use error::{MediaError, Result};
+pub use manifest::{ManifestEntry, VerifyReport};
 pub use models::{
-    AggregatedSearchResult, BatchDownloadProgress, DownloadProgress, DownloadState, ImageQuality,
-    MediaItem, MediaMetadata, MediaType, MediaUrls, ProgressCallback, SearchResult, VideoFile,
-    VideoQuality,
+    AggregatedSearchResult, BatchDownloadProgress, BatchSummary, DownloadProgress, DownloadState,
+    ExportFormat, ImageQuality, MediaItem, MediaMetadata, MediaType, MediaUrls, Orientation,
+    ProgressCallback, RateLimitInfo, SearchResult, SortOrder, VideoFile, VideoQuality,
 };
 pub use pixabay_provider::PixabayProvider;
+pub use pixabay_sdk::Color as PixabayColor;
 
 #[cfg(feature = "pexels")]
 pub use pexels_provider::PexelsProvider;
@@ -5,10 +5,11 @@ Poly Media Downloader CLI - 多媒体下载命令行工具。
 use clap::{Parser, Subcommand};
 use dotenvy::dotenv;
 use fusion_media_provider::{
-    DownloadConfig, DownloadProgress, MediaDownloader, MediaItem, MediaType, ProgressCallback,
-    SearchParams,
+    DownloadConfig, DownloadProgress, ImageQuality, MediaDownloader, MediaError, MediaItem,
+    MediaType, ProgressCallback, ProviderKind, SearchParams, SortOrder, VideoQuality,
 };
 use std::env;
+use std::path::Path;
 use std::sync::Arc;
 
 /// CLI 配置结构体
@@ -29,7 +30,7 @@ enum Commands {
         #[arg(short, long)]
         query: String,
 
-        /// 媒体类型 (image 或 video)
+        /// 媒体类型 (image 或 video，也接受 images/photo/photos、videos/clip/clips)
         #[arg(short, long, default_value = "image")]
         media_type: String,
 
@@ -40,6 +41,10 @@ enum Commands {
         /// 页码
         #[arg(long, default_value = "1")]
         page: u32,
+
+        /// 排序方式 (relevance, likes, downloads, views, duration)
+        #[arg(long, default_value = "relevance")]
+        sort: SortOrder,
     },
 
     /// 从指定提供商搜索媒体
@@ -52,7 +57,7 @@ enum Commands {
         #[arg(short, long)]
         query: String,
 
-        /// 媒体类型 (image 或 video)
+        /// 媒体类型 (image 或 video，也接受 images/photo/photos、videos/clip/clips)
         #[arg(short, long, default_value = "image")]
         media_type: String,
 
@@ -63,6 +68,10 @@ enum Commands {
         /// 页码
         #[arg(long, default_value = "1")]
         page: u32,
+
+        /// 排序方式 (relevance, likes, downloads, views, duration)
+        #[arg(long, default_value = "relevance")]
+        sort: SortOrder,
     },
 
     /// 下载指定媒体
@@ -71,13 +80,21 @@ enum Commands {
         #[arg(short, long)]
         id: String,
 
-        /// 媒体类型 (image 或 video)
+        /// 媒体类型 (image 或 video，也接受 images/photo/photos、videos/clip/clips)
         #[arg(short, long, default_value = "image")]
         media_type: String,
 
         /// 提供商名称 (可选，如果指定则只从该提供商下载)
         #[arg(short, long)]
         provider: Option<String>,
+
+        /// 图片质量 (thumbnail, medium, large, original)
+        #[arg(long, default_value = "large")]
+        image_quality: ImageQuality,
+
+        /// 视频质量 (tiny, small, medium, large, original)
+        #[arg(long, default_value = "large")]
+        video_quality: VideoQuality,
     },
 
     /// 批量下载搜索结果中的媒体
@@ -86,7 +103,7 @@ enum Commands {
         #[arg(short, long)]
         query: String,
 
-        /// 媒体类型 (image 或 video)
+        /// 媒体类型 (image 或 video，也接受 images/photo/photos、videos/clip/clips)
         #[arg(short, long, default_value = "image")]
         media_type: String,
 
@@ -101,6 +118,25 @@ enum Commands {
         /// 输出目录
         #[arg(short, long, default_value = "./downloads")]
         output_dir: String,
+
+        /// 图片质量 (thumbnail, medium, large, original)
+        #[arg(long, default_value = "large")]
+        image_quality: ImageQuality,
+
+        /// 视频质量 (tiny, small, medium, large, original)
+        #[arg(long, default_value = "large")]
+        video_quality: VideoQuality,
+
+        /// 在每个文件旁写入 `.json` 附属记录，供 verify 命令校验
+        #[arg(long)]
+        sidecars: bool,
+    },
+
+    /// 校验下载目录中的文件完整性，并重新下载与附属记录不一致的文件
+    Verify {
+        /// 下载目录（读取其中的 `.json` 附属记录）
+        #[arg(short, long, default_value = "./downloads")]
+        dir: String,
     },
 
     /// 列出所有已配置的提供商
@@ -108,6 +144,17 @@ enum Commands {
     ListProviders,
 }
 
+/// 确定实际使用的排序方式
+///
+/// 图片没有时长信息，按时长排序时回退到相关性排序并打印警告
+fn resolve_sort(sort: SortOrder, media_type: &MediaType) -> SortOrder {
+    if sort == SortOrder::Duration && *media_type == MediaType::Image {
+        eprintln!("警告: 图片没有时长信息，已回退到按相关性排序");
+        return SortOrder::Relevance;
+    }
+    sort
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 从 .env 文件加载环境变量
@@ -125,9 +172,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             media_type,
             per_page,
             page,
+            sort,
         } => {
             // 解析媒体类型
-            let media_type: MediaType = media_type.parse().unwrap_or(MediaType::Image);
+            let media_type: MediaType = media_type.parse()?;
+            let sort = resolve_sort(sort, &media_type);
 
             // 创建搜索参数
             let params = SearchParams::new(query, media_type)
@@ -135,7 +184,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .page(page);
 
             // 执行搜索
-            let result = downloader.search(params).await?;
+            let mut result = downloader.search(params).await?;
+            result.sort_by(sort);
 
             // 打印结果
             println!("总共找到 {} 个结果", result.total);
@@ -159,9 +209,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             media_type,
             per_page,
             page,
+            sort,
         } => {
             // 解析媒体类型
-            let media_type: MediaType = media_type.parse().unwrap_or(MediaType::Image);
+            let media_type: MediaType = media_type.parse()?;
+            let sort = resolve_sort(sort, &media_type);
 
             // 创建搜索参数
             let params = SearchParams::new(query, media_type)
@@ -169,7 +221,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .page(page);
 
             // 从指定提供商搜索
-            let result = downloader.search_from_provider(&provider, params).await?;
+            let mut result = downloader.search_from_provider(&provider, params).await?;
+            result.sort_by(sort);
 
             // 打印结果
             println!("提供商: {}", result.provider);
@@ -190,13 +243,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             id,
             media_type,
             provider,
+            image_quality,
+            video_quality,
         } => {
             // 解析媒体类型
-            let media_type: MediaType = media_type.parse().unwrap_or(MediaType::Image);
+            let media_type: MediaType = media_type.parse()?;
+
+            let mut downloader = downloader;
+            downloader.set_image_quality(image_quality);
+            downloader.set_video_quality(video_quality);
 
             // 如果指定了提供商，添加该提供商
             let downloader = match provider {
                 Some(p) => {
+                    // 未编译进当前构建的提供商（如关闭 `pexels` 特性时）直接报错，而不是要求设置 API 密钥
+                    let kind: ProviderKind = p.parse()?;
+                    if !kind.is_enabled() {
+                        return Err(MediaError::ProviderNotEnabled(format!(
+                            "{} feature is not enabled",
+                            kind
+                        ))
+                        .into());
+                    }
                     let api_key = env::var(format!("{}_API_KEY", p.to_uppercase()))
                         .unwrap_or_else(|_| panic!("请设置 {}_API_KEY 环境变量", p.to_uppercase()));
                     downloader.add_provider_by_name_and_apikey(&p, &api_key)
@@ -215,14 +283,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             per_page,
             limit,
             output_dir,
+            image_quality,
+            video_quality,
+            sidecars,
         } => {
             // 解析媒体类型
-            let media_type: MediaType = media_type.parse().unwrap_or(MediaType::Image);
+            let media_type: MediaType = media_type.parse()?;
 
             // 配置下载器
             let config = DownloadConfig {
                 output_dir,
+                image_quality,
+                video_quality,
                 max_concurrent: 3,
+                write_sidecars: sidecars,
                 ..Default::default()
             };
             let downloader = downloader.with_config(config);
@@ -260,6 +334,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
+        Commands::Verify { dir } => {
+            let dir = Path::new(&dir);
+            if !dir.is_dir() {
+                return Err(format!("未找到下载目录: {}", dir.display()).into());
+            }
+
+            let report = downloader.verify_dir(dir).await?;
+
+            println!("校验完成:");
+            println!("  完好: {}", report.verified);
+            println!("  已修复: {}", report.repaired);
+            println!("  失败: {}", report.failed);
+        }
+
         Commands::ListProviders => {
             let providers = downloader.providers();
             if providers.is_empty() {
@@ -275,3 +363,123 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusion_media_provider::{MediaMetadata, MediaUrls, SearchResult};
+    use std::time::Duration;
+
+    fn item_with_likes(id: &str, likes: u32) -> MediaItem {
+        MediaItem {
+            id: id.to_string(),
+            media_type: MediaType::Image,
+            title: format!("item {}", id),
+            description: String::new(),
+            tags: vec![],
+            author: "Alice".to_string(),
+            author_url: String::new(),
+            source_url: format!("https://example.com/{}", id),
+            provider: "Pixabay".to_string(),
+            urls: MediaUrls {
+                thumbnail: format!("https://example.com/{}/thumb.jpg", id),
+                medium: None,
+                large: None,
+                original: None,
+                video_files: None,
+            },
+            metadata: MediaMetadata {
+                width: 640,
+                height: 480,
+                size: None,
+                duration: None,
+                views: 0,
+                downloads: 0,
+                likes,
+                avg_color: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_sort_by_likes() {
+        let mut result = SearchResult {
+            total: 3,
+            total_hits: 3,
+            page: 1,
+            per_page: 20,
+            total_pages: 1,
+            items: vec![
+                item_with_likes("a", 1),
+                item_with_likes("b", 30),
+                item_with_likes("c", 7),
+            ],
+            provider: "Pixabay".to_string(),
+            client_side_filtered: false,
+            duration: Duration::ZERO,
+        };
+
+        result.sort_by(resolve_sort(SortOrder::Likes, &MediaType::Image));
+        let ids: Vec<&str> = result.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_duration_sort_falls_back_for_images() {
+        assert_eq!(
+            resolve_sort(SortOrder::Duration, &MediaType::Image),
+            SortOrder::Relevance
+        );
+        assert_eq!(
+            resolve_sort(SortOrder::Duration, &MediaType::Video),
+            SortOrder::Duration
+        );
+    }
+
+    #[test]
+    fn test_quality_flags_parse_into_enums() {
+        for (arg, expected) in [
+            ("thumbnail", ImageQuality::Thumbnail),
+            ("medium", ImageQuality::Medium),
+            ("large", ImageQuality::Large),
+            ("original", ImageQuality::Original),
+        ] {
+            let cli = Cli::try_parse_from([
+                "fusion",
+                "download-search",
+                "-q",
+                "cat",
+                "--image-quality",
+                arg,
+            ])
+            .unwrap();
+            let Commands::DownloadSearch { image_quality, .. } = cli.command else {
+                panic!("expected download-search");
+            };
+            assert_eq!(image_quality, expected);
+        }
+
+        for (arg, expected) in [
+            ("tiny", VideoQuality::Tiny),
+            ("small", VideoQuality::Small),
+            ("medium", VideoQuality::Medium),
+            ("large", VideoQuality::Large),
+            ("original", VideoQuality::Original),
+        ] {
+            let cli =
+                Cli::try_parse_from(["fusion", "download", "-i", "1", "--video-quality", arg])
+                    .unwrap();
+            let Commands::Download { video_quality, .. } = cli.command else {
+                panic!("expected download");
+            };
+            assert_eq!(video_quality, expected);
+        }
+
+        let err = Cli::try_parse_from(["fusion", "download", "-i", "1", "--image-quality", "huge"])
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .contains("thumbnail, medium, large, original"));
+    }
+}
@@ -0,0 +1,264 @@
+/*!
+下载清单模块 - 以 JSON Lines 格式记录已完成的下载，用于中断后续传；
+以及每个文件旁的 `.json` 附属记录（sidecar），用于校验与修复已下载的文件。
+*/
+use crate::error::Result;
+use crate::models::MediaType;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs::OpenOptions;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+/// 附属记录文件的扩展名，追加在媒体文件名之后（如 `photo.jpg.json`）
+const SIDECAR_EXTENSION: &str = "json";
+
+/// 清单中的一条记录，对应一个已成功下载的媒体项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// 媒体 ID
+    pub id: String,
+    /// 提供商名称
+    pub provider: String,
+    /// 媒体类型，重新下载时用于向提供商获取媒体项
+    pub media_type: MediaType,
+    /// 本地文件路径
+    pub path: String,
+    /// 文件大小（字节）
+    pub bytes: u64,
+    /// 文件内容的 SHA-256（小写十六进制），仅附属记录中写入
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// 下载完整性校验结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// 大小（以及记录了哈希时的哈希）与附属记录一致的文件数
+    pub verified: usize,
+    /// 不一致且已重新下载的文件数
+    pub repaired: usize,
+    /// 不一致且重新下载失败的文件数
+    pub failed: usize,
+}
+
+/// 追加写入的下载清单
+///
+/// 以 `(provider, id)` 为键，而不是以文件路径为键
+pub(crate) struct DownloadManifest {
+    path: String,
+    entries: HashMap<(String, String), ManifestEntry>,
+    write_lock: Mutex<()>,
+}
+
+impl DownloadManifest {
+    /// 加载清单文件，文件不存在时返回空清单
+    ///
+    /// 每条记录都以换行结尾，因此没有换行结尾的最后一行是崩溃时写了一半的记录，
+    /// 会从文件中截掉，避免之后追加的记录与它拼在同一行。其他无法解析的行会被忽略
+    pub(crate) async fn load(path: &str) -> Result<Self> {
+        let mut entries = HashMap::new();
+
+        if Path::new(path).exists() {
+            let mut content = tokio::fs::read(path).await?;
+            if content.last().is_some_and(|&b| b != b'\n') {
+                let complete = content
+                    .iter()
+                    .rposition(|&b| b == b'\n')
+                    .map_or(0, |i| i + 1);
+                warn!(
+                    "截掉清单末尾不完整的记录（{} 字节）",
+                    content.len() - complete
+                );
+                content.truncate(complete);
+                OpenOptions::new()
+                    .write(true)
+                    .open(path)
+                    .await?
+                    .set_len(complete as u64)
+                    .await?;
+            }
+
+            let content = String::from_utf8_lossy(&content);
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                match serde_json::from_str::<ManifestEntry>(line) {
+                    Ok(entry) => {
+                        entries.insert((entry.provider.clone(), entry.id.clone()), entry);
+                    }
+                    Err(e) => warn!("忽略无法解析的清单记录: {}", e),
+                }
+            }
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            entries,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// 查找已记录的媒体项
+    pub(crate) fn get(&self, provider: &str, id: &str) -> Option<&ManifestEntry> {
+        self.entries.get(&(provider.to_string(), id.to_string()))
+    }
+
+    /// 追加一条记录并立即刷新到磁盘
+    pub(crate) async fn append(&self, entry: &ManifestEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+/// 媒体文件对应的附属记录路径
+pub(crate) fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(SIDECAR_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// 将记录写入 `path` 对应的附属记录文件
+pub(crate) async fn write_sidecar(path: &Path, entry: &ManifestEntry) -> Result<()> {
+    let json = serde_json::to_string_pretty(entry)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    tokio::fs::write(sidecar_path(path), json).await?;
+    Ok(())
+}
+
+/// 读取目录中的所有附属记录，返回 `(媒体文件路径, 记录)`，按路径排序
+///
+/// 媒体文件路径由附属记录的位置推导（去掉 `.json` 后缀），因此整个目录移动后仍可校验。
+/// 无法解析为记录的 `.json` 文件（例如导出的署名信息）会被忽略
+pub(crate) async fn read_sidecars(dir: &Path) -> Result<Vec<(PathBuf, ManifestEntry)>> {
+    let mut sidecars = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(dir_entry) = entries.next_entry().await? {
+        let sidecar = dir_entry.path();
+        if sidecar.extension().and_then(|e| e.to_str()) != Some(SIDECAR_EXTENSION) {
+            continue;
+        }
+        let content = tokio::fs::read_to_string(&sidecar).await?;
+        match serde_json::from_str::<ManifestEntry>(&content) {
+            Ok(entry) => sidecars.push((sidecar.with_extension(""), entry)),
+            Err(e) => warn!("忽略无法解析的附属记录 {}: {}", sidecar.display(), e),
+        }
+    }
+    sidecars.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(sidecars)
+}
+
+/// 检查磁盘文件是否与记录一致：大小相同，且记录了哈希时哈希也相同
+pub(crate) async fn matches_record(path: &Path, entry: &ManifestEntry) -> bool {
+    match tokio::fs::metadata(path).await {
+        Ok(metadata) if metadata.len() == entry.bytes => {}
+        _ => return false,
+    }
+    match &entry.sha256 {
+        Some(expected) => sha256_file(path)
+            .await
+            .is_ok_and(|actual| actual.eq_ignore_ascii_case(expected)),
+        None => true,
+    }
+}
+
+/// 计算文件内容的 SHA-256，返回小写十六进制字符串
+pub(crate) async fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sidecar_round_trip_detects_truncation_and_corruption() {
+        let dir = std::env::temp_dir().join(format!("fusion-sidecar-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let file = dir.join("pixabay_good_1.jpg");
+        std::fs::write(&file, b"0123456789").unwrap();
+        let entry = ManifestEntry {
+            id: "1".to_string(),
+            provider: "Pixabay".to_string(),
+            media_type: MediaType::Image,
+            path: file.to_string_lossy().to_string(),
+            bytes: 10,
+            sha256: Some(sha256_file(&file).await.unwrap()),
+        };
+        write_sidecar(&file, &entry).await.unwrap();
+        // 不是附属记录的 JSON 文件会被忽略
+        std::fs::write(dir.join("attributions.json"), b"[]").unwrap();
+
+        let sidecars = read_sidecars(&dir).await.unwrap();
+        assert_eq!(sidecars, vec![(file.clone(), entry.clone())]);
+        assert!(matches_record(&file, &entry).await);
+
+        // 大小相同但内容被破坏
+        std::fs::write(&file, b"9876543210").unwrap();
+        assert!(!matches_record(&file, &entry).await);
+
+        std::fs::write(&file, b"01234").unwrap();
+        assert!(!matches_record(&file, &entry).await);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_load_truncates_torn_last_line() {
+        let path =
+            std::env::temp_dir().join(format!("fusion-manifest-{}.jsonl", std::process::id()));
+        let entry = |id: &str| ManifestEntry {
+            id: id.to_string(),
+            provider: "Pixabay".to_string(),
+            media_type: MediaType::Image,
+            path: format!("pixabay_{}.jpg", id),
+            bytes: 10,
+            sha256: None,
+        };
+        let first = serde_json::to_string(&entry("1")).unwrap();
+        // 崩溃时只写了一半的记录，截断位置落在多字节字符中间
+        let torn = &"{\"id\":\"2\",\"path\":\"山".as_bytes()[..19];
+        std::fs::write(&path, [format!("{}\n", first).as_bytes(), torn].concat()).unwrap();
+
+        let path_str = path.to_string_lossy().to_string();
+        let manifest = DownloadManifest::load(&path_str).await.unwrap();
+        assert!(manifest.get("Pixabay", "1").is_some());
+        manifest.append(&entry("3")).await.unwrap();
+
+        let manifest = DownloadManifest::load(&path_str).await.unwrap();
+        assert!(manifest.get("Pixabay", "1").is_some());
+        assert!(manifest.get("Pixabay", "3").is_some());
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,6 +1,17 @@
-use crate::error::Result;
-use crate::models::{MediaItem, MediaType, SearchResult};
+use crate::downloader::SearchParams;
+use crate::error::{MediaError, Result};
+use crate::models::{MediaItem, MediaType, RateLimitInfo, SearchResult};
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+
+/// 将 `get_media` 的 ID 解析为数字
+///
+/// 照片和视频 ID 都是数字；非数字 ID（例如 Pexels 收藏集 ID）返回说明期望格式的错误
+pub(crate) fn parse_media_id(id: &str) -> Result<u64> {
+    id.trim().parse().map_err(|_| {
+        MediaError::InvalidParameter(format!("expected numeric photo/video ID, got '{}'", id))
+    })
+}
 
 /// 媒体提供商的 Trait（Pixabay, Pexels 等）
 #[async_trait]
@@ -14,6 +25,216 @@ pub trait MediaProvider: Send + Sync {
     /// 搜索视频
     async fn search_videos(&self, query: &str, limit: u32, page: u32) -> Result<SearchResult>;
 
+    /// 最近一次 API 响应中的速率限制状态
+    ///
+    /// 默认返回 `None`，表示提供商不报告速率限制或尚未发出请求
+    fn rate_limit(&self) -> Option<RateLimitInfo> {
+        None
+    }
+
     /// 通过 ID 获取媒体项
     async fn get_media(&self, id: &str, media_type: MediaType) -> Result<MediaItem>;
+
+    /// 通过 ID 列表批量获取媒体项
+    ///
+    /// 返回与输入顺序一一对应的结果。默认实现逐个调用 `get_media`；
+    /// 支持批量查询的提供商应覆盖此方法以减少请求数
+    async fn get_media_batch(
+        &self,
+        ids: &[String],
+        media_type: MediaType,
+    ) -> Vec<Result<MediaItem>> {
+        let mut results = Vec::with_capacity(ids.len());
+        for id in ids {
+            results.push(self.get_media(id, media_type.clone()).await);
+        }
+        results
+    }
+
+    /// 使用完整的搜索参数搜索
+    ///
+    /// 默认实现调用 `search_images`/`search_videos`，然后在客户端按
+    /// `min_width`/`min_height` 筛选已获取的结果（见 [`SearchResult::filter_min_size`]）。
+    /// 支持服务端筛选的提供商应覆盖此方法。
+    async fn search_with_params(&self, params: &SearchParams) -> Result<SearchResult> {
+        let mut result = match params.media_type {
+            MediaType::Image => {
+                self.search_images(&params.query, params.limit, params.page)
+                    .await?
+            }
+            MediaType::Video => {
+                self.search_videos(&params.query, params.limit, params.page)
+                    .await?
+            }
+        };
+        result.filter_min_size(params.min_width, params.min_height);
+        Ok(result)
+    }
+
+    /// 逐页搜索并以流的形式返回所有媒体项
+    ///
+    /// 默认实现从第 1 页开始重复调用 `search_images`/`search_videos`，
+    /// 直到到达 `total_pages` 或某页没有结果为止。请求失败时产出该错误并结束流。
+    /// 提供商可以覆盖此方法以使用更高效的分页方式。
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 搜索关键词
+    /// * `media_type` - 媒体类型
+    /// * `limit` - 每页结果数
+    fn search_all_pages<'a>(
+        &'a self,
+        query: &'a str,
+        media_type: MediaType,
+        limit: u32,
+    ) -> BoxStream<'a, Result<MediaItem>> {
+        stream::unfold(Some(1u32), move |page| {
+            let media_type = media_type.clone();
+            async move {
+                let page = page?;
+                let result = match media_type {
+                    MediaType::Image => self.search_images(query, limit, page).await,
+                    MediaType::Video => self.search_videos(query, limit, page).await,
+                };
+
+                match result {
+                    Ok(result) => {
+                        let next_page = if result.items.is_empty() || page >= result.total_pages {
+                            None
+                        } else {
+                            Some(page + 1)
+                        };
+                        let items: Vec<Result<MediaItem>> =
+                            result.items.into_iter().map(Ok).collect();
+                        Some((stream::iter(items), next_page))
+                    }
+                    Err(e) => Some((stream::iter(vec![Err(e)]), None)),
+                }
+            }
+        })
+        .flatten()
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MediaMetadata, MediaUrls};
+    use std::time::Duration;
+
+    /// 每页返回 `per_page` 个媒体项，共 `total` 个结果的模拟提供商
+    struct PagedProvider {
+        total: u32,
+    }
+
+    impl PagedProvider {
+        fn item(id: u32) -> MediaItem {
+            MediaItem {
+                id: id.to_string(),
+                media_type: MediaType::Image,
+                title: format!("item {}", id),
+                description: String::new(),
+                tags: vec![],
+                author: String::new(),
+                author_url: String::new(),
+                source_url: String::new(),
+                provider: "Mock".to_string(),
+                urls: MediaUrls {
+                    thumbnail: String::new(),
+                    medium: None,
+                    large: None,
+                    original: None,
+                    video_files: None,
+                },
+                metadata: MediaMetadata {
+                    width: 0,
+                    height: 0,
+                    size: None,
+                    duration: None,
+                    views: 0,
+                    downloads: 0,
+                    likes: 0,
+                    avg_color: None,
+                },
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MediaProvider for PagedProvider {
+        fn name(&self) -> &str {
+            "Mock"
+        }
+
+        async fn search_images(&self, _query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+            let start = (page - 1) * limit;
+            let end = (start + limit).min(self.total);
+            Ok(SearchResult {
+                total: self.total,
+                total_hits: self.total,
+                page,
+                per_page: limit,
+                total_pages: SearchResult::calculate_total_pages(self.total, limit),
+                items: (start..end).map(Self::item).collect(),
+                provider: "Mock".to_string(),
+                client_side_filtered: false,
+                duration: Duration::ZERO,
+            })
+        }
+
+        async fn search_videos(
+            &self,
+            _query: &str,
+            _limit: u32,
+            _page: u32,
+        ) -> Result<SearchResult> {
+            Err(MediaError::DownloadError("not supported".to_string()))
+        }
+
+        async fn get_media(&self, _id: &str, _media_type: MediaType) -> Result<MediaItem> {
+            Err(MediaError::DownloadError("not supported".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_all_pages_streams_two_pages() {
+        let provider = PagedProvider { total: 5 };
+        let ids: Vec<String> = provider
+            .search_all_pages("cats", MediaType::Image, 3)
+            .map(|item| item.unwrap().id)
+            .collect()
+            .await;
+        assert_eq!(ids, vec!["0", "1", "2", "3", "4"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_all_pages_stops_on_error() {
+        let provider = PagedProvider { total: 5 };
+        let results: Vec<Result<MediaItem>> = provider
+            .search_all_pages("cats", MediaType::Video, 3)
+            .collect()
+            .await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_with_params_filters_client_side() {
+        let provider = PagedProvider { total: 3 };
+        let params = SearchParams::new("cats", MediaType::Image)
+            .limit(3)
+            .min_width(1);
+
+        // 模拟提供商返回的媒体项宽度均为 0，全部低于下限
+        let result = provider.search_with_params(&params).await.unwrap();
+        assert!(result.items.is_empty());
+        assert_eq!(result.total_hits, 0);
+        assert!(result.client_side_filtered);
+
+        let params = SearchParams::new("cats", MediaType::Image).limit(3);
+        let result = provider.search_with_params(&params).await.unwrap();
+        assert_eq!(result.items.len(), 3);
+        assert!(!result.client_side_filtered);
+    }
 }
@@ -1,9 +1,16 @@
+use crate::create_provider::ProviderKind;
+use crate::downloader::SearchParams;
+use crate::error::MediaError;
+use log::warn;
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum MediaType {
     #[serde(rename = "image")]
@@ -11,12 +18,14 @@ pub enum MediaType {
     #[serde(rename = "video")]
     Video,
 }
+/// 不区分大小写，并接受常见的复数和同义写法：
+/// `image`/`images`/`photo`/`photos` 解析为 `Image`，`video`/`videos`/`clip`/`clips` 解析为 `Video`
 impl FromStr for MediaType {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "image" => Ok(MediaType::Image),
-            "video" => Ok(MediaType::Video),
+        match s.trim().to_lowercase().as_str() {
+            "image" | "images" | "photo" | "photos" => Ok(MediaType::Image),
+            "video" | "videos" | "clip" | "clips" => Ok(MediaType::Video),
             _ => Err(format!("Invalid media type: {}", s)),
         }
     }
@@ -53,6 +62,47 @@ pub struct MediaUrls {
     /// 对于视频：不同分辨率选项
     pub video_files: Option<Vec<VideoFile>>,
 }
+
+impl MediaUrls {
+    /// 实际有 URL 的图片质量，按从高到低排列
+    pub fn available_image_qualities(&self) -> Vec<ImageQuality> {
+        [
+            (ImageQuality::Original, self.original.is_some()),
+            (ImageQuality::Large, self.large.is_some()),
+            (ImageQuality::Medium, self.medium.is_some()),
+            (ImageQuality::Thumbnail, !self.thumbnail.is_empty()),
+        ]
+        .into_iter()
+        .filter_map(|(quality, available)| available.then_some(quality))
+        .collect()
+    }
+
+    /// 能够获得的视频质量，按从高到低排列
+    ///
+    /// 某个质量可用，是指存在以该质量标记的文件，或存在宽度不低于其
+    /// [`min_width`](VideoQuality::min_width) 的文件；只要有任何视频文件，`Original` 即可用
+    pub fn available_video_qualities(&self) -> Vec<VideoQuality> {
+        let files = match &self.video_files {
+            Some(files) if !files.is_empty() => files,
+            _ => return Vec::new(),
+        };
+
+        [
+            VideoQuality::Original,
+            VideoQuality::Large,
+            VideoQuality::Medium,
+            VideoQuality::Small,
+            VideoQuality::Tiny,
+        ]
+        .into_iter()
+        .filter(|quality| {
+            files
+                .iter()
+                .any(|f| f.quality == quality.as_str() || f.width >= quality.min_width())
+        })
+        .collect()
+    }
+}
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaMetadata {
@@ -63,6 +113,53 @@ pub struct MediaMetadata {
     pub views: u32,
     pub downloads: u32,
     pub likes: u32,
+    /// 主色调（如 `#7E7E7E`），目前仅 Pexels 提供
+    #[serde(default)]
+    pub avg_color: Option<String>,
+}
+
+impl MediaMetadata {
+    /// 百万像素数（`width * height / 1_000_000`），宽或高为 0 时返回 0.0
+    pub fn megapixels(&self) -> f64 {
+        self.width as f64 * self.height as f64 / 1_000_000.0
+    }
+
+    /// 宽高比（`width / height`），宽或高为 0 时返回 0.0 而不是 `inf`/`NaN`
+    pub fn aspect_ratio(&self) -> f64 {
+        if self.width == 0 || self.height == 0 {
+            return 0.0;
+        }
+        self.width as f64 / self.height as f64
+    }
+
+    /// 人类可读的文件大小，大小未知时返回 `None`
+    pub fn human_size(&self) -> Option<String> {
+        self.size.map(DownloadProgress::format_bytes)
+    }
+
+    /// 叠加在缩略图上的文字建议使用的颜色：`"black"` 或 `"white"`
+    ///
+    /// 按 WCAG 公式计算 `avg_color` 的相对亮度，选择与其对比度更高的颜色；
+    /// 没有主色调或格式无法解析（需为 `#RRGGBB`）时返回 `None`
+    pub fn suggested_text_color(&self) -> Option<&'static str> {
+        let hex = self.avg_color.as_deref()?.trim().strip_prefix('#')?;
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| -> Option<f64> {
+            let c = u8::from_str_radix(hex.get(i..i + 2)?, 16).ok()? as f64 / 255.0;
+            Some(if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            })
+        };
+        let luminance = 0.2126 * channel(0)? + 0.7152 * channel(2)? + 0.0722 * channel(4)?;
+
+        // 与黑色、白色的对比度分别为 (L + 0.05) / 0.05 和 1.05 / (L + 0.05)，
+        // 两者相等时 L ≈ 0.179
+        Some(if luminance > 0.179 { "black" } else { "white" })
+    }
 }
 /// 统一的媒体项，表示图片或视频
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,12 +178,259 @@ pub struct MediaItem {
     pub metadata: MediaMetadata,
 }
 
+impl MediaItem {
+    /// 是否提供原始质量的 URL
+    ///
+    /// Pixabay 仅在拥有完整 API 权限时返回原图地址，标准密钥下为 `None`
+    pub fn has_original(&self) -> bool {
+        self.urls.original.is_some()
+    }
+
+    /// 按质量偏好选择下载 URL，图片使用 `image_quality`，视频使用 `video_quality`
+    ///
+    /// 图片缺少所需质量时依次回退到较低质量（原图 → 大图 → 中图 → 缩略图）；
+    /// 视频依次尝试质量标签精确匹配、满足该质量最小宽度的最窄文件，最后回退到最宽的文件。
+    /// 与 [`MediaDownloader`](crate::MediaDownloader) 下载时的选择逻辑一致，
+    /// 便于使用自己的 HTTP 客户端下载
+    pub fn preferred_url(
+        &self,
+        image_quality: ImageQuality,
+        video_quality: VideoQuality,
+    ) -> Result<String, MediaError> {
+        match self.media_type {
+            MediaType::Image => self.image_url(image_quality),
+            MediaType::Video => self.video_url(video_quality),
+        }
+    }
+
+    /// 按指定图片质量获取 URL，缺失时依次回退到较低质量
+    fn image_url(&self, quality: ImageQuality) -> Result<String, MediaError> {
+        match quality {
+            ImageQuality::Thumbnail => Ok(self.urls.thumbnail.clone()),
+            ImageQuality::Medium => self
+                .urls
+                .medium
+                .clone()
+                .or_else(|| self.urls.large.clone())
+                .or_else(|| Some(self.urls.thumbnail.clone()))
+                .ok_or_else(|| MediaError::InvalidQuality("没有可用的中等质量".to_string())),
+            ImageQuality::Large => self
+                .urls
+                .large
+                .clone()
+                .or_else(|| self.urls.medium.clone())
+                .or_else(|| Some(self.urls.thumbnail.clone()))
+                .ok_or_else(|| MediaError::InvalidQuality("没有可用的大尺寸质量".to_string())),
+            ImageQuality::Original => {
+                if !self.has_original() {
+                    warn!(
+                        "{} 的媒体项 {} 没有原始质量 URL，已回退到较低质量",
+                        self.provider, self.id
+                    );
+                }
+                self.urls
+                    .original
+                    .clone()
+                    .or_else(|| self.urls.large.clone())
+                    .or_else(|| self.urls.medium.clone())
+                    .or_else(|| Some(self.urls.thumbnail.clone()))
+                    .ok_or_else(|| MediaError::InvalidQuality("没有可用的原始质量".to_string()))
+            }
+        }
+    }
+
+    /// 按指定视频质量获取 URL，依次尝试精确匹配、分辨率匹配和最大可用
+    fn video_url(&self, quality: VideoQuality) -> Result<String, MediaError> {
+        let video_files = self
+            .urls
+            .video_files
+            .as_ref()
+            .ok_or_else(|| MediaError::InvalidQuality("没有可用的视频文件".to_string()))?;
+
+        let quality_str = quality.as_str();
+
+        // 尝试查找精确的质量匹配
+        if let Some(file) = video_files.iter().find(|f| f.quality == quality_str) {
+            return Ok(file.url.clone());
+        }
+
+        // 尝试按分辨率查找，跳过宽度为 0 的异常项（`Original` 的最小宽度为 0，否则会选中它们）
+        let min_width = quality.min_width();
+        if let Some(file) = video_files
+            .iter()
+            .filter(|f| f.width > 0 && f.width >= min_width)
+            .min_by_key(|f| f.width)
+        {
+            return Ok(file.url.clone());
+        }
+
+        // 回退到最大可用
+        video_files
+            .iter()
+            .max_by_key(|f| f.width)
+            .map(|f| f.url.clone())
+            .ok_or_else(|| MediaError::InvalidQuality("未找到合适的视频质量".to_string()))
+    }
+
+    /// 解析 `provider` 字段（不区分大小写），未知的提供商返回 `None`
+    pub fn provider_kind(&self) -> Option<ProviderKind> {
+        self.provider.parse().ok()
+    }
+
+    /// 媒体项所适用的许可证名称，未知提供商返回 `None`
+    pub fn license(&self) -> Option<&'static str> {
+        self.provider_kind().map(|kind| kind.license())
+    }
+
+    /// 许可证说明页面的 URL，未知提供商返回 `None`
+    pub fn license_url(&self) -> Option<&'static str> {
+        self.provider_kind().map(|kind| kind.license_url())
+    }
+
+    /// 按提供商署名要求生成的纯文本署名，如 "Photo by Alice on Pexels"
+    ///
+    /// 措辞与 [`embed_html`](Self::embed_html) 的 `<figcaption>` 一致，但不含链接
+    pub fn attribution_text(&self) -> String {
+        let (noun, preposition) = self.attribution_wording();
+        format!(
+            "{} by {} {} {}",
+            noun, self.author, preposition, self.provider
+        )
+    }
+
+    /// 署名中的名词与介词，如 Pexels 的 ("Photo", "on") 与 Pixabay 的 ("Image", "from")
+    fn attribution_wording(&self) -> (&'static str, &'static str) {
+        let noun = match (self.provider_kind(), &self.media_type) {
+            (Some(ProviderKind::Pixabay), MediaType::Image) => "Image",
+            (_, MediaType::Image) => "Photo",
+            (_, MediaType::Video) => "Video",
+        };
+        let preposition = match self.provider_kind() {
+            Some(ProviderKind::Pixabay) => "from",
+            _ => "on",
+        };
+        (noun, preposition)
+    }
+
+    /// 生成可直接粘贴到网页中的 HTML 嵌入片段
+    ///
+    /// 输出一个 `<figure>`：图片使用 `<img>`（优先 `large`，其次 `medium`、缩略图），
+    /// 视频使用 `<video>` 并以宽度最大的 `video_files` 作为来源、缩略图作为封面。
+    /// `<figcaption>` 按各提供商的署名要求链接到作者与 `source_url`，
+    /// 例如 Pexels 的 "Photo by … on Pexels" 与 Pixabay 的 "Image by … from Pixabay"。
+    /// 所有文本与属性值都会进行 HTML 转义
+    pub fn embed_html(&self) -> String {
+        let media = match self.media_type {
+            MediaType::Image => {
+                let src = self
+                    .urls
+                    .large
+                    .as_deref()
+                    .or(self.urls.medium.as_deref())
+                    .unwrap_or(&self.urls.thumbnail);
+                format!(
+                    r#"<img src="{}" alt="{}" width="{}" height="{}">"#,
+                    escape_html(src),
+                    escape_html(&self.title),
+                    self.metadata.width,
+                    self.metadata.height
+                )
+            }
+            MediaType::Video => {
+                let best = self
+                    .urls
+                    .video_files
+                    .iter()
+                    .flatten()
+                    .max_by_key(|file| file.width);
+                let source = best
+                    .map(|file| {
+                        format!(
+                            r#"<source src="{}" type="video/mp4">"#,
+                            escape_html(&file.url)
+                        )
+                    })
+                    .unwrap_or_default();
+                format!(
+                    r#"<video controls poster="{}" width="{}" height="{}">{}</video>"#,
+                    escape_html(&self.urls.thumbnail),
+                    best.map_or(self.metadata.width, |file| file.width),
+                    best.map_or(self.metadata.height, |file| file.height),
+                    source
+                )
+            }
+        };
+
+        let (noun, preposition) = self.attribution_wording();
+        let author = if self.author_url.is_empty() {
+            escape_html(&self.author)
+        } else {
+            format!(
+                r#"<a href="{}">{}</a>"#,
+                escape_html(&self.author_url),
+                escape_html(&self.author)
+            )
+        };
+
+        format!(
+            r#"<figure>{}<figcaption>{} by {} {} <a href="{}">{}</a></figcaption></figure>"#,
+            media,
+            noun,
+            author,
+            preposition,
+            escape_html(&self.source_url),
+            escape_html(&self.provider)
+        )
+    }
+}
+
+/// 转义 HTML 文本与属性值中的特殊字符
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 单行摘要，例如 `[Pixabay#12345 image] "yellow flowers" 1920x1080 by Alice`，
+/// 作者为空时省略 `by` 部分。需要完整字段时使用 `Debug`
+impl fmt::Display for MediaItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}#{} {}] {:?} {}x{}",
+            self.provider,
+            self.id,
+            self.media_type,
+            self.title,
+            self.metadata.width,
+            self.metadata.height
+        )?;
+        if !self.author.is_empty() {
+            write!(f, " by {}", self.author)?;
+        }
+        Ok(())
+    }
+}
+
 /// 进度回调类型
 pub type ProgressCallback = Arc<dyn Fn(DownloadProgress) + Send + Sync>;
 
 /// 下载状态
+///
+/// 序列化为 `{"state": "..."}`，失败时附带 `error` 字段：
+/// `{"state": "failed", "error": "HTTP 404"}`。`state` 取值为
+/// `starting`、`downloading`、`writing`、`completed`、`failed`
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
+#[serde(tag = "state", content = "error", rename_all = "camelCase")]
 pub enum DownloadState {
     /// 开始下载
     Starting,
@@ -101,6 +445,26 @@ pub enum DownloadState {
 }
 
 /// 下载进度信息
+///
+/// JSON 字段为 camelCase，`state` 展开到顶层，便于前端直接读取：
+///
+/// ```json
+/// {
+///   "itemId": "12345",
+///   "itemTitle": "yellow flowers",
+///   "provider": "Pixabay",
+///   "state": "failed",
+///   "error": "HTTP 404",
+///   "downloadedBytes": 0,
+///   "totalBytes": null,
+///   "speedBps": 0,
+///   "percentage": 0.0,
+///   "elapsedSecs": 0.1,
+///   "etaSecs": null
+/// }
+/// ```
+///
+/// 除 `failed` 外的状态没有 `error` 字段
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DownloadProgress {
@@ -111,6 +475,7 @@ pub struct DownloadProgress {
     /// 提供商名称
     pub provider: String,
     /// 当前状态
+    #[serde(flatten)]
     pub state: DownloadState,
     /// 已下载字节数
     pub downloaded_bytes: u64,
@@ -221,22 +586,83 @@ impl BatchDownloadProgress {
         }
     }
 
-    pub fn calculate_overall_percentage(&mut self) {
-        if self.total_items > 0 {
-            let completed_percentage =
-                (self.completed_items as f64 / self.total_items as f64) * 100.0;
-            let in_progress_percentage = self
-                .item_progress
-                .iter()
-                .filter(|p| matches!(p.state, DownloadState::Downloading))
-                .map(|p| p.percentage / self.total_items as f64)
-                .sum::<f64>();
+    /// 记录单个项目的最新进度，并重新计算各计数与总体进度
+    ///
+    /// 以 `item_id` 识别项目，同一项目的新进度会替换旧进度；计数始终按 `item_progress` 重新统计，
+    /// 避免项目完成后仍被计为下载中
+    pub fn update(&mut self, progress: DownloadProgress) {
+        match self
+            .item_progress
+            .iter_mut()
+            .find(|p| p.item_id == progress.item_id)
+        {
+            Some(existing) => *existing = progress,
+            None => self.item_progress.push(progress),
+        }
+
+        let count = |f: fn(&DownloadState) -> bool| {
+            self.item_progress.iter().filter(|p| f(&p.state)).count()
+        };
+        self.downloading_items = count(|s| matches!(s, DownloadState::Downloading));
+        self.completed_items = count(|s| matches!(s, DownloadState::Completed));
+        self.failed_items = count(|s| matches!(s, DownloadState::Failed(_)));
 
-            self.overall_percentage = completed_percentage + in_progress_percentage;
+        self.calculate_overall_percentage();
+    }
+
+    /// 计算总体进度百分比，结果限制在 0-100
+    ///
+    /// `item_progress` 中的项目按其状态计入一次（完成为 100%，下载中为其进度）；
+    /// `completed_items` 中超出 `item_progress` 已记录完成数的部分视为未跟踪进度的已完成项目，
+    /// 但不超过未出现在 `item_progress` 中的项目数，因此同一项目不会既计为完成又计为下载中
+    pub fn calculate_overall_percentage(&mut self) {
+        if self.total_items == 0 {
+            return;
         }
+
+        let tracked: f64 = self
+            .item_progress
+            .iter()
+            .map(|p| match p.state {
+                DownloadState::Completed => 100.0,
+                DownloadState::Downloading => p.percentage.clamp(0.0, 100.0),
+                _ => 0.0,
+            })
+            .sum();
+        let tracked_completed = self
+            .item_progress
+            .iter()
+            .filter(|p| matches!(p.state, DownloadState::Completed))
+            .count();
+        let untracked_completed = self
+            .completed_items
+            .saturating_sub(tracked_completed)
+            .min(self.total_items.saturating_sub(self.item_progress.len()));
+
+        let percentage = (tracked + untracked_completed as f64 * 100.0) / self.total_items as f64;
+        self.overall_percentage = percentage.min(100.0);
     }
 }
 
+/// 一批下载结束后的汇总，由 [`MediaDownloader::download_items_with_completion`] 交付一次
+///
+/// [`MediaDownloader::download_items_with_completion`]: crate::MediaDownloader::download_items_with_completion
+#[derive(Debug, Clone, Default)]
+pub struct BatchSummary {
+    /// 本批媒体项总数
+    pub total: usize,
+    /// 下载成功的媒体项数（包括下载清单中已记录而跳过的项目）
+    pub succeeded: usize,
+    /// 下载失败的媒体项数
+    pub failed: usize,
+    /// 成功下载的文件的总字节数
+    pub total_bytes: u64,
+    /// 整批下载耗时
+    pub duration: Duration,
+    /// 成功下载的文件路径，按输入顺序排列
+    pub paths: Vec<String>,
+}
+
 /// 带分页信息的搜索结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -255,9 +681,33 @@ pub struct SearchResult {
     pub items: Vec<MediaItem>,
     /// 提供商名称
     pub provider: String,
+    /// 尺寸筛选是否在客户端完成（提供商不支持服务端筛选时为 true）
+    ///
+    /// 客户端筛选只作用于已获取的当前页，因此本页可能少于 `per_page` 个结果
+    #[serde(default)]
+    pub client_side_filtered: bool,
+    /// 向该提供商请求本页所用的时间
+    ///
+    /// 由 [`MediaDownloader`](crate::MediaDownloader) 的搜索方法测量，不含等待并发许可的时间；
+    /// 直接调用提供商时为 0
+    #[serde(default)]
+    pub duration: Duration,
 }
 
 impl SearchResult {
+    /// 是否已经是最后一页（当前页码不小于总页数）
+    pub fn is_last_page(&self) -> bool {
+        self.page >= self.total_pages
+    }
+
+    /// 下一页的搜索参数，已经是最后一页时返回 `None`
+    ///
+    /// 结果中不保存查询条件，因此需要传入得到本页时使用的 `params`；
+    /// 使用 [`SearchParams::offset`] 时偏移量前进 `limit`，否则页码加 1
+    pub fn next_page_params(&self, params: &SearchParams) -> Option<SearchParams> {
+        (!self.is_last_page()).then(|| next_params(params, self.page))
+    }
+
     /// 从总结果数和每页数量计算总页数
     pub fn calculate_total_pages(total: u32, per_page: u32) -> u32 {
         if per_page == 0 {
@@ -265,6 +715,58 @@ impl SearchResult {
         }
         total.div_ceil(per_page) // 向上取整
     }
+
+    /// 按指定方式对媒体项排序（原地、稳定排序）
+    pub fn sort_by(&mut self, order: SortOrder) {
+        order.sort(&mut self.items);
+    }
+
+    /// 在客户端丢弃宽或高低于下限的媒体项
+    ///
+    /// 没有设置下限时不做任何处理；否则将 `total_hits` 调整为筛选后的数量，
+    /// 并标记 `client_side_filtered`
+    pub fn filter_min_size(&mut self, min_width: Option<u32>, min_height: Option<u32>) {
+        if min_width.is_none() && min_height.is_none() {
+            return;
+        }
+
+        self.items.retain(|item| {
+            item.metadata.width >= min_width.unwrap_or(0)
+                && item.metadata.height >= min_height.unwrap_or(0)
+        });
+        self.total_hits = self.items.len() as u32;
+        self.client_side_filtered = true;
+    }
+
+    /// 将同一提供商的多页搜索结果合并为一个结果
+    ///
+    /// 按输入顺序拼接 `items`；`total`、`total_hits`、`total_pages` 和 `per_page`
+    /// 取各页最大值（它们描述的是整个查询而非单页，不能相加），`page` 取第一页的页码。
+    /// 输入为空或来自不同提供商时返回错误。
+    pub fn merge(results: Vec<SearchResult>) -> crate::error::Result<SearchResult> {
+        let mut results = results.into_iter();
+        let mut merged = results
+            .next()
+            .ok_or_else(|| MediaError::MergeError("没有可合并的结果".to_string()))?;
+
+        for result in results {
+            if result.provider != merged.provider {
+                return Err(MediaError::MergeError(format!(
+                    "提供商不一致: {} 与 {}",
+                    merged.provider, result.provider
+                )));
+            }
+            merged.total = merged.total.max(result.total);
+            merged.total_hits = merged.total_hits.max(result.total_hits);
+            merged.total_pages = merged.total_pages.max(result.total_pages);
+            merged.per_page = merged.per_page.max(result.per_page);
+            merged.client_side_filtered |= result.client_side_filtered;
+            merged.duration += result.duration;
+            merged.items.extend(result.items);
+        }
+
+        Ok(merged)
+    }
 }
 
 /// 来自多个提供商的聚合搜索结果
@@ -287,6 +789,247 @@ pub struct AggregatedSearchResult {
     pub items: Vec<MediaItem>,
     /// 各提供商的详细结果
     pub provider_results: Vec<SearchResult>,
+    /// 失败或被跳过的提供商（提供商名称 → 错误描述）
+    #[serde(default)]
+    pub provider_errors: HashMap<String, String>,
+    /// 整个聚合搜索所用的时间，命中搜索缓存时为首次搜索时测得的值
+    #[serde(default)]
+    pub search_duration: Duration,
+}
+
+impl AggregatedSearchResult {
+    /// 是否已经是最后一页
+    ///
+    /// 按各提供商自己的总页数判断，只要还有一个提供商有下一页就不是最后一页；
+    /// `total_pages` 是各提供商总页数之和，不能直接与页码比较。
+    /// 没有提供商结果时（例如反序列化的旧数据）回退为比较 `page` 与 `total_pages`
+    pub fn is_last_page(&self) -> bool {
+        if self.provider_results.is_empty() {
+            self.page >= self.total_pages
+        } else {
+            self.provider_results
+                .iter()
+                .all(|r| self.page >= r.total_pages)
+        }
+    }
+
+    /// 下一页的搜索参数，已经是最后一页时返回 `None`，规则同 [`SearchResult::next_page_params`]
+    pub fn next_page_params(&self, params: &SearchParams) -> Option<SearchParams> {
+        (!self.is_last_page()).then(|| next_params(params, self.page))
+    }
+
+    /// 按标签过滤媒体项（不区分大小写的子串匹配）
+    ///
+    /// 没有标签的媒体项（例如 Pexels 照片）不会出现在结果中
+    pub fn filter_by_tag(&self, tag: &str) -> Vec<&MediaItem> {
+        let tag = tag.to_lowercase();
+        self.items
+            .iter()
+            .filter(|item| Self::has_tag(item, &tag))
+            .collect()
+    }
+
+    /// 返回同时包含所有给定标签的媒体项（不区分大小写的子串匹配）
+    ///
+    /// 没有标签的媒体项不会出现在结果中
+    pub fn contains_all_tags(&self, tags: &[&str]) -> Vec<&MediaItem> {
+        let tags: Vec<String> = tags.iter().map(|t| t.to_lowercase()).collect();
+        self.items
+            .iter()
+            .filter(|item| !item.tags.is_empty())
+            .filter(|item| tags.iter().all(|tag| Self::has_tag(item, tag)))
+            .collect()
+    }
+
+    /// 判断媒体项是否有标签包含 `tag`（`tag` 需为小写）
+    fn has_tag(item: &MediaItem, tag: &str) -> bool {
+        item.tags.iter().any(|t| t.to_lowercase().contains(tag))
+    }
+
+    /// 按指定方式对媒体项排序（原地、稳定排序）
+    pub fn sort_by(&mut self, order: SortOrder) {
+        order.sort(&mut self.items);
+    }
+}
+
+/// 提供商的速率限制状态，来自最近一次 API 响应的速率限制响应头
+///
+/// 用于在界面上展示类似 “Pixabay: 42/100 remaining” 的配额信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitInfo {
+    /// 当前周期内允许的请求总数
+    pub limit: u32,
+    /// 当前周期内剩余的请求数
+    pub remaining: u32,
+    /// 距离周期重置的秒数
+    ///
+    /// Pexels 按查询时刻与重置时间戳计算；Pixabay 为收到最近一次响应时的值
+    pub reset_secs: u64,
+}
+
+/// 在 `page` 之后的下一页搜索参数
+fn next_params(params: &SearchParams, page: u32) -> SearchParams {
+    let next = params.clone();
+    match params.offset {
+        Some(offset) => next.offset(offset.saturating_add(params.limit)),
+        None => next.page(page + 1),
+    }
+}
+
+/// 署名导出格式，见 [`MediaDownloader::export_attributions`]
+///
+/// [`MediaDownloader::export_attributions`]: crate::MediaDownloader::export_attributions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    /// 带表头行的 CSV
+    Csv,
+    /// 对象数组形式的 JSON
+    Json,
+}
+
+/// 搜索结果排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    /// 保持提供商返回的顺序
+    #[default]
+    Relevance,
+    /// 按点赞数降序
+    Likes,
+    /// 按下载数降序
+    Downloads,
+    /// 按浏览数降序
+    Views,
+    /// 按时长降序（无时长的媒体项排在最后）
+    Duration,
+}
+
+impl SortOrder {
+    /// 对媒体项进行原地稳定排序，数值相同的媒体项保持原有顺序
+    pub fn sort(&self, items: &mut [MediaItem]) {
+        match self {
+            SortOrder::Relevance => {}
+            SortOrder::Likes => items.sort_by_key(|item| Reverse(item.metadata.likes)),
+            SortOrder::Downloads => items.sort_by_key(|item| Reverse(item.metadata.downloads)),
+            SortOrder::Views => items.sort_by_key(|item| Reverse(item.metadata.views)),
+            SortOrder::Duration => items.sort_by_key(|item| Reverse(item.metadata.duration)),
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "relevance" => Ok(SortOrder::Relevance),
+            "likes" => Ok(SortOrder::Likes),
+            "downloads" => Ok(SortOrder::Downloads),
+            "views" => Ok(SortOrder::Views),
+            "duration" => Ok(SortOrder::Duration),
+            _ => Err(format!("Invalid sort order: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortOrder::Relevance => write!(f, "relevance"),
+            SortOrder::Likes => write!(f, "likes"),
+            SortOrder::Downloads => write!(f, "downloads"),
+            SortOrder::Views => write!(f, "views"),
+            SortOrder::Duration => write!(f, "duration"),
+        }
+    }
+}
+
+/// 跨提供商统一的媒体方向
+///
+/// 各 SDK 的方向语义不同：Pexels 支持 landscape/portrait/square，
+/// Pixabay 只支持 all/horizontal/vertical。映射规则集中定义在这里：
+///
+/// | 统一方向 | Pexels | Pixabay |
+/// |---|---|---|
+/// | `Landscape` | `Landscape` | `Horizontal` |
+/// | `Portrait` | `Portrait` | `Vertical` |
+/// | `Square` | `Square` | `All`（有损，Pixabay 没有正方形筛选） |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Orientation {
+    Landscape,
+    Portrait,
+    Square,
+}
+
+impl FromStr for Orientation {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "landscape" | "horizontal" => Ok(Orientation::Landscape),
+            "portrait" | "vertical" => Ok(Orientation::Portrait),
+            "square" => Ok(Orientation::Square),
+            _ => Err(format!("Invalid orientation: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Orientation::Landscape => write!(f, "landscape"),
+            Orientation::Portrait => write!(f, "portrait"),
+            Orientation::Square => write!(f, "square"),
+        }
+    }
+}
+
+#[cfg(feature = "pexels")]
+impl From<Orientation> for pexels_sdk::Orientation {
+    fn from(orientation: Orientation) -> Self {
+        match orientation {
+            Orientation::Landscape => pexels_sdk::Orientation::Landscape,
+            Orientation::Portrait => pexels_sdk::Orientation::Portrait,
+            Orientation::Square => pexels_sdk::Orientation::Square,
+        }
+    }
+}
+
+#[cfg(feature = "pexels")]
+impl From<pexels_sdk::Orientation> for Orientation {
+    fn from(orientation: pexels_sdk::Orientation) -> Self {
+        match orientation {
+            pexels_sdk::Orientation::Landscape => Orientation::Landscape,
+            pexels_sdk::Orientation::Portrait => Orientation::Portrait,
+            pexels_sdk::Orientation::Square => Orientation::Square,
+        }
+    }
+}
+
+/// 有损映射：Pixabay 没有正方形筛选，`Square` 映射为 `All`（不筛选方向）
+impl From<Orientation> for pixabay_sdk::Orientation {
+    fn from(orientation: Orientation) -> Self {
+        match orientation {
+            Orientation::Landscape => pixabay_sdk::Orientation::Horizontal,
+            Orientation::Portrait => pixabay_sdk::Orientation::Vertical,
+            Orientation::Square => pixabay_sdk::Orientation::All,
+        }
+    }
+}
+
+/// Pixabay 的 `All` 表示不筛选方向，没有对应的统一方向，转换会失败
+impl TryFrom<pixabay_sdk::Orientation> for Orientation {
+    type Error = String;
+    fn try_from(orientation: pixabay_sdk::Orientation) -> Result<Self, Self::Error> {
+        match orientation {
+            pixabay_sdk::Orientation::Horizontal => Ok(Orientation::Landscape),
+            pixabay_sdk::Orientation::Vertical => Ok(Orientation::Portrait),
+            pixabay_sdk::Orientation::All => {
+                Err("Pixabay orientation `all` has no unified equivalent".to_string())
+            }
+        }
+    }
 }
 
 /// 图片质量偏好
@@ -310,6 +1053,22 @@ impl ImageQuality {
     }
 }
 
+impl FromStr for ImageQuality {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "thumbnail" => Ok(ImageQuality::Thumbnail),
+            "medium" => Ok(ImageQuality::Medium),
+            "large" => Ok(ImageQuality::Large),
+            "original" => Ok(ImageQuality::Original),
+            _ => Err(format!(
+                "Invalid image quality: {} (valid values: thumbnail, medium, large, original)",
+                s
+            )),
+        }
+    }
+}
+
 /// 视频质量偏好
 #[derive(Debug, Clone, Deserialize, Serialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -342,3 +1101,721 @@ impl VideoQuality {
         }
     }
 }
+
+impl FromStr for VideoQuality {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "tiny" => Ok(VideoQuality::Tiny),
+            "small" => Ok(VideoQuality::Small),
+            "medium" => Ok(VideoQuality::Medium),
+            "large" => Ok(VideoQuality::Large),
+            "original" => Ok(VideoQuality::Original),
+            _ => Err(format!(
+                "Invalid video quality: {} (valid values: tiny, small, medium, large, original)",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_item(id: &str, provider: &str, tags: &[&str]) -> MediaItem {
+        MediaItem {
+            id: id.to_string(),
+            media_type: MediaType::Image,
+            title: format!("item {}", id),
+            description: String::new(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            author: "Alice".to_string(),
+            author_url: "https://example.com/alice".to_string(),
+            source_url: format!("https://example.com/{}", id),
+            provider: provider.to_string(),
+            urls: MediaUrls {
+                thumbnail: format!("https://example.com/{}/thumb.jpg", id),
+                medium: None,
+                large: None,
+                original: None,
+                video_files: None,
+            },
+            metadata: MediaMetadata {
+                width: 1920,
+                height: 1080,
+                size: None,
+                duration: None,
+                views: 0,
+                downloads: 0,
+                likes: 0,
+                avg_color: None,
+            },
+        }
+    }
+
+    fn aggregated(items: Vec<MediaItem>) -> AggregatedSearchResult {
+        AggregatedSearchResult {
+            provider: "Pixabay".to_string(),
+            total: items.len() as u32,
+            total_hits: items.len() as u32,
+            page: 1,
+            per_page: 20,
+            total_pages: 1,
+            items,
+            provider_results: vec![],
+            provider_errors: HashMap::new(),
+            search_duration: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_filter_by_tag() {
+        let result = aggregated(vec![
+            sample_item("1", "Pixabay", &["Sunset", "beach"]),
+            sample_item("2", "Pixabay", &["mountain"]),
+            sample_item("3", "Pexels", &[]),
+            sample_item("4", "Pixabay", &["sunsets", "sea"]),
+        ]);
+
+        let ids: Vec<&str> = result
+            .filter_by_tag("sunset")
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1", "4"]);
+
+        // 大小写不敏感
+        assert_eq!(result.filter_by_tag("BEACH").len(), 1);
+        assert!(result.filter_by_tag("forest").is_empty());
+    }
+
+    #[test]
+    fn test_contains_all_tags() {
+        let result = aggregated(vec![
+            sample_item("1", "Pixabay", &["sunset", "beach"]),
+            sample_item("2", "Pixabay", &["sunset"]),
+            sample_item("3", "Pexels", &[]),
+        ]);
+
+        let ids: Vec<&str> = result
+            .contains_all_tags(&["sunset", "Beach"])
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1"]);
+
+        // 空标签列表匹配所有带标签的项，但仍排除无标签项
+        assert_eq!(result.contains_all_tags(&[]).len(), 2);
+    }
+
+    #[test]
+    fn test_sort_by_likes_and_duration() {
+        let mut items = vec![
+            sample_item("1", "Pixabay", &[]),
+            sample_item("2", "Pixabay", &[]),
+            sample_item("3", "Pixabay", &[]),
+        ];
+        items[0].metadata.likes = 5;
+        items[1].metadata.likes = 50;
+        items[2].metadata.likes = 5;
+        items[0].metadata.duration = Some(10);
+        items[2].metadata.duration = Some(30);
+
+        let mut result = aggregated(items);
+        result.sort_by(SortOrder::Likes);
+        let ids: Vec<&str> = result.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "1", "3"]);
+
+        result.sort_by(SortOrder::Duration);
+        let ids: Vec<&str> = result.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["3", "1", "2"]);
+    }
+
+    #[test]
+    fn test_sort_order_from_str() {
+        assert_eq!("Likes".parse::<SortOrder>(), Ok(SortOrder::Likes));
+        assert_eq!("relevance".parse::<SortOrder>(), Ok(SortOrder::Relevance));
+        assert!("popularity".parse::<SortOrder>().is_err());
+    }
+
+    #[cfg(feature = "pexels")]
+    #[test]
+    fn test_orientation_pexels_round_trip() {
+        for orientation in [
+            Orientation::Landscape,
+            Orientation::Portrait,
+            Orientation::Square,
+        ] {
+            let pexels: pexels_sdk::Orientation = orientation.into();
+            assert_eq!(Orientation::from(pexels), orientation);
+        }
+        assert_eq!(
+            pexels_sdk::Orientation::from(Orientation::Square),
+            pexels_sdk::Orientation::Square
+        );
+    }
+
+    #[test]
+    fn test_orientation_to_pixabay() {
+        assert!(matches!(
+            pixabay_sdk::Orientation::from(Orientation::Landscape),
+            pixabay_sdk::Orientation::Horizontal
+        ));
+        assert!(matches!(
+            pixabay_sdk::Orientation::from(Orientation::Portrait),
+            pixabay_sdk::Orientation::Vertical
+        ));
+        // 有损：Pixabay 没有正方形筛选
+        assert!(matches!(
+            pixabay_sdk::Orientation::from(Orientation::Square),
+            pixabay_sdk::Orientation::All
+        ));
+    }
+
+    #[test]
+    fn test_orientation_from_pixabay() {
+        assert_eq!(
+            Orientation::try_from(pixabay_sdk::Orientation::Horizontal),
+            Ok(Orientation::Landscape)
+        );
+        assert_eq!(
+            Orientation::try_from(pixabay_sdk::Orientation::Vertical),
+            Ok(Orientation::Portrait)
+        );
+        assert!(Orientation::try_from(pixabay_sdk::Orientation::All).is_err());
+    }
+
+    #[test]
+    fn test_filter_min_size() {
+        let mut small = sample_item("1", "Pexels", &[]);
+        small.metadata.width = 640;
+        small.metadata.height = 480;
+        let large = sample_item("2", "Pexels", &[]);
+
+        let mut result = SearchResult {
+            total: 2,
+            total_hits: 2,
+            page: 1,
+            per_page: 20,
+            total_pages: 1,
+            items: vec![small, large],
+            provider: "Pexels".to_string(),
+            client_side_filtered: false,
+            duration: Duration::ZERO,
+        };
+        result.filter_min_size(Some(1280), None);
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].id, "2");
+        assert_eq!(result.total_hits, 1);
+        assert!(result.client_side_filtered);
+    }
+
+    fn page_of(provider: &str, page: u32, ids: &[&str]) -> SearchResult {
+        SearchResult {
+            total: 9,
+            total_hits: 9,
+            page,
+            per_page: 3,
+            total_pages: 3,
+            items: ids
+                .iter()
+                .map(|id| sample_item(id, provider, &[]))
+                .collect(),
+            provider: provider.to_string(),
+            client_side_filtered: false,
+            duration: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_merge_pages() {
+        let merged = SearchResult::merge(vec![
+            page_of("Pixabay", 1, &["1", "2", "3"]),
+            page_of("Pixabay", 2, &["4", "5", "6"]),
+            page_of("Pixabay", 3, &["7", "8", "9"]),
+        ])
+        .unwrap();
+
+        let ids: Vec<&str> = merged.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2", "3", "4", "5", "6", "7", "8", "9"]);
+        assert_eq!(merged.total, 9);
+        assert_eq!(merged.total_pages, 3);
+        assert_eq!(merged.page, 1);
+        assert_eq!(merged.provider, "Pixabay");
+    }
+
+    #[test]
+    fn test_merge_rejects_mismatched_providers() {
+        let result = SearchResult::merge(vec![
+            page_of("Pixabay", 1, &["1"]),
+            page_of("Pexels", 2, &["2"]),
+        ]);
+        assert!(matches!(result, Err(MediaError::MergeError(_))));
+        assert!(matches!(
+            SearchResult::merge(vec![]),
+            Err(MediaError::MergeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_metadata_helpers() {
+        let mut metadata = sample_item("1", "Pexels", &[]).metadata;
+        assert!((metadata.megapixels() - 2.0736).abs() < 1e-9);
+        assert!((metadata.aspect_ratio() - 16.0 / 9.0).abs() < 1e-9);
+        assert_eq!(metadata.human_size(), None);
+
+        metadata.size = Some(2048);
+        assert_eq!(metadata.human_size(), Some("2.00 KB".to_string()));
+    }
+
+    #[test]
+    fn test_provider_kind() {
+        assert_eq!(
+            sample_item("1", "Pexels", &[]).provider_kind(),
+            Some(ProviderKind::Pexels)
+        );
+        assert_eq!(
+            sample_item("1", "pixabay", &[]).provider_kind(),
+            Some(ProviderKind::Pixabay)
+        );
+        assert_eq!(sample_item("1", "Unsplash", &[]).provider_kind(), None);
+        assert_eq!(sample_item("1", "", &[]).provider_kind(), None);
+    }
+
+    fn video_file(width: u32) -> VideoFile {
+        VideoFile {
+            quality: String::new(),
+            url: format!("https://example.com/{width}.mp4"),
+            width,
+            height: width * 9 / 16,
+            size: 0,
+            thumbnail: None,
+        }
+    }
+
+    #[test]
+    fn test_available_image_qualities() {
+        let mut urls = sample_item("1", "Pixabay", &[]).urls;
+        urls.thumbnail = "https://example.com/thumb.jpg".to_string();
+        urls.medium = None;
+        urls.large = Some("https://example.com/large.jpg".to_string());
+        urls.original = None;
+        assert_eq!(
+            urls.available_image_qualities(),
+            vec![ImageQuality::Large, ImageQuality::Thumbnail]
+        );
+
+        urls.medium = Some("https://example.com/medium.jpg".to_string());
+        urls.original = Some("https://example.com/original.jpg".to_string());
+        assert_eq!(
+            urls.available_image_qualities(),
+            vec![
+                ImageQuality::Original,
+                ImageQuality::Large,
+                ImageQuality::Medium,
+                ImageQuality::Thumbnail
+            ]
+        );
+    }
+
+    #[test]
+    fn test_available_video_qualities() {
+        let mut urls = sample_item("1", "Pixabay", &[]).urls;
+        urls.video_files = None;
+        assert!(urls.available_video_qualities().is_empty());
+
+        urls.video_files = Some(vec![video_file(960), video_file(640)]);
+        assert_eq!(
+            urls.available_video_qualities(),
+            vec![
+                VideoQuality::Original,
+                VideoQuality::Small,
+                VideoQuality::Tiny
+            ]
+        );
+
+        urls.video_files = Some(vec![video_file(1920)]);
+        assert_eq!(urls.available_video_qualities().len(), 5);
+    }
+
+    #[test]
+    fn test_license() {
+        let pexels = sample_item("1", "Pexels", &[]);
+        assert_eq!(pexels.license(), Some("Pexels License"));
+        assert_eq!(
+            pexels.license_url(),
+            Some("https://www.pexels.com/license/")
+        );
+
+        let pixabay = sample_item("1", "Pixabay", &[]);
+        assert_eq!(pixabay.license(), Some("Pixabay Content License"));
+        assert_eq!(
+            pixabay.license_url(),
+            Some("https://pixabay.com/service/license-summary/")
+        );
+
+        let unknown = sample_item("1", "Unsplash", &[]);
+        assert_eq!(unknown.license(), None);
+        assert_eq!(unknown.license_url(), None);
+    }
+
+    #[test]
+    fn test_attribution_text() {
+        let pexels = sample_item("1", "Pexels", &[]);
+        assert_eq!(pexels.attribution_text(), "Photo by Alice on Pexels");
+
+        let mut pixabay = sample_item("2", "Pixabay", &[]);
+        assert_eq!(pixabay.attribution_text(), "Image by Alice from Pixabay");
+        pixabay.media_type = MediaType::Video;
+        assert_eq!(pixabay.attribution_text(), "Video by Alice from Pixabay");
+    }
+
+    #[test]
+    fn test_media_item_display() {
+        let mut image = sample_item("12345", "Pixabay", &[]);
+        image.title = "yellow flowers".to_string();
+        assert_eq!(
+            image.to_string(),
+            r#"[Pixabay#12345 image] "yellow flowers" 1920x1080 by Alice"#
+        );
+
+        let mut video = sample_item("678", "Pexels", &[]);
+        video.media_type = MediaType::Video;
+        video.title = "ocean waves".to_string();
+        video.metadata.width = 3840;
+        video.metadata.height = 2160;
+        video.author = String::new();
+        assert_eq!(
+            video.to_string(),
+            r#"[Pexels#678 video] "ocean waves" 3840x2160"#
+        );
+    }
+
+    #[test]
+    fn test_download_state_wire_format() {
+        let cases = [
+            (DownloadState::Starting, r#"{"state":"starting"}"#),
+            (DownloadState::Downloading, r#"{"state":"downloading"}"#),
+            (DownloadState::Writing, r#"{"state":"writing"}"#),
+            (DownloadState::Completed, r#"{"state":"completed"}"#),
+            (
+                DownloadState::Failed("HTTP 404".to_string()),
+                r#"{"state":"failed","error":"HTTP 404"}"#,
+            ),
+        ];
+        for (state, json) in cases {
+            assert_eq!(serde_json::to_string(&state).unwrap(), json);
+            assert_eq!(serde_json::from_str::<DownloadState>(json).unwrap(), state);
+        }
+
+        let progress = DownloadProgress {
+            item_id: "12345".to_string(),
+            item_title: "yellow flowers".to_string(),
+            provider: "Pixabay".to_string(),
+            state: DownloadState::Failed("HTTP 404".to_string()),
+            downloaded_bytes: 0,
+            total_bytes: None,
+            speed_bps: 0,
+            percentage: 0.0,
+            elapsed_secs: 0.5,
+            eta_secs: None,
+        };
+        let json = serde_json::to_string(&progress).unwrap();
+        assert_eq!(
+            json,
+            r#"{"itemId":"12345","itemTitle":"yellow flowers","provider":"Pixabay","state":"failed","error":"HTTP 404","downloadedBytes":0,"totalBytes":null,"speedBps":0,"percentage":0.0,"elapsedSecs":0.5,"etaSecs":null}"#
+        );
+        let parsed: DownloadProgress = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.state, progress.state);
+    }
+
+    #[test]
+    fn test_media_type_from_str_synonyms() {
+        for input in ["image", "images", "photo", "photos", "Photos", "IMAGE"] {
+            assert_eq!(input.parse::<MediaType>().unwrap(), MediaType::Image);
+        }
+        for input in ["video", "videos", "clip", "clips", "Videos", "CLIP"] {
+            assert_eq!(input.parse::<MediaType>().unwrap(), MediaType::Video);
+        }
+        assert_eq!(
+            "gif".parse::<MediaType>().unwrap_err(),
+            "Invalid media type: gif"
+        );
+    }
+
+    #[test]
+    fn test_embed_html() {
+        let mut image = sample_item("1", "Pexels", &[]);
+        image.title = "Tom & Jerry".to_string();
+        image.urls.large = Some("https://example.com/1/large.jpg".to_string());
+        let html = image.embed_html();
+        assert!(html.starts_with("<figure><img src=\"https://example.com/1/large.jpg\""));
+        assert!(html.contains(r#"alt="Tom &amp; Jerry""#));
+        assert!(html.contains(
+            r#"Photo by <a href="https://example.com/alice">Alice</a> on <a href="https://example.com/1">Pexels</a>"#
+        ));
+
+        let mut video = sample_item("2", "Pixabay", &[]);
+        video.media_type = MediaType::Video;
+        video.urls.video_files = Some(
+            [(960, "small"), (1920, "large"), (1280, "medium")]
+                .into_iter()
+                .map(|(width, quality)| VideoFile {
+                    quality: quality.to_string(),
+                    url: format!("https://example.com/2/{}.mp4", quality),
+                    width,
+                    height: width * 9 / 16,
+                    size: 0,
+                    thumbnail: None,
+                })
+                .collect(),
+        );
+        let html = video.embed_html();
+        assert!(html.contains(r#"<source src="https://example.com/2/large.mp4" type="video/mp4">"#));
+        assert!(html.contains(r#"width="1920" height="1080""#));
+        assert!(html.contains(
+            r#"Video by <a href="https://example.com/alice">Alice</a> from <a href="https://example.com/2">Pixabay</a>"#
+        ));
+    }
+
+    #[test]
+    fn test_preferred_url_image_fallback_chain() {
+        let mut item = sample_item("1", "Pixabay", &[]);
+        let url = |item: &MediaItem, quality| item.preferred_url(quality, VideoQuality::Large);
+
+        // 只有缩略图时，所有质量都回退到缩略图
+        for quality in [
+            ImageQuality::Thumbnail,
+            ImageQuality::Medium,
+            ImageQuality::Large,
+            ImageQuality::Original,
+        ] {
+            assert_eq!(
+                url(&item, quality).unwrap(),
+                "https://example.com/1/thumb.jpg"
+            );
+        }
+
+        item.urls.medium = Some("https://example.com/1/medium.jpg".to_string());
+        assert_eq!(
+            url(&item, ImageQuality::Large).unwrap(),
+            "https://example.com/1/medium.jpg"
+        );
+
+        item.urls.large = Some("https://example.com/1/large.jpg".to_string());
+        assert_eq!(
+            url(&item, ImageQuality::Medium).unwrap(),
+            "https://example.com/1/medium.jpg"
+        );
+        assert_eq!(
+            url(&item, ImageQuality::Original).unwrap(),
+            "https://example.com/1/large.jpg"
+        );
+        assert_eq!(
+            url(&item, ImageQuality::Thumbnail).unwrap(),
+            "https://example.com/1/thumb.jpg"
+        );
+    }
+
+    #[test]
+    fn test_zero_dimensions_are_safe() {
+        for (width, height) in [(0, 1080), (1920, 0), (0, 0)] {
+            let mut metadata = sample_item("1", "Pexels", &[]).metadata;
+            metadata.width = width;
+            metadata.height = height;
+            assert_eq!(metadata.megapixels(), 0.0);
+            assert_eq!(metadata.aspect_ratio(), 0.0);
+        }
+
+        let mut item = sample_item("2", "Pexels", &[]);
+        item.media_type = MediaType::Video;
+        item.urls.video_files = Some(
+            [
+                (0, "https://example.com/2/broken.mp4"),
+                (1280, "https://example.com/2/hd.mp4"),
+            ]
+            .into_iter()
+            .map(|(width, url)| VideoFile {
+                quality: String::new(),
+                url: url.to_string(),
+                width,
+                height: 0,
+                size: 0,
+                thumbnail: None,
+            })
+            .collect(),
+        );
+        assert_eq!(
+            item.preferred_url(ImageQuality::Large, VideoQuality::Original)
+                .unwrap(),
+            "https://example.com/2/hd.mp4"
+        );
+    }
+
+    #[test]
+    fn test_preferred_url_video_fallback_chain() {
+        let mut item = sample_item("2", "Pexels", &[]);
+        item.media_type = MediaType::Video;
+        let url = |item: &MediaItem, quality| item.preferred_url(ImageQuality::Large, quality);
+
+        assert!(matches!(
+            url(&item, VideoQuality::Large),
+            Err(MediaError::InvalidQuality(_))
+        ));
+
+        item.urls.video_files = Some(
+            [(640, "tiny"), (960, "sd"), (1280, "hd")]
+                .into_iter()
+                .map(|(width, quality)| VideoFile {
+                    quality: quality.to_string(),
+                    url: format!("https://example.com/2/{}.mp4", width),
+                    width,
+                    height: width * 9 / 16,
+                    size: 0,
+                    thumbnail: None,
+                })
+                .collect(),
+        );
+        // 质量标签精确匹配
+        assert_eq!(
+            url(&item, VideoQuality::Tiny).unwrap(),
+            "https://example.com/2/640.mp4"
+        );
+        // 按最小宽度选择最窄的文件
+        assert_eq!(
+            url(&item, VideoQuality::Small).unwrap(),
+            "https://example.com/2/960.mp4"
+        );
+        assert_eq!(
+            url(&item, VideoQuality::Medium).unwrap(),
+            "https://example.com/2/1280.mp4"
+        );
+        // 没有足够宽的文件时回退到最宽的文件
+        assert_eq!(
+            url(&item, VideoQuality::Large).unwrap(),
+            "https://example.com/2/1280.mp4"
+        );
+    }
+
+    fn search_result(provider: &str, page: u32, total_pages: u32) -> SearchResult {
+        SearchResult {
+            total: total_pages * 20,
+            total_hits: total_pages * 20,
+            page,
+            per_page: 20,
+            total_pages,
+            items: vec![],
+            provider: provider.to_string(),
+            client_side_filtered: false,
+            duration: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_search_result_paging_helpers() {
+        let params = SearchParams::new("cats", MediaType::Image).page(2);
+
+        let middle = search_result("Pixabay", 2, 5);
+        assert!(!middle.is_last_page());
+        let next = middle.next_page_params(&params).unwrap();
+        assert_eq!(next.page, 3);
+        assert_eq!(next.query, "cats");
+
+        let last = search_result("Pixabay", 5, 5);
+        assert!(last.is_last_page());
+        assert!(last.next_page_params(&params).is_none());
+
+        // 使用偏移量时按 limit 前进
+        let params = SearchParams::new("cats", MediaType::Image)
+            .offset(5)
+            .limit(10);
+        assert_eq!(middle.next_page_params(&params).unwrap().offset, Some(15));
+    }
+
+    #[test]
+    fn test_aggregated_paging_helpers() {
+        let params = SearchParams::new("cats", MediaType::Image).page(2);
+
+        // 合计的 total_pages（2 + 3）大于页码，但只看各提供商自己的页数
+        let mut result = aggregated(vec![]);
+        result.page = 3;
+        result.total_pages = 5;
+        result.provider_results = vec![
+            search_result("Pexels", 3, 2),
+            search_result("Pixabay", 3, 3),
+        ];
+        assert!(result.is_last_page());
+        assert!(result.next_page_params(&params).is_none());
+
+        result.page = 2;
+        result.provider_results = vec![
+            search_result("Pexels", 2, 2),
+            search_result("Pixabay", 2, 3),
+        ];
+        assert!(!result.is_last_page());
+        assert_eq!(result.next_page_params(&params).unwrap().page, 3);
+
+        // 没有提供商结果时比较 page 与 total_pages
+        let mut result = aggregated(vec![]);
+        assert!(result.is_last_page());
+        result.total_pages = 2;
+        assert_eq!(result.next_page_params(&params).unwrap().page, 2);
+    }
+
+    #[test]
+    fn test_batch_percentage_never_exceeds_100() {
+        let item = |id: &str, state: DownloadState, percentage: f64| {
+            let mut progress = DownloadProgress::new(&sample_item(id, "Pixabay", &[]));
+            progress.state = state;
+            progress.percentage = percentage;
+            progress
+        };
+
+        let mut batch = BatchDownloadProgress::new(2);
+        let updates = [
+            item("a", DownloadState::Starting, 0.0),
+            item("a", DownloadState::Downloading, 60.0),
+            // 服务器低报了 Content-Length，单个项目的进度可能超过 100
+            item("b", DownloadState::Downloading, 120.0),
+            item("a", DownloadState::Completed, 100.0),
+            item("b", DownloadState::Completed, 100.0),
+        ];
+        for progress in updates {
+            batch.update(progress);
+            assert!(
+                batch.overall_percentage <= 100.0,
+                "{}",
+                batch.overall_percentage
+            );
+        }
+        assert_eq!(batch.overall_percentage, 100.0);
+        assert_eq!(batch.completed_items, 2);
+        assert_eq!(batch.downloading_items, 0);
+
+        // 完成计数已更新、但项目在 item_progress 中仍是下载中时不重复计算
+        let mut batch = BatchDownloadProgress::new(1);
+        batch
+            .item_progress
+            .push(item("a", DownloadState::Downloading, 100.0));
+        batch.completed_items = 1;
+        batch.calculate_overall_percentage();
+        assert_eq!(batch.overall_percentage, 100.0);
+    }
+
+    #[test]
+    fn test_suggested_text_color() {
+        let mut metadata = sample_item("1", "Pexels", &[]).metadata;
+        assert_eq!(metadata.suggested_text_color(), None);
+
+        metadata.avg_color = Some("#1A1A2E".to_string());
+        assert_eq!(metadata.suggested_text_color(), Some("white"));
+
+        metadata.avg_color = Some("#F5E6C8".to_string());
+        assert_eq!(metadata.suggested_text_color(), Some("black"));
+
+        metadata.avg_color = Some("not a color".to_string());
+        assert_eq!(metadata.suggested_text_color(), None);
+    }
+}
@@ -1,21 +1,90 @@
 use crate::error::{MediaError, Result};
-use crate::media_provider::MediaProvider;
-use crate::models::{MediaItem, MediaMetadata, MediaType, MediaUrls, SearchResult, VideoFile};
+use crate::media_provider::{parse_media_id, MediaProvider};
+use crate::models::{
+    MediaItem, MediaMetadata, MediaType, MediaUrls, Orientation, RateLimitInfo, SearchResult,
+    VideoFile,
+};
+use crate::{PixabayColor, SearchParams};
 use async_trait::async_trait;
-use pexels_sdk::{SearchBuilder, VideoSearchBuilder};
+use pexels_sdk::{Color, MediaBuilder, MediaTypeResponse, SearchBuilder, VideoSearchBuilder};
+use std::time::Duration;
 
-#[cfg(feature = "pexels")]
 pub struct PexelsProvider {
     client: pexels_sdk::Pexels,
+    orientation: Option<Orientation>,
 }
 
-#[cfg(feature = "pexels")]
 impl PexelsProvider {
     pub fn new(api_key: String) -> Self {
         Self {
             client: pexels_sdk::Pexels::new(api_key),
+            orientation: None,
         }
     }
+
+    /// 设置图片和视频搜索的方向筛选
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    /// Pexels 每页结果数上限
+    const MAX_PER_PAGE: u32 = 80;
+
+    /// 将每页结果数限制在 Pexels 支持的范围内（1-80）
+    ///
+    /// 超出上限时 Pexels 会静默截断，因此在请求前截断，并在结果中记录实际生效的 `per_page`
+    fn clamp_limit(limit: u32) -> u32 {
+        limit.clamp(1, Self::MAX_PER_PAGE)
+    }
+    /// 从 `alt` 文本派生的标签中要去掉的停用词
+    const STOP_WORDS: &[&str] = &[
+        "a", "an", "the", "and", "or", "of", "in", "on", "at", "to", "for", "from", "by", "with",
+        "under", "over", "above", "below", "near", "behind", "beside", "between", "into", "onto",
+        "during", "is", "are", "was", "were", "be", "its", "it", "this", "that", "while",
+    ];
+
+    /// 从 `alt` 文本派生近似标签
+    ///
+    /// Pexels 不返回结构化标签，因此将 `alt` 文本转为小写、按空白拆分、去掉标点和停用词，
+    /// 并按首次出现的顺序去重。结果只是近似值，用于让跨提供商的标签筛选有可匹配的内容，
+    /// 与 Pixabay 由作者标注的标签并不等价。
+    fn derive_tags(alt: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for word in alt.split_whitespace() {
+            let word = word
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase();
+            if word.is_empty() || Self::STOP_WORDS.contains(&word.as_str()) || tags.contains(&word)
+            {
+                continue;
+            }
+            tags.push(word);
+        }
+        tags
+    }
+
+    /// 取颜色列表中第一个 Pexels 也支持的颜色
+    ///
+    /// Pexels 只接受一个颜色；`Grayscale` 和 `Transparent` 没有对应的取值，会被跳过
+    fn pexels_color(colors: &[PixabayColor]) -> Option<Color<'static>> {
+        colors.iter().find_map(|color| match color {
+            PixabayColor::Red => Some(Color::Red),
+            PixabayColor::Orange => Some(Color::Orange),
+            PixabayColor::Yellow => Some(Color::Yellow),
+            PixabayColor::Green => Some(Color::Green),
+            PixabayColor::Turquoise => Some(Color::Turquoise),
+            PixabayColor::Blue => Some(Color::Blue),
+            PixabayColor::Lilac => Some(Color::Violet),
+            PixabayColor::Pink => Some(Color::Pink),
+            PixabayColor::White => Some(Color::White),
+            PixabayColor::Gray => Some(Color::Gray),
+            PixabayColor::Black => Some(Color::Black),
+            PixabayColor::Brown => Some(Color::Brown),
+            PixabayColor::Grayscale | PixabayColor::Transparent => None,
+        })
+    }
+
     /// 处理查询关键字，支持多种输入格式
     ///
     /// Pexels API 支持自然语言查询，可以直接使用空格分隔的关键字
@@ -23,34 +92,70 @@ impl PexelsProvider {
     /// - 单个关键字: "nature"
     /// - 空格分隔: "nature landscape"
     /// - 逗号分隔: "nature,landscape"
+    /// - 全角分隔: "山脉，火山"（全角逗号、分号、顿号、竖线）
     /// - 短语: "group of people working"
     ///
     /// 所有格式都会转换为空格分隔的自然语言查询
     fn process_query(query: &str) -> String {
-        // 将逗号、分号、竖线等分隔符统一替换为空格，保持自然语言风格
+        // 将逗号、分号、竖线等分隔符（含全角形式）统一替换为空格，保持自然语言风格
         query
-            .split([',', ';', '|'])
+            .split([',', ';', '|', '，', '；', '、', '｜'])
             .map(|s| s.trim())
             .filter(|s| !s.is_empty())
             .collect::<Vec<_>>()
             .join(" ")
     }
-}
 
-#[cfg(feature = "pexels")]
-#[async_trait]
-impl MediaProvider for PexelsProvider {
-    fn name(&self) -> &str {
-        "Pexels"
+    /// 获取集合中的全部媒体项（照片与视频），按集合中的顺序返回
+    ///
+    /// 自动翻页直到没有下一页，结果可以直接交给 `MediaDownloader::download_items` 下载
+    pub async fn get_collection_items(&self, id: &str) -> Result<Vec<MediaItem>> {
+        let mut items = Vec::new();
+        let mut page = 1;
+        loop {
+            let builder = MediaBuilder::new()
+                .id(id.to_string())
+                .per_page(Self::MAX_PER_PAGE as usize)
+                .page(page);
+            let response = self
+                .client
+                .search_media(builder)
+                .await
+                .map_err(|e| MediaError::PexelsError(e.to_string()))?;
+
+            items.extend(response.media.iter().map(|media| match media {
+                MediaTypeResponse::Photo(photo) => MediaItem::from(photo),
+                MediaTypeResponse::Video(video) => MediaItem::from(video),
+            }));
+            if response.next_page.is_none() || response.media.is_empty() {
+                break;
+            }
+            page += 1;
+        }
+        Ok(items)
     }
 
-    async fn search_images(&self, query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+    /// 搜索图片，应用方向筛选和可选的颜色筛选
+    async fn search_photos_colored(
+        &self,
+        query: &str,
+        limit: u32,
+        page: u32,
+        color: Option<Color<'static>>,
+    ) -> Result<SearchResult> {
+        let limit = Self::clamp_limit(limit);
         // 处理多关键字查询
         let processed_query = Self::process_query(query);
-        let search_builder: SearchBuilder = SearchBuilder::new()
+        let mut search_builder: SearchBuilder = SearchBuilder::new()
             .query(&processed_query)
             .per_page(limit as usize)
             .page(page as usize);
+        if let Some(orientation) = self.orientation {
+            search_builder = search_builder.orientation(orientation.into());
+        }
+        if let Some(color) = color {
+            search_builder = search_builder.color(color);
+        }
         let response = self
             .client
             .search_photos(search_builder)
@@ -65,7 +170,7 @@ impl MediaProvider for PexelsProvider {
                 media_type: MediaType::Image,
                 title: photo.alt.clone(),
                 description: photo.alt.clone(),
-                tags: vec![],
+                tags: Self::derive_tags(&photo.alt),
                 author: photo.photographer.clone(),
                 author_url: photo.photographer_url.clone(),
                 source_url: photo.url.clone(),
@@ -85,6 +190,7 @@ impl MediaProvider for PexelsProvider {
                     views: 0,
                     downloads: 0,
                     likes: 0,
+                    avg_color: Some(photo.avg_color.clone()),
                 },
             })
             .collect();
@@ -99,16 +205,46 @@ impl MediaProvider for PexelsProvider {
             total_pages,
             items,
             provider: "Pexels".to_string(),
+            client_side_filtered: false,
+            duration: Duration::ZERO,
         })
     }
+}
+
+#[async_trait]
+impl MediaProvider for PexelsProvider {
+    fn name(&self) -> &str {
+        "Pexels"
+    }
+
+    fn rate_limit(&self) -> Option<RateLimitInfo> {
+        let info = self.client.rate_limit()?;
+        // Pexels 返回的是重置时刻的 UNIX 时间戳
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Some(RateLimitInfo {
+            limit: info.limit,
+            remaining: info.remaining,
+            reset_secs: info.reset.saturating_sub(now),
+        })
+    }
+
+    async fn search_images(&self, query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+        self.search_photos_colored(query, limit, page, None).await
+    }
 
     async fn search_videos(&self, query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+        let limit = Self::clamp_limit(limit);
         // 处理多关键字查询
         let processed_query = Self::process_query(query);
-        let search_builder: VideoSearchBuilder = VideoSearchBuilder::new()
+        let mut search_builder: VideoSearchBuilder = VideoSearchBuilder::new()
             .query(&processed_query)
             .per_page(limit as usize)
             .page(page as usize);
+        if let Some(orientation) = self.orientation {
+            search_builder = search_builder.orientation(orientation.into());
+        }
         let response = self
             .client
             .search_videos(search_builder)
@@ -163,6 +299,7 @@ impl MediaProvider for PexelsProvider {
                         views: 0,
                         downloads: 0,
                         likes: 0,
+                        avg_color: video.avg_color.clone(),
                     },
                 }
             })
@@ -178,13 +315,35 @@ impl MediaProvider for PexelsProvider {
             total_pages,
             items,
             provider: "Pexels".to_string(),
+            client_side_filtered: false,
+            duration: Duration::ZERO,
         })
     }
 
+    /// 图片搜索会把 `colors` 中第一个 Pexels 支持的颜色传给服务端，
+    /// 最小尺寸仍在客户端筛选
+    async fn search_with_params(&self, params: &SearchParams) -> Result<SearchResult> {
+        let mut result = match params.media_type {
+            MediaType::Image => {
+                self.search_photos_colored(
+                    &params.query,
+                    params.limit,
+                    params.page,
+                    Self::pexels_color(&params.colors),
+                )
+                .await?
+            }
+            MediaType::Video => {
+                self.search_videos(&params.query, params.limit, params.page)
+                    .await?
+            }
+        };
+        result.filter_min_size(params.min_width, params.min_height);
+        Ok(result)
+    }
+
     async fn get_media(&self, id: &str, media_type: MediaType) -> Result<MediaItem> {
-        let id_num = id
-            .parse::<i32>()
-            .map_err(|_| MediaError::PexelsError("Invalid ID format".to_string()))?;
+        let id_num = parse_media_id(id)?;
 
         match media_type {
             MediaType::Image => {
@@ -199,7 +358,7 @@ impl MediaProvider for PexelsProvider {
                     media_type: MediaType::Image,
                     title: photo.alt.clone(),
                     description: photo.alt.clone(),
-                    tags: vec![],
+                    tags: Self::derive_tags(&photo.alt),
                     author: photo.photographer.clone(),
                     author_url: photo.photographer_url.clone(),
                     source_url: photo.url.clone(),
@@ -219,6 +378,7 @@ impl MediaProvider for PexelsProvider {
                         views: 0,
                         downloads: 0,
                         likes: 0,
+                        avg_color: Some(photo.avg_color.clone()),
                     },
                 })
             }
@@ -273,6 +433,7 @@ impl MediaProvider for PexelsProvider {
                         views: 0,
                         downloads: 0,
                         likes: 0,
+                        avg_color: video.avg_color.clone(),
                     },
                 })
             }
@@ -280,6 +441,91 @@ impl MediaProvider for PexelsProvider {
     }
 }
 
+/// 将集合中的照片转换为统一的媒体项
+impl From<&pexels_sdk::MediaPhoto> for MediaItem {
+    fn from(photo: &pexels_sdk::MediaPhoto) -> Self {
+        MediaItem {
+            id: photo.id.to_string(),
+            media_type: MediaType::Image,
+            title: photo.alt.clone(),
+            description: photo.alt.clone(),
+            tags: PexelsProvider::derive_tags(&photo.alt),
+            author: photo.photographer.clone().unwrap_or_default(),
+            author_url: photo.photographer_url.clone().unwrap_or_default(),
+            source_url: photo.url.clone().unwrap_or_default(),
+            provider: "Pexels".to_string(),
+            urls: MediaUrls {
+                thumbnail: photo.src.tiny.clone(),
+                medium: Some(photo.src.medium.clone()),
+                large: Some(photo.src.large.clone()),
+                original: Some(photo.src.original.clone()),
+                video_files: None,
+            },
+            metadata: MediaMetadata {
+                width: photo.width,
+                height: photo.height,
+                size: None,
+                duration: None,
+                views: 0,
+                downloads: 0,
+                likes: 0,
+                avg_color: Some(photo.avg_color.clone()),
+            },
+        }
+    }
+}
+
+/// 将集合中的视频转换为统一的媒体项
+impl From<&pexels_sdk::MediaVideo> for MediaItem {
+    fn from(video: &pexels_sdk::MediaVideo) -> Self {
+        let video_files: Vec<VideoFile> = video
+            .video_files
+            .iter()
+            .map(|vf| VideoFile {
+                quality: vf.quality.clone().unwrap_or_default(),
+                url: vf.file_link.clone(),
+                width: vf.width,
+                height: vf.height,
+                size: vf.size,
+                thumbnail: None,
+            })
+            .collect();
+        let hd = video_files
+            .iter()
+            .find(|f| f.quality.to_lowercase().contains("hd"))
+            .map(|f| f.url.clone());
+
+        MediaItem {
+            id: video.id.to_string(),
+            media_type: MediaType::Video,
+            title: "Video".to_string(),
+            description: String::new(),
+            tags: video.tags.clone(),
+            author: video.user.name.clone(),
+            author_url: video.user.user_url.clone(),
+            source_url: video.url.clone().unwrap_or_default(),
+            provider: "Pexels".to_string(),
+            urls: MediaUrls {
+                thumbnail: video.image.clone().unwrap_or_default(),
+                medium: hd.clone(),
+                large: hd,
+                original: None,
+                video_files: Some(video_files),
+            },
+            metadata: MediaMetadata {
+                width: video.width,
+                height: video.height,
+                size: None,
+                duration: Some(video.duration),
+                views: 0,
+                downloads: 0,
+                likes: 0,
+                avg_color: video.avg_color.clone(),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,4 +586,123 @@ mod tests {
             "yellow flowers mountain sunset"
         );
     }
+
+    #[test]
+    fn test_process_query_multibyte() {
+        // 多字节字符不会被拆开
+        assert_eq!(PexelsProvider::process_query("山脉火山"), "山脉火山");
+        assert_eq!(PexelsProvider::process_query("🌋,山脉"), "🌋 山脉");
+
+        // 全角分隔符与半角分隔符等价
+        assert_eq!(
+            PexelsProvider::process_query("山脉，火山；湖泊"),
+            "山脉 火山 湖泊"
+        );
+        assert_eq!(
+            PexelsProvider::process_query("山脉、火山｜湖泊"),
+            "山脉 火山 湖泊"
+        );
+    }
+
+    #[test]
+    fn test_clamp_limit() {
+        assert_eq!(PexelsProvider::clamp_limit(20), 20);
+        assert_eq!(PexelsProvider::clamp_limit(80), 80);
+        assert_eq!(PexelsProvider::clamp_limit(250), 80);
+        assert_eq!(PexelsProvider::clamp_limit(0), 1);
+    }
+
+    #[test]
+    fn test_derive_tags_from_alt() {
+        assert_eq!(
+            PexelsProvider::derive_tags("Green mountain under white sky"),
+            vec!["green", "mountain", "white", "sky"]
+        );
+        assert_eq!(
+            PexelsProvider::derive_tags("A dog, and the dog's ball."),
+            vec!["dog", "dog's", "ball"]
+        );
+        assert!(PexelsProvider::derive_tags("").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_media_rejects_non_numeric_id() {
+        let provider = PexelsProvider::new("test_key".to_string());
+        let err = provider
+            .get_media("gxmx8ts", MediaType::Image)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "无效的参数: expected numeric photo/video ID, got 'gxmx8ts'"
+        );
+    }
+
+    #[test]
+    fn test_pexels_color_uses_first_overlapping_color() {
+        assert!(matches!(
+            PexelsProvider::pexels_color(&[
+                PixabayColor::Grayscale,
+                PixabayColor::Lilac,
+                PixabayColor::Red
+            ]),
+            Some(Color::Violet)
+        ));
+        assert!(matches!(
+            PexelsProvider::pexels_color(&[PixabayColor::Blue]),
+            Some(Color::Blue)
+        ));
+        assert!(PexelsProvider::pexels_color(&[PixabayColor::Transparent]).is_none());
+        assert!(PexelsProvider::pexels_color(&[]).is_none());
+    }
+
+    fn collection_fixture() -> pexels_sdk::MediaResponse {
+        serde_json::from_str(include_str!(
+            "../tests/fixtures/pexels_collection_media.json"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_collection_photo_to_media_item() {
+        let response = collection_fixture();
+        let MediaTypeResponse::Photo(photo) = &response.media[0] else {
+            panic!("expected a photo");
+        };
+
+        let item = MediaItem::from(photo);
+        assert_eq!(item.id, "736230");
+        assert_eq!(item.media_type, MediaType::Image);
+        assert_eq!(item.title, "Close-up of Yellow Sunflowers");
+        assert_eq!(item.tags, vec!["close-up", "yellow", "sunflowers"]);
+        assert_eq!(item.author, "Jonas Kakaroto");
+        assert_eq!(item.provider, "Pexels");
+        assert_eq!(
+            item.urls.original.as_deref(),
+            Some(photo.src.original.as_str())
+        );
+        assert_eq!((item.metadata.width, item.metadata.height), (4000, 6000));
+    }
+
+    #[test]
+    fn test_collection_video_to_media_item() {
+        let response = collection_fixture();
+        let MediaTypeResponse::Video(video) = &response.media[1] else {
+            panic!("expected a video");
+        };
+
+        let item = MediaItem::from(video);
+        assert_eq!(item.id, "1093662");
+        assert_eq!(item.media_type, MediaType::Video);
+        assert_eq!(item.author, "Ruvim Miksanskiy");
+        assert_eq!(
+            item.source_url,
+            "https://www.pexels.com/video/waves-crashing-on-shore-1093662/"
+        );
+        assert_eq!(item.metadata.duration, Some(20));
+        let files = item.urls.video_files.as_ref().unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(item.urls.large.as_deref(), Some(files[0].url.as_str()));
+        assert_eq!(files[0].size, 3_000_000);
+    }
 }
@@ -1,85 +1,213 @@
-use crate::error::{MediaError, Result};
-use crate::media_provider::MediaProvider;
-use crate::models::{MediaItem, MediaMetadata, MediaType, MediaUrls, SearchResult, VideoFile};
+use crate::downloader::SearchParams;
+use crate::error::Result;
+use crate::media_provider::{parse_media_id, MediaProvider};
+use crate::models::{
+    MediaItem, MediaMetadata, MediaType, MediaUrls, Orientation, RateLimitInfo, SearchResult,
+    VideoFile, VideoQuality,
+};
+use crate::PixabayColor;
 use async_trait::async_trait;
+use pixabay_sdk::{PixabayError, SearchImageParams, SearchVideoParams};
+use std::time::Duration;
 
 /// Pixabay 提供商实现
 pub struct PixabayProvider {
     client: pixabay_sdk::Pixabay,
+    orientation: Option<Orientation>,
 }
 
 impl PixabayProvider {
     pub fn new(api_key: String) -> Self {
         Self {
             client: pixabay_sdk::Pixabay::new(api_key),
+            orientation: None,
         }
     }
+
+    /// 使用已配置的 Pixabay 客户端创建提供商（例如自定义基础 URL）
+    pub fn with_client(client: pixabay_sdk::Pixabay) -> Self {
+        Self {
+            client,
+            orientation: None,
+        }
+    }
+
+    /// 设置图片搜索的方向筛选
+    ///
+    /// Pixabay 没有正方形筛选，`Orientation::Square` 会退化为不筛选方向；
+    /// Pixabay 的视频搜索不支持方向筛选
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    /// Pixabay 每页结果数上限
+    const MAX_PER_PAGE: u32 = 200;
+
+    /// 将每页结果数限制在 Pixabay 支持的范围内（3-200）
+    ///
+    /// 与 SDK 内部的截断保持一致，并在结果中记录实际生效的 `per_page`
+    fn clamp_limit(limit: u32) -> u32 {
+        limit.clamp(3, Self::MAX_PER_PAGE)
+    }
     /// 处理查询关键字，支持多种输入格式
     ///
     /// 支持的格式：
     /// - 单个关键字: "nature"
     /// - 空格分隔: "nature landscape"
     /// - 逗号分隔: "nature,landscape"
+    /// - 全角分隔: "山脉，火山"（全角逗号、分号、顿号、竖线）
     /// - 数组形式: &["nature", "landscape"]
     ///
     /// Pixabay API 使用 + 号连接多个关键字（URL编码后的空格）
     /// HTTP 客户端库（如 reqwest）会自动对 URL 参数进行编码，将空格转换为 %20 或 +
     fn process_query(query: &str) -> String {
-        // 统一使用空白、逗号、分号、竖线作为分隔符，然后用 + 连接关键字
+        // 统一使用空白、逗号、分号、竖线（含全角形式）作为分隔符，然后用 + 连接关键字
         query
-            .split([' ', ',', ';', '|'])
+            .split([' ', ',', ';', '|', '，', '；', '、', '｜'])
             .map(|s| s.trim())
             .filter(|s| !s.is_empty())
             .collect::<Vec<_>>()
             .join("+")
     }
-}
 
-#[async_trait]
-impl MediaProvider for PixabayProvider {
-    fn name(&self) -> &str {
-        "Pixabay"
+    /// 将 Pixabay 的视频文件转换为统一的视频文件列表（按宽度从大到小）
+    ///
+    /// Pixabay 的 `large`/`medium`/`small`/`tiny` 名称与实际分辨率并不固定对应，
+    /// 因此按实际宽度重新标注质量，使 [`VideoQuality`] 的精确匹配与分辨率一致
+    fn map_video_files(videos: &pixabay_sdk::VideoFiles) -> Vec<VideoFile> {
+        let mut files: Vec<VideoFile> = videos
+            .iter()
+            .map(|v| VideoFile {
+                quality: Self::quality_for_width(v.width).as_str().to_string(),
+                url: v.url.clone(),
+                width: v.width,
+                height: v.height,
+                size: v.size,
+                thumbnail: Some(v.thumbnail.clone()),
+            })
+            .collect();
+        files.sort_by_key(|f| std::cmp::Reverse(f.width));
+        files
     }
 
-    async fn search_images(&self, query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+    /// 按宽度确定视频质量等级
+    fn quality_for_width(width: u32) -> VideoQuality {
+        [
+            VideoQuality::Large,
+            VideoQuality::Medium,
+            VideoQuality::Small,
+        ]
+        .into_iter()
+        .find(|quality| width >= quality.min_width())
+        .unwrap_or(VideoQuality::Tiny)
+    }
+
+    /// 将 Pixabay 图片转换为统一的媒体项
+    fn map_image(img: pixabay_sdk::Image) -> MediaItem {
+        MediaItem {
+            id: img.id.to_string(),
+            media_type: MediaType::Image,
+            title: img.tags.clone(),
+            description: img.tags.clone(),
+            tags: img.tags_vec().into_iter().map(String::from).collect(),
+            author: img.user.clone(),
+            author_url: format!("https://pixabay.com/users/{}-{}/", img.user, img.user_id),
+            source_url: img.page_url.clone(),
+            provider: "Pixabay".to_string(),
+            urls: MediaUrls {
+                thumbnail: img.preview_url.clone(),
+                medium: Some(img.webformat_url.clone()),
+                large: Some(img.large_image_url.clone()),
+                original: img.image_url.clone(),
+                video_files: None,
+            },
+            metadata: MediaMetadata {
+                width: img.image_width,
+                height: img.image_height,
+                size: Some(img.image_size),
+                duration: None,
+                views: img.views,
+                downloads: img.downloads,
+                likes: img.likes,
+                avg_color: None,
+            },
+        }
+    }
+
+    /// 将 Pixabay 视频转换为统一的媒体项
+    fn map_video(vid: pixabay_sdk::Video) -> MediaItem {
+        let video_files = Self::map_video_files(&vid.videos);
+
+        let thumbnail = video_files
+            .first()
+            .and_then(|f| f.thumbnail.clone())
+            .unwrap_or_default();
+
+        MediaItem {
+            id: vid.id.to_string(),
+            media_type: MediaType::Video,
+            title: vid.tags.clone(),
+            description: vid.tags.clone(),
+            tags: vid.tags_vec().into_iter().map(String::from).collect(),
+            author: vid.user.clone(),
+            author_url: format!("https://pixabay.com/users/{}-{}/", vid.user, vid.user_id),
+            source_url: vid.page_url.clone(),
+            provider: "Pixabay".to_string(),
+            urls: MediaUrls {
+                thumbnail,
+                medium: vid
+                    .videos
+                    .smallest_at_least(VideoQuality::Medium.min_width())
+                    .or_else(|| vid.videos.largest())
+                    .map(|f| f.url.clone()),
+                large: vid.videos.largest().map(|f| f.url.clone()),
+                original: None,
+                video_files: Some(video_files),
+            },
+            metadata: MediaMetadata {
+                width: vid.videos.largest().map(|v| v.width).unwrap_or(0),
+                height: vid.videos.largest().map(|v| v.height).unwrap_or(0),
+                size: vid.videos.largest().map(|v| v.size),
+                duration: Some(vid.duration),
+                views: vid.views,
+                downloads: vid.downloads,
+                likes: vid.likes,
+                avg_color: None,
+            },
+        }
+    }
+
+    /// 搜索图片，可选地在服务端按最小尺寸和颜色筛选
+    async fn search_images_sized(
+        &self,
+        query: &str,
+        limit: u32,
+        page: u32,
+        min_width: Option<u32>,
+        min_height: Option<u32>,
+        colors: &[PixabayColor],
+    ) -> Result<SearchResult> {
+        let limit = Self::clamp_limit(limit);
         // 处理多关键字查询
         let processed_query = Self::process_query(query);
-        let response = self
-            .client
-            .search_images(&processed_query, Some(limit), Some(page))
-            .await?;
+        let mut params = SearchImageParams::new()
+            .query(processed_query)
+            .per_page(limit)
+            .page(page)
+            .with_colors(colors.iter().copied());
+        if let Some(orientation) = self.orientation {
+            params = params.orientation(orientation.into());
+        }
+        if let Some(min_width) = min_width {
+            params = params.min_width(min_width);
+        }
+        if let Some(min_height) = min_height {
+            params = params.min_height(min_height);
+        }
+        let response = self.client.search_images_advanced(params).await?;
 
-        let items: Vec<MediaItem> = response
-            .hits
-            .into_iter()
-            .map(|img| MediaItem {
-                id: img.id.to_string(),
-                media_type: MediaType::Image,
-                title: img.tags.clone(),
-                description: img.tags.clone(),
-                tags: img.tags.split(',').map(|s| s.trim().to_string()).collect(),
-                author: img.user.clone(),
-                author_url: format!("https://pixabay.com/users/{}-{}/", img.user, img.user_id),
-                source_url: img.page_url.clone(),
-                provider: "Pixabay".to_string(),
-                urls: MediaUrls {
-                    thumbnail: img.preview_url.clone(),
-                    medium: Some(img.webformat_url.clone()),
-                    large: Some(img.large_image_url.clone()),
-                    original: img.image_url.clone(),
-                    video_files: None,
-                },
-                metadata: MediaMetadata {
-                    width: img.image_width,
-                    height: img.image_height,
-                    size: Some(img.image_size),
-                    duration: None,
-                    views: img.views,
-                    downloads: img.downloads,
-                    likes: img.likes,
-                },
-            })
-            .collect();
+        let items: Vec<MediaItem> = response.hits.into_iter().map(Self::map_image).collect();
 
         let total_pages = SearchResult::calculate_total_pages(response.total, limit);
 
@@ -91,99 +219,36 @@ impl MediaProvider for PixabayProvider {
             total_pages,
             items,
             provider: "Pixabay".to_string(),
+            client_side_filtered: false,
+            duration: Duration::ZERO,
         })
     }
 
-    async fn search_videos(&self, query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+    /// 搜索视频，可选地在服务端按最小尺寸筛选
+    async fn search_videos_sized(
+        &self,
+        query: &str,
+        limit: u32,
+        page: u32,
+        min_width: Option<u32>,
+        min_height: Option<u32>,
+    ) -> Result<SearchResult> {
+        let limit = Self::clamp_limit(limit);
         // 处理多关键字查询
         let processed_query = Self::process_query(query);
-        let response = self
-            .client
-            .search_videos(&processed_query, Some(limit), Some(page))
-            .await?;
+        let mut params = SearchVideoParams::new()
+            .query(processed_query)
+            .per_page(limit)
+            .page(page);
+        if let Some(min_width) = min_width {
+            params = params.min_width(min_width);
+        }
+        if let Some(min_height) = min_height {
+            params = params.min_height(min_height);
+        }
+        let response = self.client.search_videos_advanced(params).await?;
 
-        let items: Vec<MediaItem> = response
-            .hits
-            .into_iter()
-            .map(|vid| {
-                let video_files: Vec<VideoFile> = vec![
-                    vid.videos.large.as_ref().map(|v| VideoFile {
-                        quality: "large".to_string(),
-                        url: v.url.clone(),
-                        width: v.width,
-                        height: v.height,
-                        size: v.size,
-                        thumbnail: Some(v.thumbnail.clone()),
-                    }),
-                    vid.videos.medium.as_ref().map(|v| VideoFile {
-                        quality: "medium".to_string(),
-                        url: v.url.clone(),
-                        width: v.width,
-                        height: v.height,
-                        size: v.size,
-                        thumbnail: Some(v.thumbnail.clone()),
-                    }),
-                    vid.videos.small.as_ref().map(|v| VideoFile {
-                        quality: "small".to_string(),
-                        url: v.url.clone(),
-                        width: v.width,
-                        height: v.height,
-                        size: v.size,
-                        thumbnail: Some(v.thumbnail.clone()),
-                    }),
-                    vid.videos.tiny.as_ref().map(|v| VideoFile {
-                        quality: "tiny".to_string(),
-                        url: v.url.clone(),
-                        width: v.width,
-                        height: v.height,
-                        size: v.size,
-                        thumbnail: Some(v.thumbnail.clone()),
-                    }),
-                ]
-                .into_iter()
-                .flatten()
-                .collect();
-
-                let thumbnail = video_files
-                    .first()
-                    .and_then(|f| f.thumbnail.clone())
-                    .unwrap_or_default();
-
-                MediaItem {
-                    id: vid.id.to_string(),
-                    media_type: MediaType::Video,
-                    title: vid.tags.clone(),
-                    description: vid.tags.clone(),
-                    tags: vid.tags.split(',').map(|s| s.trim().to_string()).collect(),
-                    author: vid.user.clone(),
-                    author_url: format!("https://pixabay.com/users/{}-{}/", vid.user, vid.user_id),
-                    source_url: vid.page_url.clone(),
-                    provider: "Pixabay".to_string(),
-                    urls: MediaUrls {
-                        thumbnail,
-                        medium: video_files
-                            .iter()
-                            .find(|f| f.quality == "medium")
-                            .map(|f| f.url.clone()),
-                        large: video_files
-                            .iter()
-                            .find(|f| f.quality == "large")
-                            .map(|f| f.url.clone()),
-                        original: None,
-                        video_files: Some(video_files),
-                    },
-                    metadata: MediaMetadata {
-                        width: vid.videos.large.as_ref().map(|v| v.width).unwrap_or(0),
-                        height: vid.videos.large.as_ref().map(|v| v.height).unwrap_or(0),
-                        size: vid.videos.large.as_ref().map(|v| v.size),
-                        duration: Some(vid.duration),
-                        views: vid.views,
-                        downloads: vid.downloads,
-                        likes: vid.likes,
-                    },
-                }
-            })
-            .collect();
+        let items: Vec<MediaItem> = response.hits.into_iter().map(Self::map_video).collect();
 
         let total_pages = SearchResult::calculate_total_pages(response.total, limit);
 
@@ -195,133 +260,146 @@ impl MediaProvider for PixabayProvider {
             total_pages,
             items,
             provider: "Pixabay".to_string(),
+            client_side_filtered: false,
+            duration: Duration::ZERO,
+        })
+    }
+}
+
+#[async_trait]
+impl MediaProvider for PixabayProvider {
+    fn name(&self) -> &str {
+        "Pixabay"
+    }
+
+    fn rate_limit(&self) -> Option<RateLimitInfo> {
+        self.client.rate_limit().map(|info| RateLimitInfo {
+            limit: info.limit,
+            remaining: info.remaining,
+            reset_secs: info.reset,
         })
     }
 
+    async fn search_images(&self, query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+        self.search_images_sized(query, limit, page, None, None, &[])
+            .await
+    }
+
+    async fn search_videos(&self, query: &str, limit: u32, page: u32) -> Result<SearchResult> {
+        self.search_videos_sized(query, limit, page, None, None)
+            .await
+    }
+
+    /// Pixabay 在服务端支持 `min_width`/`min_height` 和图片的 `colors` 筛选，无需客户端筛选
+    async fn search_with_params(&self, params: &SearchParams) -> Result<SearchResult> {
+        match params.media_type {
+            MediaType::Image => {
+                self.search_images_sized(
+                    &params.query,
+                    params.limit,
+                    params.page,
+                    params.min_width,
+                    params.min_height,
+                    &params.colors,
+                )
+                .await
+            }
+            MediaType::Video => {
+                self.search_videos_sized(
+                    &params.query,
+                    params.limit,
+                    params.page,
+                    params.min_width,
+                    params.min_height,
+                )
+                .await
+            }
+        }
+    }
+
     async fn get_media(&self, id: &str, media_type: MediaType) -> Result<MediaItem> {
-        let id_num = id.parse::<u64>().map_err(|_| {
-            MediaError::PixabayError(pixabay_sdk::PixabayError::ApiError(
-                "Invalid ID format".to_string(),
-            ))
-        })?;
+        let id_num = parse_media_id(id)?;
 
         match media_type {
             MediaType::Image => {
                 let img = self.client.get_image(id_num).await?;
-                Ok(MediaItem {
-                    id: img.id.to_string(),
-                    media_type: MediaType::Image,
-                    title: img.tags.clone(),
-                    description: img.tags.clone(),
-                    tags: img.tags.split(',').map(|s| s.trim().to_string()).collect(),
-                    author: img.user.clone(),
-                    author_url: format!("https://pixabay.com/users/{}-{}/", img.user, img.user_id),
-                    source_url: img.page_url.clone(),
-                    provider: "Pixabay".to_string(),
-                    urls: MediaUrls {
-                        thumbnail: img.preview_url.clone(),
-                        medium: Some(img.webformat_url.clone()),
-                        large: Some(img.large_image_url.clone()),
-                        original: img.image_url.clone(),
-                        video_files: None,
-                    },
-                    metadata: MediaMetadata {
-                        width: img.image_width,
-                        height: img.image_height,
-                        size: Some(img.image_size),
-                        duration: None,
-                        views: img.views,
-                        downloads: img.downloads,
-                        likes: img.likes,
-                    },
-                })
+                Ok(Self::map_image(img))
             }
             MediaType::Video => {
                 let vid = self.client.get_video(id_num).await?;
-                let video_files: Vec<VideoFile> = vec![
-                    vid.videos.large.as_ref().map(|v| VideoFile {
-                        quality: "large".to_string(),
-                        url: v.url.clone(),
-                        width: v.width,
-                        height: v.height,
-                        size: v.size,
-                        thumbnail: Some(v.thumbnail.clone()),
-                    }),
-                    vid.videos.medium.as_ref().map(|v| VideoFile {
-                        quality: "medium".to_string(),
-                        url: v.url.clone(),
-                        width: v.width,
-                        height: v.height,
-                        size: v.size,
-                        thumbnail: Some(v.thumbnail.clone()),
-                    }),
-                    vid.videos.small.as_ref().map(|v| VideoFile {
-                        quality: "small".to_string(),
-                        url: v.url.clone(),
-                        width: v.width,
-                        height: v.height,
-                        size: v.size,
-                        thumbnail: Some(v.thumbnail.clone()),
-                    }),
-                    vid.videos.tiny.as_ref().map(|v| VideoFile {
-                        quality: "tiny".to_string(),
-                        url: v.url.clone(),
-                        width: v.width,
-                        height: v.height,
-                        size: v.size,
-                        thumbnail: Some(v.thumbnail.clone()),
-                    }),
-                ]
-                .into_iter()
-                .flatten()
-                .collect();
-
-                let thumbnail = video_files
-                    .first()
-                    .and_then(|f| f.thumbnail.clone())
-                    .unwrap_or_default();
-
-                Ok(MediaItem {
-                    id: vid.id.to_string(),
-                    media_type: MediaType::Video,
-                    title: vid.tags.clone(),
-                    description: vid.tags.clone(),
-                    tags: vid.tags.split(',').map(|s| s.trim().to_string()).collect(),
-                    author: vid.user.clone(),
-                    author_url: format!("https://pixabay.com/users/{}-{}/", vid.user, vid.user_id),
-                    source_url: vid.page_url.clone(),
-                    provider: "Pixabay".to_string(),
-                    urls: MediaUrls {
-                        thumbnail,
-                        medium: video_files
-                            .iter()
-                            .find(|f| f.quality == "medium")
-                            .map(|f| f.url.clone()),
-                        large: video_files
-                            .iter()
-                            .find(|f| f.quality == "large")
-                            .map(|f| f.url.clone()),
-                        original: None,
-                        video_files: Some(video_files),
-                    },
-                    metadata: MediaMetadata {
-                        width: vid.videos.large.as_ref().map(|v| v.width).unwrap_or(0),
-                        height: vid.videos.large.as_ref().map(|v| v.height).unwrap_or(0),
-                        size: vid.videos.large.as_ref().map(|v| v.size),
-                        duration: Some(vid.duration),
-                        views: vid.views,
-                        downloads: vid.downloads,
-                        likes: vid.likes,
-                    },
-                })
+                Ok(Self::map_video(vid))
             }
         }
     }
+
+    /// 合法的数字 ID 通过一次 Pixabay 批量请求获取；非数字 ID 和响应中缺少的 ID 在对应位置返回错误
+    async fn get_media_batch(
+        &self,
+        ids: &[String],
+        media_type: MediaType,
+    ) -> Vec<Result<MediaItem>> {
+        let parsed: Vec<Result<u64>> = ids.iter().map(|id| parse_media_id(id)).collect();
+        let valid: Vec<u64> = parsed
+            .iter()
+            .filter_map(|id| id.as_ref().ok().copied())
+            .collect();
+
+        let fetched: pixabay_sdk::Result<Vec<Result<MediaItem>>> = match media_type {
+            MediaType::Image => self.client.get_images(&valid).await.map(|images| {
+                images
+                    .into_iter()
+                    .map(|img| Ok(Self::map_image(img?)))
+                    .collect()
+            }),
+            MediaType::Video => self.client.get_videos(&valid).await.map(|videos| {
+                videos
+                    .into_iter()
+                    .map(|vid| Ok(Self::map_video(vid?)))
+                    .collect()
+            }),
+        };
+        let fetched = match fetched {
+            Ok(fetched) => fetched,
+            // 整个批量请求失败时，每个 ID 都返回该错误：第一个 ID 得到原始错误，其余得到其副本
+            Err(e) => {
+                let copies: Vec<_> = (1..ids.len()).map(|_| copy_error(&e)).collect();
+                return std::iter::once(e)
+                    .chain(copies)
+                    .map(|e| Err(e.into()))
+                    .collect();
+            }
+        };
+
+        let mut fetched = fetched.into_iter();
+        parsed
+            .into_iter()
+            .map(|id| match id {
+                Ok(_) => fetched.next().expect("每个合法 ID 都有对应结果"),
+                Err(e) => Err(e),
+            })
+            .collect()
+    }
+}
+
+/// 复制一个 `PixabayError`，保留其变体（`PixabayError` 不实现 `Clone`）
+///
+/// 包装底层库错误的变体无法复制，退化为携带原错误信息的 `ApiError`
+fn copy_error(e: &PixabayError) -> PixabayError {
+    match e {
+        PixabayError::InvalidBaseUrl(msg) => PixabayError::InvalidBaseUrl(msg.clone()),
+        PixabayError::ApiError(msg) => PixabayError::ApiError(msg.clone()),
+        PixabayError::QueryTooLong => PixabayError::QueryTooLong,
+        PixabayError::BadRequest(msg) => PixabayError::BadRequest(msg.clone()),
+        PixabayError::RateLimitExceeded => PixabayError::RateLimitExceeded,
+        PixabayError::InvalidApiKey => PixabayError::InvalidApiKey,
+        other => PixabayError::ApiError(other.to_string()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::MediaError;
 
     #[test]
     fn test_process_query() {
@@ -373,4 +451,229 @@ mod tests {
             "yellow+flowers"
         );
     }
+
+    #[test]
+    fn test_process_query_multibyte() {
+        // 多字节字符不会被拆开
+        assert_eq!(PixabayProvider::process_query("山脉火山"), "山脉火山");
+        assert_eq!(PixabayProvider::process_query("🌋,山脉"), "🌋+山脉");
+
+        // 全角分隔符与半角分隔符等价
+        assert_eq!(
+            PixabayProvider::process_query("山脉，火山；湖泊"),
+            "山脉+火山+湖泊"
+        );
+        assert_eq!(
+            PixabayProvider::process_query("山脉、火山｜湖泊"),
+            "山脉+火山+湖泊"
+        );
+    }
+
+    #[test]
+    fn test_clamp_limit() {
+        assert_eq!(PixabayProvider::clamp_limit(20), 20);
+        assert_eq!(PixabayProvider::clamp_limit(200), 200);
+        assert_eq!(PixabayProvider::clamp_limit(250), 200);
+        assert_eq!(PixabayProvider::clamp_limit(0), 3);
+    }
+
+    #[tokio::test]
+    async fn test_search_with_params_sends_min_size() {
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("min_width", "1920"))
+            .and(query_param("min_height", "1080"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"total": 0, "totalHits": 0, "hits": []}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = pixabay_sdk::Pixabay::new("test_key".to_string())
+            .with_base_urls(
+                format!("{}/api/", server.uri()),
+                format!("{}/api/videos/", server.uri()),
+            )
+            .unwrap();
+        let provider = PixabayProvider::with_client(client);
+        let params = SearchParams::new("mountain", MediaType::Image)
+            .min_width(1920)
+            .min_height(1080);
+
+        let result = provider.search_with_params(&params).await.unwrap();
+        assert!(!result.client_side_filtered);
+    }
+
+    #[tokio::test]
+    async fn test_search_with_params_sends_colors() {
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("colors", "blue,grayscale"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"total": 0, "totalHits": 0, "hits": []}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = pixabay_sdk::Pixabay::new("test_key".to_string())
+            .with_base_urls(
+                format!("{}/api/", server.uri()),
+                format!("{}/api/videos/", server.uri()),
+            )
+            .unwrap();
+        let provider = PixabayProvider::with_client(client);
+        let params = SearchParams::new("sky", MediaType::Image)
+            .colors([PixabayColor::Blue, PixabayColor::Grayscale]);
+
+        provider.search_with_params(&params).await.unwrap();
+    }
+
+    #[test]
+    fn test_map_video_files_labels_by_resolution() {
+        let file = |width: u32| {
+            Some(pixabay_sdk::VideoFile {
+                url: format!("https://cdn.pixabay.com/video/{}.mp4", width),
+                width,
+                height: width * 9 / 16,
+                size: 0,
+                thumbnail: String::new(),
+            })
+        };
+        let videos = pixabay_sdk::VideoFiles {
+            large: file(3840),
+            medium: file(1920),
+            small: file(1280),
+            tiny: file(960),
+        };
+
+        let files = PixabayProvider::map_video_files(&videos);
+        let labels: Vec<(&str, u32)> = files
+            .iter()
+            .map(|f| (f.quality.as_str(), f.width))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("large", 3840),
+                ("large", 1920),
+                ("medium", 1280),
+                ("small", 960)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_media_rejects_non_numeric_id() {
+        let provider = PixabayProvider::new("test_key".to_string());
+        let err = provider
+            .get_media("abc", MediaType::Image)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "无效的参数: expected numeric photo/video ID, got 'abc'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_media_batch_uses_single_request() {
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let image = |id: u64| {
+            serde_json::json!({
+                "id": id,
+                "pageURL": format!("https://pixabay.com/photos/{id}/"),
+                "type": "photo",
+                "tags": "mountain, sky",
+                "previewURL": "https://cdn.pixabay.com/preview.jpg",
+                "previewWidth": 150,
+                "previewHeight": 100,
+                "webformatURL": "https://pixabay.com/webformat.jpg",
+                "webformatWidth": 640,
+                "webformatHeight": 427,
+                "largeImageURL": "https://pixabay.com/large.jpg",
+                "imageWidth": 1920,
+                "imageHeight": 1280,
+                "imageSize": 1000,
+                "views": 1,
+                "downloads": 1,
+                "likes": 1,
+                "comments": 0,
+                "user_id": 1,
+                "user": "tester",
+                "userImageURL": ""
+            })
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("id", "10,20,30"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total": 2,
+                "totalHits": 2,
+                "hits": [image(30), image(10)]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = pixabay_sdk::Pixabay::new("test_key".to_string())
+            .with_base_urls(
+                format!("{}/api/", server.uri()),
+                format!("{}/api/videos/", server.uri()),
+            )
+            .unwrap();
+        let provider = PixabayProvider::with_client(client);
+        let ids = ["10", "20", "30"].map(String::from);
+
+        let results = provider.get_media_batch(&ids, MediaType::Image).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().id, "10");
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap().id, "30");
+    }
+
+    #[tokio::test]
+    async fn test_get_media_batch_keeps_typed_error() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = pixabay_sdk::Pixabay::new("test_key".to_string())
+            .with_base_urls(
+                format!("{}/api/", server.uri()),
+                format!("{}/api/videos/", server.uri()),
+            )
+            .unwrap();
+        let provider = PixabayProvider::with_client(client);
+        let ids = ["10", "20"].map(String::from);
+
+        let results = provider.get_media_batch(&ids, MediaType::Image).await;
+
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert!(matches!(
+                result,
+                Err(MediaError::PixabayError(PixabayError::RateLimitExceeded))
+            ));
+        }
+    }
 }
@@ -160,9 +160,7 @@ async fn test_query_length_validation() {
     let result = client.search_images_advanced(params).await;
     assert!(result.is_err());
 
-    if let Err(PixabayError::ApiError(msg)) = result {
-        assert!(msg.contains("100 characters"));
-    }
+    assert!(matches!(result, Err(PixabayError::QueryTooLong)));
 }
 
 #[tokio::test]
@@ -464,6 +462,7 @@ async fn test_media_item_creation() {
             views: 100,
             downloads: 50,
             likes: 200,
+            avg_color: None,
         },
     };
 
@@ -501,6 +500,7 @@ async fn test_download_progress_new() {
             views: 0,
             downloads: 0,
             likes: 0,
+            avg_color: None,
         },
     };
 
@@ -0,0 +1,173 @@
+//! Recorded HTTP fixtures (VCR-style) shared by the SDK integration tests.
+//!
+//! Each fixture file stores one API request (method, path and query parameters)
+//! together with the response the live API returned for it. [`Fixtures::replay`]
+//! serves that response from a local mock server, so the tests run in CI without
+//! an API key or network access.
+//!
+//! # Synthetic fixtures
+//!
+//! A fixture marked `"synthetic": true` was written by hand in the API's
+//! response shape rather than recorded, for example because no API key was
+//! available. It still exercises the response models, but cannot catch changes
+//! in what the live API actually returns. Recording a fixture clears the flag,
+//! so `git grep '"synthetic": true'` lists the fixtures still awaiting a real
+//! recording.
+//!
+//! # Recording
+//!
+//! When `record` is `true`, [`Fixtures::replay`] first re-sends the stored
+//! request to the live API and overwrites the fixture's response before serving
+//! it. The SDK crates pass `cfg!(feature = "record-fixtures")`, so:
+//!
+//! ```text
+//! PEXELS_API_KEY=... cargo test -p pexels-sdk --features record-fixtures --test integration_test
+//! PIXABAY_API_KEY=... cargo test -p pixabay-sdk --features record-fixtures --test integration_test
+//! ```
+//!
+//! To add a fixture, create `<name>.json` containing only the `request` object
+//! and run the command above. Review the diff before committing; the API key is
+//! only ever sent with the request and never written to disk.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// How the live API expects the API key when recording.
+#[derive(Debug, Clone, Copy)]
+pub enum LiveAuth {
+    /// Sent as the value of the named header (Pexels: `Authorization`).
+    Header(&'static str),
+    /// Sent as the named query parameter (Pixabay: `key`).
+    Query(&'static str),
+}
+
+/// A directory of fixtures recorded against one live API.
+#[derive(Debug, Clone)]
+pub struct Fixtures {
+    dir: PathBuf,
+    live_base_url: &'static str,
+    api_key_var: &'static str,
+    auth: LiveAuth,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Cassette {
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    synthetic: bool,
+    request: RecordedRequest,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    response: Option<RecordedResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    #[serde(default)]
+    query: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    body: serde_json::Value,
+}
+
+impl Fixtures {
+    /// Creates a fixture set stored in `dir`, recorded from `live_base_url` with
+    /// the API key read from the `api_key_var` environment variable.
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        live_base_url: &'static str,
+        api_key_var: &'static str,
+        auth: LiveAuth,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            live_base_url,
+            api_key_var,
+            auth,
+        }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+
+    fn load(&self, name: &str) -> Cassette {
+        let path = self.path(name);
+        let content = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read fixture {}: {e}", path.display()));
+        serde_json::from_str(&content)
+            .unwrap_or_else(|e| panic!("Invalid fixture {}: {e}", path.display()))
+    }
+
+    async fn record(&self, name: &str, cassette: &mut Cassette) {
+        dotenvy::dotenv().ok();
+        let api_key = std::env::var(self.api_key_var)
+            .unwrap_or_else(|_| panic!("{} not set", self.api_key_var));
+
+        let request = &cassette.request;
+        let mut builder = reqwest::Client::new()
+            .request(
+                request.method.parse().expect("Invalid HTTP method"),
+                format!("{}{}", self.live_base_url, request.path),
+            )
+            .query(&request.query);
+        builder = match self.auth {
+            LiveAuth::Header(name) => builder.header(name, api_key),
+            LiveAuth::Query(name) => builder.query(&[(name, api_key)]),
+        };
+        let response = builder.send().await.expect("Recording request failed");
+
+        cassette.synthetic = false;
+        cassette.response = Some(RecordedResponse {
+            status: response.status().as_u16(),
+            body: response.json().await.expect("Response is not JSON"),
+        });
+
+        let content = serde_json::to_string_pretty(cassette).unwrap() + "\n";
+        std::fs::write(self.path(name), content).expect("Failed to write fixture");
+    }
+
+    /// Returns the recorded response body of a fixture without starting a server.
+    ///
+    /// Useful for checking that alternative response models parse the same payload.
+    pub fn body(&self, name: &str) -> serde_json::Value {
+        recorded(name, self.load(name).response).body
+    }
+
+    /// Starts a mock server that answers the fixture's request with its recorded
+    /// response, re-recording it from the live API first when `record` is set.
+    pub async fn replay(&self, name: &str, record: bool) -> MockServer {
+        let mut cassette = self.load(name);
+        if record {
+            self.record(name, &mut cassette).await;
+        }
+        let request = cassette.request;
+        let response = recorded(name, cassette.response);
+
+        let server = MockServer::start().await;
+        let mut mock =
+            Mock::given(method(request.method.as_str())).and(path(request.path.as_str()));
+        for (key, value) in &request.query {
+            mock = mock.and(query_param(key.as_str(), value.as_str()));
+        }
+        mock.respond_with(ResponseTemplate::new(response.status).set_body_json(response.body))
+            .expect(1)
+            .mount(&server)
+            .await;
+        server
+    }
+}
+
+fn recorded(name: &str, response: Option<RecordedResponse>) -> RecordedResponse {
+    response.unwrap_or_else(|| {
+        panic!(
+            "Fixture {name} has no recorded response; re-record it with --features record-fixtures"
+        )
+    })
+}
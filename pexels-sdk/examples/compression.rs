@@ -0,0 +1,70 @@
+//! Compares transfer size and fetch+parse time of a large (`per_page=80`) search
+//! with and without response compression.
+//!
+//! Run with `cargo run -p pexels-sdk --example compression`.
+use dotenvy::dotenv;
+use pexels_sdk::PhotosResponse;
+use reqwest::{header, Client};
+use std::env;
+use std::time::Instant;
+
+const URL: &str = "https://api.pexels.com/v1/search?query=nature&per_page=80";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv().ok();
+
+    let api_key = env::var("PEXELS_API_KEY").expect("PEXELS_API_KEY must be set in .env file");
+
+    println!("=== Transfer size ===");
+    // Decompression disabled so the body is exactly what went over the wire
+    let raw = Client::builder().no_gzip().no_brotli().build()?;
+    for encoding in ["identity", "gzip", "br"] {
+        let response = raw
+            .get(URL)
+            .header(header::AUTHORIZATION, &api_key)
+            .header(header::ACCEPT_ENCODING, encoding)
+            .send()
+            .await?;
+        let served = response
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("identity")
+            .to_string();
+        let bytes = response.bytes().await?;
+        println!(
+            "- requested {encoding:<8} served {served:<8} {:>8} bytes",
+            bytes.len()
+        );
+    }
+
+    println!("\n=== Fetch + parse time ===");
+    let clients = [
+        (
+            "uncompressed",
+            Client::builder().no_gzip().no_brotli().build()?,
+        ),
+        (
+            "gzip + brotli",
+            Client::builder().gzip(true).brotli(true).build()?,
+        ),
+    ];
+    for (name, client) in clients {
+        let start = Instant::now();
+        let response: PhotosResponse = client
+            .get(URL)
+            .header(header::AUTHORIZATION, &api_key)
+            .send()
+            .await?
+            .json()
+            .await?;
+        println!(
+            "- {name:<14} {:>4} photos in {:?}",
+            response.photos.len(),
+            start.elapsed()
+        );
+    }
+
+    Ok(())
+}
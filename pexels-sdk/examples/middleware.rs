@@ -0,0 +1,34 @@
+//! Sends Pexels requests through `reqwest-tracing` middleware.
+//!
+//! Run with `cargo run -p pexels-sdk --features middleware --example middleware`.
+use dotenvy::dotenv;
+use pexels_sdk::{PexelsClient, SearchParams};
+use reqwest_middleware::ClientBuilder;
+use reqwest_tracing::TracingMiddleware;
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv().ok();
+    // Print the spans emitted by the middleware to stdout
+    tracing_subscriber::fmt()
+        .with_max_level(tracing_subscriber::filter::LevelFilter::INFO)
+        .init();
+
+    let api_key = env::var("PEXELS_API_KEY").expect("PEXELS_API_KEY must be set in .env file");
+
+    let http = reqwest::Client::builder().gzip(true).brotli(true).build()?;
+    let client = ClientBuilder::new(http)
+        .with(TracingMiddleware::default())
+        .build();
+    let client = PexelsClient::with_middleware(api_key, client);
+
+    let page = client
+        .search_photos("mountains", SearchParams::new().per_page(5))
+        .await?;
+    for photo in &page.photos {
+        println!("- {} by {}", photo.id, photo.photographer);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+/*!
+同步（阻塞）调用包装，需要启用 `blocking` 特性。
+
+适用于 CLI 工具、GUI 事件循环等没有异步运行时的场景：每个 `*_blocking` 方法都在
+一个进程内共享的 current-thread Tokio 运行时上执行对应的异步方法并等待其完成，
+调用方无需自行创建运行时。[`PexelsClient`] 与 [`Pexels`] 都提供这些方法。
+
+# 注意
+
+不要在异步上下文中（例如 `#[tokio::main]` 或 `tokio::spawn` 的任务内）调用这些方法，
+否则 Tokio 会因嵌套运行时而 panic。异步代码请直接使用对应的异步方法。
+*/
+use std::borrow::Borrow;
+use std::sync::OnceLock;
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::client::PexelsClient;
+use crate::models::{Photo, PhotosPage, Video, VideosPage};
+use crate::search::{PaginationParams, SearchParams, VideoSearchParams};
+use crate::{
+    Pexels, PexelsError, PhotosResponse, SearchBuilder, VideoResponse, VideoSearchBuilder,
+};
+
+/// 所有阻塞调用共享的运行时，首次使用时创建
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("无法创建阻塞调用所需的 Tokio 运行时")
+    })
+}
+
+impl PexelsClient {
+    /// [`search_photos`](Self::search_photos) 的阻塞版本
+    pub fn search_photos_blocking(
+        &self,
+        query: &str,
+        params: impl Borrow<SearchParams>,
+    ) -> Result<PhotosPage, PexelsError> {
+        runtime().block_on(self.search_photos(query, params))
+    }
+
+    /// [`curated_photos`](Self::curated_photos) 的阻塞版本
+    pub fn curated_photos_blocking(
+        &self,
+        params: &PaginationParams,
+    ) -> Result<PhotosPage, PexelsError> {
+        runtime().block_on(self.curated_photos(params))
+    }
+
+    /// [`get_photo`](Self::get_photo) 的阻塞版本
+    pub fn get_photo_blocking(&self, id: u64) -> Result<Photo, PexelsError> {
+        runtime().block_on(self.get_photo(id))
+    }
+
+    /// [`search_videos`](Self::search_videos) 的阻塞版本
+    pub fn search_videos_blocking(
+        &self,
+        query: &str,
+        params: impl Borrow<VideoSearchParams>,
+    ) -> Result<VideosPage, PexelsError> {
+        runtime().block_on(self.search_videos(query, params))
+    }
+
+    /// [`popular_videos`](Self::popular_videos) 的阻塞版本
+    pub fn popular_videos_blocking(
+        &self,
+        params: &PaginationParams,
+    ) -> Result<VideosPage, PexelsError> {
+        runtime().block_on(self.popular_videos(params))
+    }
+
+    /// [`get_video`](Self::get_video) 的阻塞版本
+    pub fn get_video_blocking(&self, id: u64) -> Result<Video, PexelsError> {
+        runtime().block_on(self.get_video(id))
+    }
+}
+
+impl Pexels {
+    /// [`search_photos`](Self::search_photos) 的阻塞版本
+    pub fn search_photos_blocking(
+        &self,
+        builder: SearchBuilder<'_>,
+    ) -> Result<PhotosResponse, PexelsError> {
+        runtime().block_on(self.search_photos(builder))
+    }
+
+    /// [`get_photo`](Self::get_photo) 的阻塞版本
+    pub fn get_photo_blocking(&self, id: usize) -> Result<crate::Photo, PexelsError> {
+        runtime().block_on(self.get_photo(id))
+    }
+
+    /// [`search_videos`](Self::search_videos) 的阻塞版本
+    pub fn search_videos_blocking(
+        &self,
+        builder: VideoSearchBuilder<'_>,
+    ) -> Result<VideoResponse, PexelsError> {
+        runtime().block_on(self.search_videos(builder))
+    }
+
+    /// [`get_video`](Self::get_video) 的阻塞版本
+    pub fn get_video_blocking(&self, id: usize) -> Result<crate::Video, PexelsError> {
+        runtime().block_on(self.get_video(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_search_photos_blocking() {
+        // 模拟服务器在测试自己的运行时中启动，阻塞调用发生在运行时之外
+        let rt = Runtime::new().unwrap();
+        let server = rt.block_on(MockServer::start());
+        rt.block_on(
+            Mock::given(method("GET"))
+                .and(path("/search"))
+                .and(query_param("query", "nature"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "total_results": 7,
+                    "page": 1,
+                    "per_page": 15,
+                    "photos": []
+                })))
+                .expect(1)
+                .mount(&server),
+        );
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        let page = client
+            .search_photos_blocking("nature", SearchParams::new())
+            .unwrap();
+        assert_eq!(page.total_results, 7);
+
+        rt.block_on(server.verify());
+    }
+}
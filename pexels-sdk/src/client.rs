@@ -1,24 +1,80 @@
-use reqwest::{header, Client, StatusCode};
+use futures::future::join_all;
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::{header, Client, ClientBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use std::borrow::Borrow;
+use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Semaphore;
 use url::Url;
 
-use crate::models::{CollectionsPage, MediaPage, Photo, PhotosPage, Video, VideosPage};
+use crate::models::{
+    Collection, CollectionsPage, MediaItemType, MediaPage, Photo, PhotosPage, Video, VideosPage,
+};
+use crate::rate_limit::{retry_after, RateLimitState};
 use crate::search::{PaginationParams, SearchParams, VideoSearchParams};
-use crate::PexelsError;
+use crate::{Locale, PexelsError, RateLimitInfo};
+
+/// 发送请求所用的 HTTP 客户端，启用 `middleware` 特性时为带中间件的客户端
+#[cfg(feature = "middleware")]
+type HttpClient = reqwest_middleware::ClientWithMiddleware;
+#[cfg(not(feature = "middleware"))]
+type HttpClient = Client;
+
+/// 将普通 `reqwest::Client` 包装为 [`HttpClient`]（不附加任何中间件）
+fn http_client(client: Client) -> HttpClient {
+    #[cfg(feature = "middleware")]
+    return reqwest_middleware::ClientWithMiddleware::from(client);
+    #[cfg(not(feature = "middleware"))]
+    client
+}
+
+/// 带有语言回退信息的搜索结果
+#[derive(Debug, Clone)]
+pub struct LocaleFallback<T> {
+    /// 最终返回的结果页
+    pub page: T,
+    /// 结果是否来自回退语言
+    pub used_fallback: bool,
+}
+
+/// `Authorization` 请求头中 API 密钥的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AuthScheme {
+    /// 直接发送密钥（`Authorization: <key>`），即 Pexels API 要求的格式
+    #[default]
+    Bare,
+    /// 以 `Bearer` 前缀发送密钥（`Authorization: Bearer <key>`），适用于要求该格式的代理或 API 网关
+    Bearer,
+}
 
 /// Pexels API 的主要客户端
 ///
 /// 此客户端提供与 Pexels API 所有端点交互的方法，
 /// 并处理认证、请求构建和响应解析。
+///
+/// 克隆开销很小：内部的 `reqwest::Client` 克隆后共享同一个连接池，
+/// 因此可以直接把克隆移入 `tokio::spawn` 任务，而无需包装在 `Arc` 中。
+#[derive(Clone)]
 pub struct PexelsClient {
     /// 用于 Pexels API 认证的 API 密钥
     api_key: String,
 
     /// 具有连接池和可配置超时的 HTTP 客户端
-    client: Client,
+    client: HttpClient,
 
     /// Pexels API 的基础 URL
     base_url: String,
+
+    /// 是否启用严格解析（报告解析失败的 JSON 路径）
+    strict_parse: bool,
+
+    /// `Authorization` 请求头的格式
+    auth_scheme: AuthScheme,
+
+    /// 最近一次响应中的速率限制信息，克隆之间共享
+    rate_limit: RateLimitState,
 }
 
 impl PexelsClient {
@@ -39,18 +95,35 @@ impl PexelsClient {
     ///
     /// let client = PexelsClient::new("your_api_key");
     /// ```
+    ///
+    /// # 注意
+    ///
+    /// 如果底层 HTTP 客户端构建失败（例如 TLS 初始化失败），此方法会静默回退到
+    /// `reqwest` 的默认客户端，此时超时和连接池设置不会生效。
+    /// 如需获知构建错误，请使用 [`PexelsClient::try_new`]。
     pub fn new<S: Into<String>>(api_key: S) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .pool_max_idle_per_host(10)
-            .build()
-            .unwrap_or_default();
+        Self::with_config(api_key, 30, 10)
+    }
 
-        Self {
-            api_key: api_key.into(),
-            client,
-            base_url: "https://api.pexels.com/v1".to_string(),
-        }
+    /// 使用提供的 API 密钥创建新的 PexelsClient，并返回 HTTP 客户端构建错误
+    ///
+    /// # 参数
+    ///
+    /// * `api_key` - Pexels API 密钥
+    ///
+    /// # 返回
+    ///
+    /// PexelsClient 的新实例，或 HTTP 客户端构建失败时的 `PexelsError::RequestError`
+    ///
+    /// # 示例
+    ///
+    /// ```
+    /// use pexels_sdk::PexelsClient;
+    ///
+    /// let client = PexelsClient::try_new("your_api_key").expect("Failed to build client");
+    /// ```
+    pub fn try_new<S: Into<String>>(api_key: S) -> Result<Self, PexelsError> {
+        Self::try_with_config(api_key, 30, 10)
     }
 
     /// 使用自定义配置创建新的 PexelsClient
@@ -64,44 +137,179 @@ impl PexelsClient {
     /// # 返回
     ///
     /// PexelsClient 的新实例
+    ///
+    /// # 注意
+    ///
+    /// 与 [`PexelsClient::new`] 相同，构建失败时会静默回退到默认客户端，
+    /// 此时 `timeout` 和 `max_idle_connections` 不会生效。
+    /// 如需获知构建错误，请使用 [`PexelsClient::try_with_config`]。
     pub fn with_config<S: Into<String>>(
         api_key: S,
         timeout: u64,
         max_idle_connections: usize,
     ) -> Self {
-        let client = Client::builder()
+        let api_key = api_key.into();
+        Self::try_with_config(api_key.clone(), timeout, max_idle_connections).unwrap_or_else(|_| {
+            Self {
+                api_key,
+                client: http_client(Client::default()),
+                base_url: "https://api.pexels.com/v1".to_string(),
+                strict_parse: false,
+                auth_scheme: AuthScheme::Bare,
+                rate_limit: RateLimitState::default(),
+            }
+        })
+    }
+
+    /// 使用自定义配置创建新的 PexelsClient，并返回 HTTP 客户端构建错误
+    ///
+    /// # 参数
+    ///
+    /// * `api_key` - Pexels API 密钥
+    /// * `timeout` - 请求超时时间（秒）
+    /// * `max_idle_connections` - 每个主机的最大空闲连接数
+    ///
+    /// # 返回
+    ///
+    /// PexelsClient 的新实例，或 HTTP 客户端构建失败时的 `PexelsError::RequestError`
+    ///
+    /// 启用 gzip 与 brotli 响应解压，大分页的 JSON 响应传输量会显著减少
+    pub fn try_with_config<S: Into<String>>(
+        api_key: S,
+        timeout: u64,
+        max_idle_connections: usize,
+    ) -> Result<Self, PexelsError> {
+        let builder = Client::builder()
             .timeout(Duration::from_secs(timeout))
             .pool_max_idle_per_host(max_idle_connections)
-            .build()
-            .unwrap_or_default();
+            .gzip(true)
+            .brotli(true);
+
+        Self::from_builder(api_key, builder)
+    }
 
+    /// 从 `reqwest::ClientBuilder` 构建客户端，构建失败时返回错误
+    ///
+    /// 构建器按原样使用，不会覆盖调用方对响应解压等选项的设置
+    pub(crate) fn from_builder<S: Into<String>>(
+        api_key: S,
+        builder: ClientBuilder,
+    ) -> Result<Self, PexelsError> {
+        Ok(Self {
+            api_key: api_key.into(),
+            client: http_client(builder.build()?),
+            base_url: "https://api.pexels.com/v1".to_string(),
+            strict_parse: false,
+            auth_scheme: AuthScheme::Bare,
+            rate_limit: RateLimitState::default(),
+        })
+    }
+
+    /// 使用带中间件的 HTTP 客户端创建 PexelsClient
+    ///
+    /// 需要启用 `middleware` 特性。可用于附加请求 ID、链路追踪（如 `reqwest-tracing`）、
+    /// 重试（如 `reqwest-retry`）或 API 网关所需的自定义认证。
+    /// 客户端按原样使用，超时、连接池与响应解压均由调用方配置。
+    ///
+    /// # 参数
+    ///
+    /// * `api_key` - Pexels API 密钥
+    /// * `client` - 带中间件的 HTTP 客户端
+    #[cfg(feature = "middleware")]
+    pub fn with_middleware<S: Into<String>>(
+        api_key: S,
+        client: reqwest_middleware::ClientWithMiddleware,
+    ) -> Self {
         Self {
             api_key: api_key.into(),
             client,
             base_url: "https://api.pexels.com/v1".to_string(),
+            strict_parse: false,
+            auth_scheme: AuthScheme::Bare,
+            rate_limit: RateLimitState::default(),
         }
     }
 
     /// 为 Pexels API 设置自定义基础 URL
     ///
+    /// URL 会在设置时校验（必须能被解析且协议为 `http` 或 `https`），
+    /// 末尾的 `/` 会被去掉，以免拼接端点路径时出现双斜杠。
+    ///
     /// # 参数
     ///
     /// * `base_url` - 自定义基础 URL
     ///
     /// # 返回
     ///
+    /// 用于方法链的 Self；URL 无效时返回 `PexelsError::ParseError` 或 `PexelsError::InvalidParameter`
+    pub fn with_base_url<S: Into<String>>(mut self, base_url: S) -> Result<Self, PexelsError> {
+        let base_url = base_url.into();
+        let url = Url::parse(&base_url)?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(PexelsError::InvalidParameter(format!(
+                "基础 URL 必须使用 http 或 https 协议: {base_url}"
+            )));
+        }
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        Ok(self)
+    }
+
+    /// 启用或关闭严格解析模式
+    ///
+    /// 默认关闭：响应按 serde 默认方式宽松解析，未知字段会被忽略。
+    /// 启用后，解析失败时返回的 `PexelsError::JsonParseError` 会包含出错字段的 JSON 路径
+    /// （例如 `photos[0].width`），便于排查上游字段变更。
+    ///
+    /// # 参数
+    ///
+    /// * `strict_parse` - 是否启用严格解析
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的 Self
+    pub fn with_strict_parse(mut self, strict_parse: bool) -> Self {
+        self.strict_parse = strict_parse;
+        self
+    }
+
+    /// 设置 `Authorization` 请求头的格式
+    ///
+    /// 默认为 [`AuthScheme::Bare`]，与 Pexels API 的要求一致；
+    /// 当 API 前面有要求 `Bearer <key>` 的代理或网关时使用 [`AuthScheme::Bearer`]。
+    ///
+    /// # 参数
+    ///
+    /// * `auth_scheme` - 请求头格式
+    ///
+    /// # 返回
+    ///
     /// 用于方法链的 Self
-    pub fn with_base_url<S: Into<String>>(mut self, base_url: S) -> Self {
-        self.base_url = base_url.into();
+    pub fn with_auth_scheme(mut self, auth_scheme: AuthScheme) -> Self {
+        self.auth_scheme = auth_scheme;
         self
     }
 
+    /// 最近一次成功响应中的速率限制信息，尚未收到带速率限制响应头的响应时返回 `None`
+    ///
+    /// 克隆之间共享同一份状态
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        self.rate_limit.get()
+    }
+
+    /// 按 `auth_scheme` 生成 `Authorization` 请求头的值
+    fn authorization(&self) -> String {
+        match self.auth_scheme {
+            AuthScheme::Bare => self.api_key.clone(),
+            AuthScheme::Bearer => format!("Bearer {}", self.api_key),
+        }
+    }
+
     /// 搜索与指定查询和参数匹配的照片
     ///
     /// # 参数
     ///
     /// * `query` - 搜索查询
-    /// * `params` - 其他搜索参数（分页、过滤器等）
+    /// * `params` - 其他搜索参数（分页、过滤器等），可以传引用或直接传入构建链的值
     ///
     /// # 返回
     ///
@@ -130,8 +338,49 @@ impl PexelsClient {
     pub async fn search_photos(
         &self,
         query: &str,
-        params: &SearchParams,
+        params: impl Borrow<SearchParams>,
     ) -> Result<PhotosPage, PexelsError> {
+        let url = self.photo_search_url(query, params.borrow())?;
+        let response = self.send_request(url).await?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let photos_page: PhotosPage = self.parse_json(response).await?;
+                Ok(photos_page)
+            }
+            StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
+            StatusCode::TOO_MANY_REQUESTS => {
+                Err(PexelsError::RateLimitError(retry_after(response.headers())))
+            }
+            status => Err(PexelsError::ApiError(format!(
+                "Search photos failed with status: {status}"
+            ))),
+        }
+    }
+
+    /// 构建视频端点的请求 URL
+    ///
+    /// 视频端点不使用 `/v1` 版本前缀（例如 `https://api.pexels.com/videos/videos/{id}`），
+    /// 因此会去掉基础 URL 末尾的 `/v1`
+    fn video_url(&self, path: &str) -> Result<Url, PexelsError> {
+        let base_url = self.base_url.strip_suffix("/v1").unwrap_or(&self.base_url);
+        Ok(Url::parse(&format!("{base_url}/videos/{path}"))?)
+    }
+
+    /// 构建精选照片的请求 URL
+    fn curated_url(&self, params: &PaginationParams) -> Result<Url, PexelsError> {
+        let url = Url::parse(&format!("{}/curated", self.base_url))?;
+        Ok(Self::with_pagination(url, params))
+    }
+
+    /// 构建精选收藏的请求 URL
+    fn featured_collections_url(&self, params: &PaginationParams) -> Result<Url, PexelsError> {
+        let url = Url::parse(&format!("{}/collections/featured", self.base_url))?;
+        Ok(Self::with_pagination(url, params))
+    }
+
+    /// 构建照片搜索的请求 URL
+    fn photo_search_url(&self, query: &str, params: &SearchParams) -> Result<Url, PexelsError> {
         let mut url = Url::parse(&format!("{}/search", self.base_url))?;
 
         // 添加查询参数
@@ -142,23 +391,59 @@ impl PexelsClient {
             url.query_pairs_mut().append_pair(&key, &value);
         }
 
-        let response = self.send_request(url).await?;
+        Ok(url)
+    }
 
-        match response.status() {
-            StatusCode::OK => {
-                let photos_page: PhotosPage = response.json().await?;
-                Ok(photos_page)
-            }
-            StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
-            StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError),
-            status => Err(PexelsError::ApiError(format!(
-                "Search photos failed with status: {status}"
-            ))),
+    /// 使用首选语言搜索照片，结果过少时回退到备用语言
+    ///
+    /// 先以 `primary` 语言搜索；若 `total_results < min_results`，再以 `fallback` 语言搜索，
+    /// 并返回两者中 `total_results` 较多的结果。`params` 中已有的 `locale` 会被覆盖。
+    ///
+    /// # 参数
+    ///
+    /// * `query` - 搜索关键词
+    /// * `params` - 搜索参数
+    /// * `primary` - 首选语言
+    /// * `fallback` - 备用语言
+    /// * `min_results` - 触发回退的最少结果数
+    pub async fn search_photos_with_locale_fallback(
+        &self,
+        query: &str,
+        params: &SearchParams,
+        primary: Locale,
+        fallback: Locale,
+        min_results: u32,
+    ) -> Result<LocaleFallback<PhotosPage>, PexelsError> {
+        let primary_params = params.clone().locale(primary.as_str());
+        let primary_page = self.search_photos(query, &primary_params).await?;
+        if primary_page.total_results >= min_results {
+            return Ok(LocaleFallback {
+                page: primary_page,
+                used_fallback: false,
+            });
+        }
+
+        let fallback_params = params.clone().locale(fallback.as_str());
+        let fallback_page = self.search_photos(query, &fallback_params).await?;
+        if fallback_page.total_results > primary_page.total_results {
+            Ok(LocaleFallback {
+                page: fallback_page,
+                used_fallback: true,
+            })
+        } else {
+            Ok(LocaleFallback {
+                page: primary_page,
+                used_fallback: false,
+            })
         }
     }
 
     /// 获取精选/推荐照片
     ///
+    /// Pexels 没有“热门照片”端点，精选照片即与 [`popular_videos`](Self::popular_videos)
+    /// 对应的照片浏览入口。与 [`Pexels::curated_photos`](crate::Pexels::curated_photos)
+    /// 请求相同的 URL
+    ///
     /// # 参数
     ///
     /// * `params` - 分页参数
@@ -166,31 +451,24 @@ impl PexelsClient {
     /// # 返回
     ///
     /// 包含精选照片响应或错误的结果
+    #[doc(alias = "popular_photos")]
     pub async fn curated_photos(
         &self,
         params: &PaginationParams,
     ) -> Result<PhotosPage, PexelsError> {
-        let mut url = Url::parse(&format!("{}/curated", self.base_url))?;
-
-        // 添加分页参数
-        if let Some(page) = params.page {
-            url.query_pairs_mut().append_pair("page", &page.to_string());
-        }
-
-        if let Some(per_page) = params.per_page {
-            url.query_pairs_mut()
-                .append_pair("per_page", &per_page.to_string());
-        }
+        let url = self.curated_url(params)?;
 
         let response = self.send_request(url).await?;
 
         match response.status() {
             StatusCode::OK => {
-                let photos_page: PhotosPage = response.json().await?;
+                let photos_page: PhotosPage = self.parse_json(response).await?;
                 Ok(photos_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
-            StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError),
+            StatusCode::TOO_MANY_REQUESTS => {
+                Err(PexelsError::RateLimitError(retry_after(response.headers())))
+            }
             status => Err(PexelsError::ApiError(format!(
                 "Curated photos failed with status: {status}"
             ))),
@@ -213,14 +491,16 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let photo: Photo = response.json().await?;
+                let photo: Photo = self.parse_json(response).await?;
                 Ok(photo)
             }
             StatusCode::NOT_FOUND => Err(PexelsError::NotFound(format!(
                 "Photo with ID {id} not found"
             ))),
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
-            StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError),
+            StatusCode::TOO_MANY_REQUESTS => {
+                Err(PexelsError::RateLimitError(retry_after(response.headers())))
+            }
             status => Err(PexelsError::ApiError(format!(
                 "Get photo failed with status: {status}"
             ))),
@@ -232,7 +512,7 @@ impl PexelsClient {
     /// # 参数
     ///
     /// * `query` - 搜索查询
-    /// * `params` - 其他搜索参数（分页、过滤器等）
+    /// * `params` - 其他搜索参数（分页、过滤器等），可以传引用或直接传入构建链的值
     ///
     /// # 返回
     ///
@@ -240,9 +520,10 @@ impl PexelsClient {
     pub async fn search_videos(
         &self,
         query: &str,
-        params: &VideoSearchParams,
+        params: impl Borrow<VideoSearchParams>,
     ) -> Result<VideosPage, PexelsError> {
-        let mut url = Url::parse(&format!("{}/videos/search", self.base_url))?;
+        let params = params.borrow();
+        let mut url = self.video_url("search")?;
 
         // 添加查询参数
         url.query_pairs_mut().append_pair("query", query);
@@ -274,11 +555,13 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let videos_page: VideosPage = response.json().await?;
+                let videos_page: VideosPage = self.parse_json(response).await?;
                 Ok(videos_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
-            StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError),
+            StatusCode::TOO_MANY_REQUESTS => {
+                Err(PexelsError::RateLimitError(retry_after(response.headers())))
+            }
             status => Err(PexelsError::ApiError(format!(
                 "Search videos failed with status: {status}"
             ))),
@@ -298,7 +581,7 @@ impl PexelsClient {
         &self,
         params: &PaginationParams,
     ) -> Result<VideosPage, PexelsError> {
-        let mut url = Url::parse(&format!("{}/videos/popular", self.base_url))?;
+        let mut url = self.video_url("popular")?;
 
         // 添加分页参数
         if let Some(page) = params.page {
@@ -314,11 +597,13 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let videos_page: VideosPage = response.json().await?;
+                let videos_page: VideosPage = self.parse_json(response).await?;
                 Ok(videos_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
-            StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError),
+            StatusCode::TOO_MANY_REQUESTS => {
+                Err(PexelsError::RateLimitError(retry_after(response.headers())))
+            }
             status => Err(PexelsError::ApiError(format!(
                 "Popular videos failed with status: {status}"
             ))),
@@ -335,26 +620,95 @@ impl PexelsClient {
     ///
     /// 包含视频或错误的结果
     pub async fn get_video(&self, id: u64) -> Result<Video, PexelsError> {
-        let url = Url::parse(&format!("{}/videos/videos/{}", self.base_url, id))?;
+        let url = self.video_url(&format!("videos/{id}"))?;
 
         let response = self.send_request(url).await?;
 
         match response.status() {
             StatusCode::OK => {
-                let video: Video = response.json().await?;
+                let video: Video = self.parse_json(response).await?;
                 Ok(video)
             }
             StatusCode::NOT_FOUND => Err(PexelsError::NotFound(format!(
                 "Video with ID {id} not found"
             ))),
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
-            StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError),
+            StatusCode::TOO_MANY_REQUESTS => {
+                Err(PexelsError::RateLimitError(retry_after(response.headers())))
+            }
             status => Err(PexelsError::ApiError(format!(
                 "Get video failed with status: {status}"
             ))),
         }
     }
 
+    /// 根据 ID 列表并发获取多张照片
+    ///
+    /// Pexels 没有批量端点，此方法并发调用 [`PexelsClient::get_photo`]，
+    /// 并使用信号量限制同时进行的请求数。
+    ///
+    /// # 参数
+    ///
+    /// * `ids` - 照片 ID 列表
+    /// * `concurrency` - 最大并发请求数（0 视为 1）
+    ///
+    /// # 返回
+    ///
+    /// 与输入顺序一一对应的结果列表
+    pub async fn get_photos(
+        &self,
+        ids: &[u64],
+        concurrency: usize,
+    ) -> Vec<Result<Photo, PexelsError>> {
+        let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+        let futures = ids.iter().map(|&id| {
+            let semaphore = Arc::clone(&semaphore);
+            async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .map_err(|_| PexelsError::AsyncError)?;
+                self.get_photo(id).await
+            }
+        });
+
+        join_all(futures).await
+    }
+
+    /// 根据 ID 列表并发获取多个视频
+    ///
+    /// 与 [`PexelsClient::get_photos`] 相同，基于 [`PexelsClient::get_video`] 实现。
+    ///
+    /// # 参数
+    ///
+    /// * `ids` - 视频 ID 列表
+    /// * `concurrency` - 最大并发请求数（0 视为 1）
+    ///
+    /// # 返回
+    ///
+    /// 与输入顺序一一对应的结果列表
+    pub async fn get_videos(
+        &self,
+        ids: &[u64],
+        concurrency: usize,
+    ) -> Vec<Result<Video, PexelsError>> {
+        let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+        let futures = ids.iter().map(|&id| {
+            let semaphore = Arc::clone(&semaphore);
+            async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .map_err(|_| PexelsError::AsyncError)?;
+                self.get_video(id).await
+            }
+        });
+
+        join_all(futures).await
+    }
+
     /// 获取收藏列表
     ///
     /// # 参数
@@ -368,27 +722,131 @@ impl PexelsClient {
         &self,
         params: &PaginationParams,
     ) -> Result<CollectionsPage, PexelsError> {
-        let mut url = Url::parse(&format!("{}/collections", self.base_url))?;
+        let url = Url::parse(&format!("{}/collections", self.base_url))?;
+        let url = Self::with_pagination(url, params);
 
-        // 添加分页参数
+        self.fetch_collections_page(url).await
+    }
+
+    /// 获取 Pexels 精选收藏
+    ///
+    /// 与 [`Pexels::featured_collections`](crate::Pexels::featured_collections) 请求相同的 URL
+    ///
+    /// # 参数
+    ///
+    /// * `params` - 分页参数
+    ///
+    /// # 返回
+    ///
+    /// 包含收藏响应或错误的结果
+    pub async fn featured_collections(
+        &self,
+        params: &PaginationParams,
+    ) -> Result<CollectionsPage, PexelsError> {
+        let url = self.featured_collections_url(params)?;
+
+        self.fetch_collections_page(url).await
+    }
+
+    /// 以流的形式逐个返回所有收藏
+    ///
+    /// 从 `params` 指定的页开始，按响应中的 `next_page` 惰性地请求后续页面；
+    /// 请求失败时产出该错误并结束流。
+    ///
+    /// # 参数
+    ///
+    /// * `params` - 起始页的分页参数
+    pub fn collections_stream(
+        &self,
+        params: &PaginationParams,
+    ) -> impl Stream<Item = Result<Collection, PexelsError>> + '_ {
+        let first = Url::parse(&format!("{}/collections", self.base_url))
+            .map(|url| Self::with_pagination(url, params))
+            .map_err(PexelsError::from);
+        Self::follow_next_pages(first, move |url| async move {
+            let page = self.fetch_collections_page(url).await?;
+            Ok((page.collections, page.next_page))
+        })
+    }
+
+    /// 以流的形式逐个返回收藏中的所有媒体项目
+    ///
+    /// 从 `params` 指定的页开始，按响应中的 `next_page` 惰性地请求后续页面；
+    /// 请求失败时产出该错误并结束流。
+    ///
+    /// # 参数
+    ///
+    /// * `id` - 收藏 ID
+    /// * `params` - 起始页的分页参数
+    pub fn collection_media_stream<'a>(
+        &'a self,
+        id: &'a str,
+        params: &PaginationParams,
+    ) -> impl Stream<Item = Result<MediaItemType, PexelsError>> + 'a {
+        let first = Url::parse(&format!("{}/collections/{}", self.base_url, id))
+            .map(|url| Self::with_pagination(url, params))
+            .map_err(PexelsError::from);
+        Self::follow_next_pages(first, move |url| async move {
+            let page = self.fetch_collection_media_page(id, url).await?;
+            Ok((page.media, page.next_page))
+        })
+    }
+
+    /// 为 URL 添加分页参数
+    fn with_pagination(mut url: Url, params: &PaginationParams) -> Url {
         if let Some(page) = params.page {
             url.query_pairs_mut().append_pair("page", &page.to_string());
         }
-
         if let Some(per_page) = params.per_page {
             url.query_pairs_mut()
                 .append_pair("per_page", &per_page.to_string());
         }
+        url
+    }
+
+    /// 从 `first` 开始请求页面，并沿 `next_page` 继续，将每页的条目展开为流
+    fn follow_next_pages<'a, T, F, Fut>(
+        first: Result<Url, PexelsError>,
+        fetch: F,
+    ) -> impl Stream<Item = Result<T, PexelsError>> + 'a
+    where
+        T: 'a,
+        F: Fn(Url) -> Fut + 'a,
+        Fut: Future<Output = Result<(Vec<T>, Option<String>), PexelsError>> + 'a,
+    {
+        stream::unfold(Some(first), move |next| {
+            let request = next.map(|url| url.map(&fetch));
+            async move {
+                let page = match request? {
+                    Ok(request) => request.await,
+                    Err(e) => Err(e),
+                };
+                let (items, next): (Vec<Result<T, PexelsError>>, _) = match page {
+                    Ok((items, next_page)) => (
+                        items.into_iter().map(Ok).collect(),
+                        next_page.map(|u| Url::parse(&u).map_err(PexelsError::from)),
+                    ),
+                    Err(e) => (vec![Err(e)], None),
+                };
+                Some((stream::iter(items), next))
+            }
+        })
+        .flatten()
+    }
 
+    /// 请求一页收藏并处理错误状态码
+    async fn fetch_collections_page(&self, url: Url) -> Result<CollectionsPage, PexelsError> {
         let response = self.send_request(url).await?;
 
         match response.status() {
             StatusCode::OK => {
-                let collections_page: CollectionsPage = response.json().await?;
+                let collections_page: CollectionsPage = self.parse_json(response).await?;
                 Ok(collections_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
-            StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError),
+            StatusCode::TOO_MANY_REQUESTS => {
+                Err(PexelsError::RateLimitError(retry_after(response.headers())))
+            }
             status => Err(PexelsError::ApiError(format!(
                 "Get collections failed with status: {status}"
             ))),
@@ -410,30 +868,32 @@ impl PexelsClient {
         id: &str,
         params: &PaginationParams,
     ) -> Result<MediaPage, PexelsError> {
-        let mut url = Url::parse(&format!("{}/collections/{}", self.base_url, id))?;
+        let url = Url::parse(&format!("{}/collections/{}", self.base_url, id))?;
+        let url = Self::with_pagination(url, params);
 
-        // 添加分页参数
-        if let Some(page) = params.page {
-            url.query_pairs_mut().append_pair("page", &page.to_string());
-        }
-
-        if let Some(per_page) = params.per_page {
-            url.query_pairs_mut()
-                .append_pair("per_page", &per_page.to_string());
-        }
+        self.fetch_collection_media_page(id, url).await
+    }
 
+    /// 请求一页收藏媒体并处理错误状态码
+    async fn fetch_collection_media_page(
+        &self,
+        id: &str,
+        url: Url,
+    ) -> Result<MediaPage, PexelsError> {
         let response = self.send_request(url).await?;
 
         match response.status() {
             StatusCode::OK => {
-                let media_page: MediaPage = response.json().await?;
+                let media_page: MediaPage = self.parse_json(response).await?;
                 Ok(media_page)
             }
             StatusCode::NOT_FOUND => Err(PexelsError::NotFound(format!(
                 "Collection with ID {id} not found"
             ))),
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
-            StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError),
+            StatusCode::TOO_MANY_REQUESTS => {
+                Err(PexelsError::RateLimitError(retry_after(response.headers())))
+            }
             status => Err(PexelsError::ApiError(format!(
                 "Get collection media failed with status: {status}"
             ))),
@@ -453,10 +913,673 @@ impl PexelsClient {
         let response = self
             .client
             .get(url)
-            .header(header::AUTHORIZATION, &self.api_key)
+            .header(header::AUTHORIZATION, self.authorization())
             .send()
             .await?;
+        self.rate_limit.record(response.headers());
 
         Ok(response)
     }
+
+    /// 解析 JSON 响应体
+    ///
+    /// 严格模式下使用 `serde_path_to_error`，错误信息中包含出错字段的 JSON 路径
+    async fn parse_json<T: DeserializeOwned>(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<T, PexelsError> {
+        if !self.strict_parse {
+            return Ok(response.json().await?);
+        }
+
+        let body = response.text().await?;
+        let deserializer = &mut serde_json::Deserializer::from_str(&body);
+        serde_path_to_error::deserialize(deserializer).map_err(|e| {
+            PexelsError::JsonParseError(serde::de::Error::custom(format!(
+                "{}: {}",
+                e.path(),
+                e.inner()
+            )))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Orientation, SearchBuilder, Size};
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn photo_json(id: u64) -> serde_json::Value {
+        json!({
+            "id": id,
+            "width": 1920,
+            "height": 1080,
+            "url": format!("https://www.pexels.com/photo/{id}/"),
+            "photographer": "Test Photographer",
+            "photographer_url": "https://www.pexels.com/@test",
+            "photographer_id": 1,
+            "avg_color": "#7E7E7E",
+            "src": {
+                "original": format!("https://images.pexels.com/photos/{id}/original.jpg"),
+                "large2x": format!("https://images.pexels.com/photos/{id}/large2x.jpg"),
+                "large": format!("https://images.pexels.com/photos/{id}/large.jpg"),
+                "medium": format!("https://images.pexels.com/photos/{id}/medium.jpg"),
+                "small": format!("https://images.pexels.com/photos/{id}/small.jpg"),
+                "portrait": format!("https://images.pexels.com/photos/{id}/portrait.jpg"),
+                "landscape": format!("https://images.pexels.com/photos/{id}/landscape.jpg"),
+                "tiny": format!("https://images.pexels.com/photos/{id}/tiny.jpg")
+            },
+            "alt": "Test photo"
+        })
+    }
+
+    fn video_json(id: u64) -> serde_json::Value {
+        json!({
+            "id": id,
+            "width": 1920,
+            "height": 1080,
+            "url": format!("https://www.pexels.com/video/{id}/"),
+            "image": format!("https://images.pexels.com/videos/{id}/preview.jpg"),
+            "duration": 12,
+            "user": {
+                "id": 1,
+                "name": "Test User",
+                "url": "https://www.pexels.com/@test"
+            },
+            "video_files": [{
+                "id": 1,
+                "quality": "hd",
+                "file_type": "video/mp4",
+                "width": 1920,
+                "height": 1080,
+                "link": format!("https://videos.pexels.com/video-files/{id}/hd.mp4")
+            }],
+            "video_pictures": []
+        })
+    }
+
+    #[test]
+    fn test_try_new_builds_client() {
+        assert!(PexelsClient::try_new("test_key").is_ok());
+        assert!(PexelsClient::try_with_config("test_key", 5, 1).is_ok());
+    }
+
+    #[test]
+    fn test_from_builder_propagates_build_error() {
+        // 非法的 User-Agent 会让 reqwest 在 build() 时返回错误
+        let builder = Client::builder().user_agent("invalid\nagent");
+        let result = PexelsClient::from_builder("test_key", builder);
+        assert!(matches!(result, Err(PexelsError::RequestError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_photos_preserves_order() {
+        let server = MockServer::start().await;
+        for id in [1u64, 3] {
+            Mock::given(method("GET"))
+                .and(path(format!("/photos/{id}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(photo_json(id)))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/photos/2"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        let results = client.get_photos(&[1, 2, 3], 2).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().id, 1);
+        assert!(matches!(results[1], Err(PexelsError::NotFound(_))));
+        assert_eq!(results[2].as_ref().unwrap().id, 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_videos_preserves_order() {
+        let server = MockServer::start().await;
+        for id in [10u64, 30] {
+            Mock::given(method("GET"))
+                .and(path(format!("/videos/videos/{id}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(video_json(id)))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/videos/videos/20"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        let results = client.get_videos(&[10, 20, 30], 0).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().id, 10);
+        assert!(matches!(results[1], Err(PexelsError::NotFound(_))));
+        assert_eq!(results[2].as_ref().unwrap().id, 30);
+    }
+
+    #[tokio::test]
+    async fn test_strict_parse_reports_json_path() {
+        let server = MockServer::start().await;
+        let mut body = photo_json(1);
+        body["width"] = json!("wide");
+        Mock::given(method("GET"))
+            .and(path("/photos/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+
+        let lenient = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        assert!(lenient.get_photo(1).await.is_err());
+
+        let strict = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap()
+            .with_strict_parse(true);
+        match strict.get_photo(1).await {
+            Err(PexelsError::JsonParseError(e)) => assert!(e.to_string().starts_with("width:")),
+            other => panic!("expected JsonParseError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cloned_clients_in_spawned_tasks() {
+        let server = MockServer::start().await;
+        for id in [1u64, 2, 3] {
+            Mock::given(method("GET"))
+                .and(path(format!("/photos/{id}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(photo_json(id)))
+                .mount(&server)
+                .await;
+        }
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        let handles: Vec<_> = [1u64, 2, 3]
+            .into_iter()
+            .map(|id| {
+                let client = client.clone();
+                tokio::spawn(async move { client.get_photo(id).await })
+            })
+            .collect();
+
+        for (handle, id) in handles.into_iter().zip([1u64, 2, 3]) {
+            let photo = handle.await.unwrap().unwrap();
+            assert_eq!(photo.id, id);
+        }
+    }
+
+    fn photos_page_json(total_results: u32, ids: &[u64]) -> serde_json::Value {
+        json!({
+            "page": 1,
+            "per_page": 15,
+            "photos": ids.iter().map(|id| photo_json(*id)).collect::<Vec<_>>(),
+            "total_results": total_results,
+            "prev_page": null,
+            "next_page": null
+        })
+    }
+
+    #[tokio::test]
+    async fn test_search_photos_falls_back_to_second_locale() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(query_param("locale", "ja-JP"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(photos_page_json(2, &[1, 2])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(query_param("locale", "en-US"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(photos_page_json(50, &[3, 4, 5])),
+            )
+            .mount(&server)
+            .await;
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        let result = client
+            .search_photos_with_locale_fallback(
+                "猫",
+                &SearchParams::new(),
+                Locale::ja_JP,
+                Locale::en_US,
+                10,
+            )
+            .await
+            .unwrap();
+
+        assert!(result.used_fallback);
+        assert_eq!(result.page.total_results, 50);
+        assert_eq!(result.page.photos[0].id, 3);
+    }
+
+    #[tokio::test]
+    async fn test_with_base_url_strips_trailing_slash() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/photos/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(photo_json(1)))
+            .mount(&server)
+            .await;
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(format!("{}/", server.uri()))
+            .unwrap();
+        assert_eq!(client.base_url, server.uri());
+        assert_eq!(client.get_photo(1).await.unwrap().id, 1);
+    }
+
+    #[test]
+    fn test_with_base_url_rejects_invalid_urls() {
+        assert!(matches!(
+            PexelsClient::new("test_key").with_base_url("htps://api.pexels.com/v1"),
+            Err(PexelsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            PexelsClient::new("test_key").with_base_url("not a url"),
+            Err(PexelsError::ParseError(_))
+        ));
+    }
+
+    fn collection_json(id: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "title": format!("Collection {id}"),
+            "description": null,
+            "private": false,
+            "media_count": 1,
+            "photos_count": 1,
+            "videos_count": 0
+        })
+    }
+
+    #[tokio::test]
+    async fn test_collections_stream_follows_next_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/collections"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "collections": [collection_json("a"), collection_json("b")],
+                "page": 1,
+                "per_page": 2,
+                "total_results": 3,
+                "next_page": format!("{}/collections?page=2&per_page=2", server.uri()),
+                "prev_page": null
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/collections"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "collections": [collection_json("c")],
+                "page": 2,
+                "per_page": 2,
+                "total_results": 3,
+                "next_page": null,
+                "prev_page": format!("{}/collections?page=1&per_page=2", server.uri())
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        let params = PaginationParams::new().page(1).per_page(2);
+        let ids: Vec<String> = client
+            .collections_stream(&params)
+            .map(|c| c.unwrap().id)
+            .collect()
+            .await;
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_collection_media_stream_follows_next_page() {
+        let server = MockServer::start().await;
+        let media = |id: u64| {
+            let mut photo = photo_json(id);
+            photo["type"] = json!("Photo");
+            photo
+        };
+        Mock::given(method("GET"))
+            .and(path("/collections/abc"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "abc",
+                "media": [media(1)],
+                "page": 1,
+                "per_page": 1,
+                "total_results": 2,
+                "next_page": format!("{}/collections/abc?page=2&per_page=1", server.uri()),
+                "prev_page": null
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/collections/abc"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "abc",
+                "media": [media(2)],
+                "page": 2,
+                "per_page": 1,
+                "total_results": 2,
+                "next_page": null,
+                "prev_page": null
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        let params = PaginationParams::new().page(1).per_page(1);
+        let ids: Vec<u64> = client
+            .collection_media_stream("abc", &params)
+            .map(|item| match item.unwrap() {
+                MediaItemType::Photo(photo) => photo.id,
+                MediaItemType::Video(video) => video.id,
+            })
+            .collect()
+            .await;
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_search_photos_accepts_owned_params() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(query_param("query", "x"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(photos_page_json(1, &[7])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        let page = client
+            .search_photos("x", SearchParams::new().page(1))
+            .await
+            .unwrap();
+        assert_eq!(page.photos[0].id, 7);
+    }
+
+    #[test]
+    fn test_photo_search_url_matches_builder_client() {
+        let client = PexelsClient::new("test_key");
+        let cases = [
+            SearchParams::new(),
+            SearchParams::new().page(2).per_page(30),
+            SearchParams::new()
+                .orientation(Orientation::Portrait)
+                .size(Size::Large)
+                .color("red")
+                .locale("ja-JP"),
+            SearchParams::new().color("#FFFFFF").locale("ru-RU"),
+        ];
+
+        for params in &cases {
+            let client_url = client.photo_search_url("山 mountains", params).unwrap();
+            let builder_url = SearchBuilder::from_params("山 mountains", params)
+                .unwrap()
+                .build()
+                .create_uri()
+                .unwrap();
+            assert_eq!(client_url.as_str(), builder_url, "{params:?}");
+        }
+    }
+
+    #[test]
+    fn test_browse_urls_match_builder_client() {
+        use crate::{CuratedBuilder, FeaturedBuilder};
+
+        let client = PexelsClient::new("test_key");
+        for (page, per_page) in [(None, None), (Some(2), None), (Some(3), Some(40))] {
+            let mut params = PaginationParams::new();
+            let mut curated = CuratedBuilder::new();
+            let mut featured = FeaturedBuilder::new();
+            if let Some(page) = page {
+                params = params.page(page);
+                curated = curated.page(page as usize);
+                featured = featured.page(page as usize);
+            }
+            if let Some(per_page) = per_page {
+                params = params.per_page(per_page);
+                curated = curated.per_page(per_page as usize);
+                featured = featured.per_page(per_page as usize);
+            }
+
+            assert_eq!(
+                client.curated_url(&params).unwrap().as_str(),
+                curated.build().create_uri().unwrap(),
+                "{params:?}"
+            );
+            assert_eq!(
+                client.featured_collections_url(&params).unwrap().as_str(),
+                featured.build().create_uri().unwrap(),
+                "{params:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_params_rejects_unknown_color() {
+        let params = SearchParams::new().color("plaid");
+        assert!(matches!(
+            SearchBuilder::from_params("cats", &params),
+            Err(PexelsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_video_urls_skip_version_prefix() {
+        let client = PexelsClient::new("test_key");
+        assert_eq!(
+            client.video_url("videos/2499611").unwrap().as_str(),
+            "https://api.pexels.com/videos/videos/2499611"
+        );
+        assert_eq!(
+            client.video_url("search").unwrap().as_str(),
+            "https://api.pexels.com/videos/search"
+        );
+        assert_eq!(
+            client.video_url("popular").unwrap().as_str(),
+            "https://api.pexels.com/videos/popular"
+        );
+    }
+
+    fn gzip(body: &[u8]) -> Vec<u8> {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_search_photos_decodes_gzip_response() {
+        use wiremock::matchers::header_regex;
+
+        let server = MockServer::start().await;
+        let body = serde_json::to_vec(&photos_page_json(2, &[1, 2])).unwrap();
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(header_regex("accept-encoding", "gzip"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .set_body_raw(gzip(&body), "application/json"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        let page = client
+            .search_photos("x", SearchParams::new())
+            .await
+            .unwrap();
+        let ids: Vec<u64> = page.photos.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_from_builder_keeps_caller_compression_settings() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(photos_page_json(1, &[1])))
+            .mount(&server)
+            .await;
+
+        let builder = reqwest::Client::builder().no_gzip().no_brotli();
+        let client = PexelsClient::from_builder("test_key", builder)
+            .unwrap()
+            .with_base_url(server.uri())
+            .unwrap();
+        client
+            .search_photos("x", SearchParams::new())
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.get("accept-encoding").is_none());
+    }
+
+    /// 记录经过的请求并附加 `x-request-id` 头的测试中间件
+    #[cfg(feature = "middleware")]
+    struct RecordingMiddleware {
+        seen: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[cfg(feature = "middleware")]
+    #[async_trait::async_trait]
+    impl reqwest_middleware::Middleware for RecordingMiddleware {
+        async fn handle(
+            &self,
+            mut req: reqwest::Request,
+            extensions: &mut http::Extensions,
+            next: reqwest_middleware::Next<'_>,
+        ) -> reqwest_middleware::Result<reqwest::Response> {
+            self.seen.lock().unwrap().push(req.url().path().to_string());
+            req.headers_mut()
+                .insert("x-request-id", "test-id".parse().unwrap());
+            next.run(req, extensions).await
+        }
+    }
+
+    #[cfg(feature = "middleware")]
+    #[tokio::test]
+    async fn test_middleware_observes_request() {
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(header("x-request-id", "test-id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(photos_page_json(1, &[5])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let http = reqwest_middleware::ClientBuilder::new(Client::new())
+            .with(RecordingMiddleware {
+                seen: Arc::clone(&seen),
+            })
+            .build();
+        let client = PexelsClient::with_middleware("test_key", http)
+            .with_base_url(server.uri())
+            .unwrap();
+
+        let page = client
+            .search_photos("x", SearchParams::new())
+            .await
+            .unwrap();
+        assert_eq!(page.photos[0].id, 5);
+        assert_eq!(*seen.lock().unwrap(), vec!["/search".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_auth_scheme_formats_authorization_header() {
+        use wiremock::matchers::header;
+
+        for (scheme, expected) in [
+            (AuthScheme::Bare, "test_key"),
+            (AuthScheme::Bearer, "Bearer test_key"),
+        ] {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/photos/5"))
+                .and(header("authorization", expected))
+                .respond_with(ResponseTemplate::new(200).set_body_json(photo_json(5)))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let client = PexelsClient::new("test_key")
+                .with_auth_scheme(scheme)
+                .with_base_url(server.uri())
+                .unwrap();
+            assert_eq!(client.get_photo(5).await.unwrap().id, 5);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_recorded_from_response_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/photos/5"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-Ratelimit-Limit", "20000")
+                    .insert_header("X-Ratelimit-Remaining", "19999")
+                    .insert_header("X-Ratelimit-Reset", "1590529646")
+                    .set_body_json(photo_json(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = PexelsClient::new("test_key")
+            .with_base_url(server.uri())
+            .unwrap();
+        assert_eq!(client.rate_limit(), None);
+
+        client.get_photo(5).await.unwrap();
+        let info = client.clone().rate_limit().unwrap();
+        assert_eq!(info.limit, 20000);
+        assert_eq!(info.remaining, 19999);
+        assert_eq!(info.reset, 1590529646);
+    }
 }
@@ -1,6 +1,8 @@
 use crate::{
-    CollectionsResponse, Pexels, PexelsError, PEXELS_API, PEXELS_COLLECTIONS_PATH, PEXELS_VERSION,
+    CollectionSource, CollectionsResponse, Pexels, PexelsError, PEXELS_API,
+    PEXELS_COLLECTIONS_PATH, PEXELS_VERSION,
 };
+use serde_json::Value;
 use url::Url;
 
 /// Path to get featured collections.
@@ -43,7 +45,13 @@ impl Featured {
     pub async fn fetch(&self, client: &Pexels) -> Result<CollectionsResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let collection_response: CollectionsResponse = serde_json::from_value(response)?;
+        Self::parse_response(response)
+    }
+
+    /// Parses the featured collections response and marks it with [`CollectionSource::Featured`].
+    fn parse_response(response: Value) -> Result<CollectionsResponse, PexelsError> {
+        let mut collection_response: CollectionsResponse = serde_json::from_value(response)?;
+        collection_response.source = CollectionSource::Featured;
         Ok(collection_response)
     }
 }
@@ -84,3 +92,20 @@ impl FeaturedBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response_marks_source() {
+        let response = serde_json::json!({
+            "collections": [],
+            "page": 1,
+            "per_page": 15,
+            "total_results": 0
+        });
+        let parsed = Featured::parse_response(response).unwrap();
+        assert_eq!(parsed.source, CollectionSource::Featured);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
-    CollectionsResponse, Pexels, PexelsError, PEXELS_API, PEXELS_COLLECTIONS_PATH, PEXELS_VERSION,
+    CollectionSource, CollectionsResponse, Pexels, PexelsError, PEXELS_API,
+    PEXELS_COLLECTIONS_PATH, PEXELS_VERSION,
 };
+use serde_json::Value;
 use url::Url;
 
 /// Represents a request to fetch a list of collections from the Pexels API.
@@ -38,7 +40,13 @@ impl Collections {
     pub async fn fetch(&self, client: &Pexels) -> Result<CollectionsResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let collections_response: CollectionsResponse = serde_json::from_value(response)?;
+        Self::parse_response(response)
+    }
+
+    /// Parses the collections response and marks it with [`CollectionSource::All`].
+    fn parse_response(response: Value) -> Result<CollectionsResponse, PexelsError> {
+        let mut collections_response: CollectionsResponse = serde_json::from_value(response)?;
+        collections_response.source = CollectionSource::All;
         Ok(collections_response)
     }
 }
@@ -79,3 +87,20 @@ impl CollectionsBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response_marks_source() {
+        let response = serde_json::json!({
+            "collections": [],
+            "page": 1,
+            "per_page": 15,
+            "total_results": 0
+        });
+        let parsed = Collections::parse_response(response).unwrap();
+        assert_eq!(parsed.source, CollectionSource::All);
+    }
+}
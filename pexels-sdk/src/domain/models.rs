@@ -1,5 +1,25 @@
 use serde::{Deserialize, Serialize};
 
+/// Ceiling division of `total` by `per_page`, guarded against a zero page size.
+fn total_pages(total: u32, per_page: u32) -> u32 {
+    if per_page == 0 {
+        0
+    } else {
+        total.div_ceil(per_page)
+    }
+}
+
+/// Which endpoint a [`CollectionsResponse`] was fetched from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionSource {
+    /// The authenticated user's collections (`/collections`).
+    #[default]
+    All,
+    /// Collections featured by Pexels (`/collections/featured`).
+    Featured,
+}
+
 /// Represents the response for a list of collections.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CollectionsResponse {
@@ -9,6 +29,21 @@ pub struct CollectionsResponse {
     pub total_results: u32,
     pub next_page: Option<String>,
     pub prev_page: Option<String>,
+    /// Endpoint the collections came from. Not part of the API response; set by the client.
+    #[serde(default)]
+    pub source: CollectionSource,
+}
+
+impl CollectionsResponse {
+    /// Total number of pages, or 0 when `per_page` is 0.
+    pub fn total_pages(&self) -> u32 {
+        total_pages(self.total_results, self.per_page)
+    }
+
+    /// Whether the API reported a next page.
+    pub fn has_next_page(&self) -> bool {
+        self.next_page.is_some()
+    }
 }
 
 /// Represents a Pexels collection.
@@ -146,6 +181,18 @@ pub struct PhotosResponse {
     pub prev_page: Option<String>,
 }
 
+impl PhotosResponse {
+    /// Total number of pages, or 0 when `per_page` is 0.
+    pub fn total_pages(&self) -> u32 {
+        total_pages(self.total_results, self.per_page)
+    }
+
+    /// Whether the API reported a next page.
+    pub fn has_next_page(&self) -> bool {
+        self.next_page.is_some()
+    }
+}
+
 /// Represents the response for a list of videos.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct VideoResponse {
@@ -158,6 +205,18 @@ pub struct VideoResponse {
     pub next_page: Option<String>,
 }
 
+impl VideoResponse {
+    /// Total number of pages, or 0 when `per_page` is 0.
+    pub fn total_pages(&self) -> u32 {
+        total_pages(self.total_results, self.per_page)
+    }
+
+    /// Whether the API reported a next page.
+    pub fn has_next_page(&self) -> bool {
+        self.next_page.is_some()
+    }
+}
+
 /// Represents a Pexels video.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Video {
@@ -213,3 +272,66 @@ pub struct VideoPicture {
     #[serde(rename = "picture")]
     pub picture_url: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn photos_response(
+        total_results: u32,
+        per_page: u32,
+        next_page: Option<&str>,
+    ) -> PhotosResponse {
+        PhotosResponse {
+            total_results,
+            page: 1,
+            per_page,
+            photos: vec![],
+            next_page: next_page.map(str::to_string),
+            prev_page: None,
+        }
+    }
+
+    #[test]
+    fn test_total_pages_rounds_up() {
+        assert_eq!(photos_response(0, 15, None).total_pages(), 0);
+        assert_eq!(photos_response(15, 15, None).total_pages(), 1);
+        assert_eq!(photos_response(16, 15, None).total_pages(), 2);
+
+        let collections = CollectionsResponse {
+            collections: vec![],
+            page: 1,
+            per_page: 10,
+            total_results: 21,
+            next_page: None,
+            prev_page: None,
+            source: CollectionSource::All,
+        };
+        assert_eq!(collections.total_pages(), 3);
+    }
+
+    #[test]
+    fn test_total_pages_zero_per_page() {
+        assert_eq!(photos_response(100, 0, None).total_pages(), 0);
+
+        let videos = VideoResponse {
+            page: 1,
+            per_page: 0,
+            total_results: 100,
+            url: String::new(),
+            videos: vec![],
+            prev_page: None,
+            next_page: None,
+        };
+        assert_eq!(videos.total_pages(), 0);
+    }
+
+    #[test]
+    fn test_has_next_page() {
+        assert!(!photos_response(10, 15, None).has_next_page());
+        assert!(
+            photos_response(30, 15, Some("https://api.pexels.com/v1/search?page=2"))
+                .has_next_page()
+        );
+    }
+}
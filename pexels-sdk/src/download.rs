@@ -1,10 +1,11 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use futures::stream::StreamExt;
 use reqwest::header::HeaderMap;
-use reqwest::{header, Client};
+use reqwest::{header, Client, ClientBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Semaphore;
@@ -39,9 +40,34 @@ pub type ProgressCallback = fn(current: u64, total: u64);
 /// Result type alias
 type Result<T> = std::result::Result<T, PexelsError>;
 
+/// Validators of a remote file, stored next to a partial download
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PartMeta {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    total: Option<u64>,
+}
+
+impl PartMeta {
+    fn from_headers(headers: &HeaderMap, total: Option<u64>) -> Self {
+        let value = |name| {
+            headers
+                .get(name)
+                .and_then(|v: &header::HeaderValue| v.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            etag: value(header::ETAG),
+            last_modified: value(header::LAST_MODIFIED),
+            total,
+        }
+    }
+}
+
 pub struct DownloadManager {
     client: Client,
     max_concurrent: usize,
+    progress_interval: Duration,
 }
 
 impl DownloadManager {
@@ -50,17 +76,39 @@ impl DownloadManager {
     ///
     /// # Arguments
     /// * `max_concurrent` - Maximum number of concurrent downloads
+    ///
+    /// # Note
+    /// If the underlying HTTP client fails to build (e.g. TLS initialization fails),
+    /// this silently falls back to the default `reqwest` client and the timeout and
+    /// pool settings are not applied. Use [`DownloadManager::try_new`] to observe the error.
     pub fn new(max_concurrent: usize) -> Self {
-        let client = Client::builder()
+        Self::try_new(max_concurrent)
+            .unwrap_or_else(|_| Self::with_client(Client::default(), max_concurrent))
+    }
+
+    /// Create a new 'DownloadManager', returning an error if the HTTP client fails to build
+    /// The default timeout is set to 60 seconds
+    ///
+    /// # Arguments
+    /// * `max_concurrent` - Maximum number of concurrent downloads
+    ///
+    /// # Errors
+    /// Returns `PexelsError::RequestError` if the underlying `reqwest::Client` cannot be built
+    pub fn try_new(max_concurrent: usize) -> Result<Self> {
+        // Media files are already compressed, and a content-encoded body would break
+        // byte-range resumption, so responses are requested without compression
+        let builder = Client::builder()
             .timeout(Duration::from_secs(60))
             .pool_max_idle_per_host(20)
-            .build()
-            .unwrap_or_default();
+            .no_gzip()
+            .no_brotli();
 
-        Self {
-            client,
-            max_concurrent,
-        }
+        Self::from_builder(builder, max_concurrent)
+    }
+
+    /// Build a 'DownloadManager' from a 'ClientBuilder', surfacing build errors
+    pub(crate) fn from_builder(builder: ClientBuilder, max_concurrent: usize) -> Result<Self> {
+        Ok(Self::with_client(builder.build()?, max_concurrent))
     }
 
     /// Create a 'DownloadManager' with a custom 'Client'
@@ -68,9 +116,19 @@ impl DownloadManager {
         Self {
             client,
             max_concurrent,
+            progress_interval: Duration::from_millis(100),
         }
     }
 
+    /// Set the minimum interval between two progress callbacks of the same download
+    ///
+    /// Defaults to 100ms so a UI is not flooded with an update per chunk. A zero interval
+    /// disables throttling. The final update of each download is always reported.
+    pub fn with_progress_interval(mut self, interval: Duration) -> Self {
+        self.progress_interval = interval;
+        self
+    }
+
     /// Download the photos from the given URL and save to the specified output directory
     /// Resumable upload is supported
     ///
@@ -141,6 +199,7 @@ impl DownloadManager {
             let dir = output_dir.clone();
             let client = self.client.clone();
             let callback = progress_callback;
+            let interval = self.progress_interval;
 
             let handle = tokio::spawn(async move {
                 let _permit = permit.await.map_err(|_| PexelsError::AsyncError)?;
@@ -164,53 +223,7 @@ impl DownloadManager {
                     fs::create_dir_all(&dir).await?;
                 }
 
-                // Resumable upload logic
-                let mut headers = HeaderMap::new();
-                let mut range_start = 0;
-
-                if path.exists() {
-                    if let Ok(metadata) = fs::metadata(&path).await {
-                        range_start = metadata.len();
-                        headers.insert(
-                            header::RANGE,
-                            format!("bytes={range_start}-").parse().unwrap(),
-                        );
-                    }
-                }
-
-                // Download the file
-                let response = client.get(url).headers(headers).send().await?;
-
-                if !response.status().is_success() {
-                    return Err(PexelsError::DownloadError(format!(
-                        "Failed to download file: {}",
-                        response.status()
-                    )));
-                }
-
-                // Get the file size
-                let total_size = response.content_length().unwrap_or(0) + range_start;
-
-                let mut file = if range_start > 0 {
-                    fs::OpenOptions::new().append(true).open(&path).await?
-                } else {
-                    fs::File::create(&path).await?
-                };
-
-                let mut stream = response.bytes_stream();
-                let mut downloaded = range_start;
-
-                while let Some(chunk) = stream.next().await {
-                    let chunk = chunk?;
-                    file.write_all(&chunk).await?;
-
-                    downloaded += chunk.len() as u64;
-
-                    // Call progress callback (if provided)
-                    if let Some(cb) = callback {
-                        cb(downloaded, total_size);
-                    }
-                }
+                Self::fetch_resumable(&client, url, &path, callback, interval).await?;
 
                 Ok::<PathBuf, PexelsError>(path)
             });
@@ -262,6 +275,7 @@ impl DownloadManager {
             let dir = output_dir.clone();
             let client = self.client.clone();
             let callback = progress_callback;
+            let interval = self.progress_interval;
 
             let handle = tokio::spawn(async move {
                 let _permit = permit.await.map_err(|_| PexelsError::AsyncError)?;
@@ -292,53 +306,7 @@ impl DownloadManager {
                     fs::create_dir_all(&dir).await?;
                 }
 
-                // Resumable upload logic
-                let mut headers = HeaderMap::new();
-                let mut range_start = 0;
-
-                if path.exists() {
-                    if let Ok(metadata) = fs::metadata(&path).await {
-                        range_start = metadata.len();
-                        headers.insert(
-                            header::RANGE,
-                            format!("bytes={range_start}-").parse().unwrap(),
-                        );
-                    }
-                }
-
-                // Download the file
-                let response = client.get(url).headers(headers).send().await?;
-
-                if !response.status().is_success() {
-                    return Err(PexelsError::DownloadError(format!(
-                        "Failed to download file: {}",
-                        response.status()
-                    )));
-                }
-
-                // Get the file size
-                let total_size = response.content_length().unwrap_or(0) + range_start;
-
-                let mut file = if range_start > 0 {
-                    fs::OpenOptions::new().append(true).open(&path).await?
-                } else {
-                    fs::File::create(&path).await?
-                };
-
-                let mut stream = response.bytes_stream();
-                let mut downloaded = range_start;
-
-                while let Some(chunk) = stream.next().await {
-                    let chunk = chunk?;
-                    file.write_all(&chunk).await?;
-
-                    downloaded += chunk.len() as u64;
-
-                    // Call progress callback (if provided)
-                    if let Some(cb) = callback {
-                        cb(downloaded, total_size);
-                    }
-                }
+                Self::fetch_resumable(&client, url, &path, callback, interval).await?;
 
                 Ok::<PathBuf, PexelsError>(path)
             });
@@ -385,22 +353,56 @@ impl DownloadManager {
             fs::create_dir_all(&output_dir).await?;
         }
 
-        // Resumable upload logic
-        let mut headers = HeaderMap::new();
-        let mut range_start = 0;
+        Self::fetch_resumable(&self.client, url, &path, None, self.progress_interval).await?;
 
+        Ok(path)
+    }
+
+    /// Download `url` to `path`, resuming from `<path>.part` when possible
+    ///
+    /// Data is written to `<path>.part` and renamed to `path` once complete; an existing
+    /// `path` is treated as already downloaded. When the partial file is first created, the
+    /// remote `ETag`, `Last-Modified` and total length are stored in `<path>.part.meta`.
+    /// Before resuming, a `Range: bytes=0-0` probe re-reads them and the partial file is
+    /// discarded if any differs, so a remote file that changed between runs is downloaded
+    /// afresh instead of being appended to stale bytes.
+    ///
+    /// Progress callbacks are at least `interval` apart, except for the final one.
+    async fn fetch_resumable(
+        client: &Client,
+        url: &str,
+        path: &Path,
+        callback: Option<ProgressCallback>,
+        interval: Duration,
+    ) -> Result<()> {
         if path.exists() {
-            if let Ok(metadata) = fs::metadata(&path).await {
+            return Ok(());
+        }
+
+        let part_path = Self::sibling_path(path, "part");
+        let meta_path = Self::sibling_path(path, "part.meta");
+
+        let mut range_start = 0;
+        if let Ok(metadata) = fs::metadata(&part_path).await {
+            let stored = match fs::read(&meta_path).await {
+                Ok(bytes) => serde_json::from_slice::<PartMeta>(&bytes).ok(),
+                Err(_) => None,
+            };
+            let current = Self::probe(client, url).await?;
+            if stored.is_some_and(|stored| stored == current) {
                 range_start = metadata.len();
-                headers.insert(
-                    header::RANGE,
-                    format!("bytes={range_start}-").parse().unwrap(),
-                );
             }
         }
 
-        // Send a request
-        let response = self.client.get(url).headers(headers).send().await?;
+        let mut headers = HeaderMap::new();
+        if range_start > 0 {
+            headers.insert(
+                header::RANGE,
+                format!("bytes={range_start}-").parse().unwrap(),
+            );
+        }
+
+        let response = client.get(url).headers(headers).send().await?;
 
         if !response.status().is_success() {
             return Err(PexelsError::DownloadError(format!(
@@ -409,23 +411,89 @@ impl DownloadManager {
             )));
         }
 
-        // Get the file size
-        let _total_size = response.content_length().unwrap_or(0) + range_start;
+        // The server may ignore the range and send the whole file
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            range_start = 0;
+        }
 
         let mut file = if range_start > 0 {
-            fs::OpenOptions::new().append(true).open(&path).await?
+            fs::OpenOptions::new().append(true).open(&part_path).await?
         } else {
-            fs::File::create(&path).await?
+            let meta = PartMeta::from_headers(response.headers(), response.content_length());
+            let meta = serde_json::to_vec(&meta)?;
+            fs::write(&meta_path, meta).await?;
+            fs::File::create(&part_path).await?
         };
 
+        // Get the file size
+        let total_size = response.content_length().unwrap_or(0) + range_start;
         let mut stream = response.bytes_stream();
+        let mut downloaded = range_start;
+        let mut reported = range_start;
+        let mut last_report: Option<Instant> = None;
 
         while let Some(chunk) = stream.next().await {
             let chunk = chunk?;
             file.write_all(&chunk).await?;
+
+            downloaded += chunk.len() as u64;
+
+            // Call progress callback (if provided), throttled to `interval`
+            if let Some(cb) = callback {
+                if last_report.is_none_or(|at| at.elapsed() >= interval) {
+                    cb(downloaded, total_size);
+                    reported = downloaded;
+                    last_report = Some(Instant::now());
+                }
+            }
         }
+        file.flush().await?;
 
-        Ok(path)
+        // Always report the final size
+        if let Some(cb) = callback {
+            if reported != downloaded {
+                cb(downloaded, total_size);
+            }
+        }
+
+        fs::rename(&part_path, path).await?;
+        let _ = fs::remove_file(&meta_path).await;
+        Ok(())
+    }
+
+    /// Fetch the validators of the remote file with a one-byte range request
+    async fn probe(client: &Client, url: &str) -> Result<PartMeta> {
+        let response = client
+            .get(url)
+            .header(header::RANGE, "bytes=0-0")
+            .send()
+            .await?;
+
+        // `Content-Range: bytes 0-0/<total>` carries the full length of a ranged response
+        let total = response
+            .headers()
+            .get(header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.rsplit('/').next())
+            .and_then(|v| v.parse().ok())
+            .or_else(|| {
+                (response.status() == StatusCode::OK)
+                    .then(|| response.content_length())
+                    .flatten()
+            });
+
+        Ok(PartMeta {
+            total,
+            ..PartMeta::from_headers(response.headers(), None)
+        })
+    }
+
+    /// `path` with `.{suffix}` appended to the file name
+    fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".");
+        name.push(suffix);
+        PathBuf::from(name)
     }
 
     /// Get the photo URL
@@ -511,4 +579,160 @@ mod tests {
             "https://images.pexels.com/photos/1/large2x.jpg"
         );
     }
+
+    #[test]
+    async fn test_try_new_propagates_build_error() {
+        assert!(DownloadManager::try_new(5).is_ok());
+
+        // An invalid User-Agent makes reqwest fail in build()
+        let builder = Client::builder().user_agent("invalid\nagent");
+        let result = DownloadManager::from_builder(builder, 5);
+        assert!(matches!(result, Err(PexelsError::RequestError(_))));
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pexels_sdk_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    async fn write_partial(dir: &Path, file_name: &str, bytes: &[u8], etag: &str, total: u64) {
+        let path = dir.join(file_name);
+        fs::write(DownloadManager::sibling_path(&path, "part"), bytes)
+            .await
+            .unwrap();
+        let meta = PartMeta {
+            etag: Some(etag.to_string()),
+            last_modified: None,
+            total: Some(total),
+        };
+        fs::write(
+            DownloadManager::sibling_path(&path, "part.meta"),
+            serde_json::to_vec(&meta).unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    async fn test_resume_restarts_when_etag_changes() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/photo.jpg"))
+            .and(header("range", "bytes=0-0"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("etag", "\"v2\"")
+                    .insert_header("content-range", "bytes 0-0/10")
+                    .set_body_bytes("N"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/photo.jpg"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v2\"")
+                    .set_body_bytes("NEWCONTENT"),
+            )
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("etag_changed");
+        write_partial(&dir, "photo.jpg", b"OLD", "\"v1\"", 10).await;
+
+        let manager = DownloadManager::new(1);
+        let url = format!("{}/photo.jpg", server.uri());
+        let path = manager
+            .download_file(&url, &dir, "photo.jpg")
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"NEWCONTENT");
+        assert!(!DownloadManager::sibling_path(&path, "part").exists());
+        assert!(!DownloadManager::sibling_path(&path, "part.meta").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    async fn test_resume_appends_when_etag_matches() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/photo.jpg"))
+            .and(header("range", "bytes=0-0"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("etag", "\"v1\"")
+                    .insert_header("content-range", "bytes 0-0/10")
+                    .set_body_bytes("O"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/photo.jpg"))
+            .and(header("range", "bytes=3-"))
+            .respond_with(ResponseTemplate::new(206).set_body_bytes("CONTENT"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("etag_matches");
+        write_partial(&dir, "photo.jpg", b"OLD", "\"v1\"", 10).await;
+
+        let manager = DownloadManager::new(1);
+        let url = format!("{}/photo.jpg", server.uri());
+        let path = manager
+            .download_file(&url, &dir, "photo.jpg")
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"OLDCONTENT");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    async fn test_progress_callbacks_are_throttled() {
+        use std::sync::Mutex;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        static CALLS: Mutex<Vec<(Instant, u64, u64)>> = Mutex::new(Vec::new());
+        fn record(current: u64, total: u64) {
+            CALLS.lock().unwrap().push((Instant::now(), current, total));
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/photo.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 512 * 1024]))
+            .mount(&server)
+            .await;
+
+        let dir = temp_dir("progress_interval");
+        let interval = Duration::from_millis(50);
+        let url = format!("{}/photo.jpg", server.uri());
+        DownloadManager::fetch_resumable(
+            &Client::new(),
+            &url,
+            &dir.join("photo.jpg"),
+            Some(record),
+            interval,
+        )
+        .await
+        .unwrap();
+
+        let calls = CALLS.lock().unwrap();
+        let (last, throttled) = calls.split_last().unwrap();
+        assert_eq!((last.1, last.2), (512 * 1024, 512 * 1024));
+        assert!(throttled.windows(2).all(|w| w[1].0 - w[0].0 >= interval));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -45,6 +45,17 @@ async fn main() {
 
 您可以使用 `cargo run` 来运行它！就是如此简单。
 
+# 选择客户端
+
+本库提供两个客户端：
+
+* [`PexelsClient`] - 推荐使用。接受 [`SearchParams`] 等参数结构体，返回强类型结果，
+  并区分认证失败、速率限制等错误。
+* [`Pexels`] - 基于构建器（如 [`SearchBuilder`]）的客户端，保留以兼容旧代码。
+  [`Pexels::search_photos_with_params`] 接受同样的 [`SearchParams`]。
+
+对于相同的搜索条件，两个客户端会生成相同的请求 URL。
+
 # 随机照片
 
 如果您想获取随机照片，可以使用 `curated_photos` 函数并将 `per_page` 设置为 1，`page` 设置为 1 到 1000 之间的随机数，以获取漂亮的随机照片。如果您想要获取特定主题的随机照片，也可以对热门搜索使用相同的方法。
@@ -61,12 +72,15 @@ async fn main() {
 * tiny - 此图片宽度为 280 像素，高度为 200 像素。
 */
 
+#[cfg(feature = "blocking")]
+mod blocking;
 mod client;
 mod collections;
 mod domain;
 mod download;
 mod models;
 mod photos;
+mod rate_limit;
 mod search;
 mod videos;
 
@@ -79,6 +93,7 @@ pub use collections::media::Media;
 pub use collections::media::MediaBuilder;
 /// domain 模块
 pub use domain::models::Collection;
+pub use domain::models::CollectionSource;
 pub use domain::models::CollectionsResponse;
 pub use domain::models::MediaPhoto;
 pub use domain::models::MediaResponse;
@@ -109,15 +124,22 @@ pub use videos::search::SearchBuilder as VideoSearchBuilder;
 pub use videos::video::FetchVideo;
 pub use videos::video::FetchVideoBuilder;
 
+pub use client::AuthScheme;
+pub use client::LocaleFallback;
 pub use client::PexelsClient;
+pub use models::MediaItemType;
+pub use rate_limit::RateLimitInfo;
+pub use search::PaginationParams;
 pub use search::SearchParams;
+pub use search::VideoSearchParams;
 
 pub use download::DownloadManager;
 pub use download::ProgressCallback;
 
 /// 导入依赖包
-use reqwest::Client;
+use rate_limit::RateLimitState;
 use reqwest::Error as ReqwestError;
+use reqwest::{Client, StatusCode};
 use serde_json::Error as JSONError;
 use serde_json::Value;
 use std::env::VarError;
@@ -349,7 +371,7 @@ impl Locale {
             Locale::nb_NO => "nb-NO",
             Locale::sk_SK => "sk-SK",
             Locale::tr_TR => "tr-TR",
-            Locale::ru_RU => "-ES",
+            Locale::ru_RU => "ru-RU",
         }
     }
 }
@@ -487,8 +509,9 @@ pub enum PexelsError {
     IoError(#[from] std::io::Error),
     #[error("API 错误: {0}")]
     ApiError(String),
+    /// 超出速率限制（HTTP 429），附带 `Retry-After` 响应头给出的等待时长（如有）
     #[error("超出速率限制")]
-    RateLimitError,
+    RateLimitError(Option<std::time::Duration>),
     #[error("认证错误: {0}")]
     AuthError(String),
     #[error("无效的参数: {0}")]
@@ -499,6 +522,19 @@ pub enum PexelsError {
     AsyncError,
     #[error("未知错误: {0}")]
     Unknown(String),
+    #[cfg(feature = "middleware")]
+    #[error("中间件错误: {0}")]
+    MiddlewareError(String),
+}
+
+#[cfg(feature = "middleware")]
+impl From<reqwest_middleware::Error> for PexelsError {
+    fn from(e: reqwest_middleware::Error) -> Self {
+        match e {
+            reqwest_middleware::Error::Reqwest(e) => PexelsError::RequestError(e),
+            reqwest_middleware::Error::Middleware(e) => PexelsError::MiddlewareError(e.to_string()),
+        }
+    }
 }
 
 // Manual implementation PartialEq
@@ -565,9 +601,13 @@ impl PartialEq for PexelsError {
 ///     println!("{:?}", response);
 /// }
 /// ```
+///
+/// `Pexels` 可以廉价克隆，克隆之间共享底层 HTTP 连接池。
+#[derive(Clone)]
 pub struct Pexels {
     client: Client,
     api_key: String,
+    rate_limit: RateLimitState,
 }
 
 impl Pexels {
@@ -591,26 +631,54 @@ impl Pexels {
     /// ```
     pub fn new(api_key: String) -> Self {
         Pexels {
-            client: Client::new(),
+            // 与 `Client::new()` 相同，但显式启用 gzip/brotli 响应解压
+            client: Client::builder()
+                .gzip(true)
+                .brotli(true)
+                .build()
+                .expect("无法创建 HTTP 客户端"),
             api_key,
+            rate_limit: RateLimitState::default(),
         }
     }
 
+    /// 最近一次成功响应中的速率限制信息，尚未收到带速率限制响应头的响应时返回 `None`
+    ///
+    /// 克隆之间共享同一份状态
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        self.rate_limit.get()
+    }
+
     /// 向指定 URL 发送 HTTP GET 请求并返回 JSON 响应。
     /// 使用 `reqwest` crate 发送 HTTP 请求。
     ///
     /// # 错误
     /// 如果请求失败或响应无法解析为 JSON，则返回 `PexelsError`。
+    /// 非 2xx 响应不会解析响应体：429 返回 `RateLimitError`（附带 `Retry-After`），
+    /// 401 返回 `AuthError`，404 返回 `NotFound`，其他状态返回 `ApiError`。
     async fn make_request(&self, url: &str) -> Result<Value, PexelsError> {
-        let json_response = self
+        let response = self
             .client
             .get(url)
             .header("Authorization", &self.api_key)
             .send()
-            .await?
-            .json::<Value>()
             .await?;
-        Ok(json_response)
+        self.rate_limit.record(response.headers());
+
+        // 先检查状态码，避免把错误响应体当作正常结果解析
+        match response.status() {
+            status if status.is_success() => Ok(response.json::<Value>().await?),
+            StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError(
+                rate_limit::retry_after(response.headers()),
+            )),
+            StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
+            StatusCode::NOT_FOUND => {
+                Err(PexelsError::NotFound(format!("Resource not found: {url}")))
+            }
+            status => Err(PexelsError::ApiError(format!(
+                "Request failed with status: {status}"
+            ))),
+        }
     }
 
     /// 根据搜索条件从 Pexels API 检索照片列表。
@@ -645,6 +713,27 @@ impl Pexels {
         builder.build().fetch(self).await
     }
 
+    /// 使用与 [`PexelsClient::search_photos`] 相同的 [`SearchParams`] 搜索照片。
+    ///
+    /// 生成的请求 URL 与 `PexelsClient` 对相同参数生成的 URL 一致。
+    ///
+    /// # 参数
+    /// * `query` - 搜索关键词。
+    /// * `params` - 搜索参数。
+    ///
+    /// # 错误
+    /// 如果参数中的颜色或语言无效、请求失败或响应无法解析为 JSON，则返回 `PexelsError`。
+    pub async fn search_photos_with_params(
+        &self,
+        query: &str,
+        params: &SearchParams,
+    ) -> Result<PhotosResponse, PexelsError> {
+        SearchBuilder::from_params(query, params)?
+            .build()
+            .fetch(self)
+            .await
+    }
+
     /// 根据 ID 从 Pexels API 检索照片。
     ///
     /// # 参数
@@ -672,7 +761,10 @@ impl Pexels {
         FetchPhotoBuilder::new().id(id).build().fetch(self).await
     }
 
-    /// 从 Pexels API 检索随机照片。
+    /// 从 Pexels API 检索精选照片。
+    ///
+    /// Pexels 没有“热门照片”端点，精选照片即与 [`popular_videos`](Self::popular_videos)
+    /// 对应的照片浏览入口。与 [`PexelsClient::curated_photos`] 请求相同的 URL。
     ///
     /// # 参数
     /// * `builder` - 带有搜索参数的 `CuratedBuilder` 实例。
@@ -692,17 +784,26 @@ impl Pexels {
     ///     dotenv().ok();
     ///     let api_key = env::var("PEXELS_API_KEY").expect("PEXELS_API_KEY not set");
     ///     let client = Pexels::new(api_key);
-    ///     let response = client.curated_photo(CuratedBuilder::new().per_page(1).page(1)).await.expect("Failed to get random photo");
+    ///     let response = client.curated_photos(CuratedBuilder::new().per_page(1).page(1)).await.expect("Failed to get curated photos");
     ///     println!("{:?}", response);
     /// }
     /// ```                 
-    pub async fn curated_photo(
+    #[doc(alias = "popular_photos")]
+    pub async fn curated_photos(
         &self,
         builder: CuratedBuilder,
     ) -> Result<PhotosResponse, PexelsError> {
         builder.build().fetch(self).await
     }
 
+    /// [`curated_photos`](Self::curated_photos) 的旧名称，保留以兼容已有代码。
+    pub async fn curated_photo(
+        &self,
+        builder: CuratedBuilder,
+    ) -> Result<PhotosResponse, PexelsError> {
+        self.curated_photos(builder).await
+    }
+
     /// Retrieves a list of videos from the Pexels API based on the search criteria.
     ///
     /// # Arguments
@@ -951,4 +1052,59 @@ mod tests {
         let response = client.make_request(url).await;
         assert!(response.is_ok());
     }
+
+    /// 用返回指定状态码的模拟服务器调用 `make_request`
+    async fn make_request_with(response: wiremock::ResponseTemplate) -> Result<Value, PexelsError> {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .respond_with(response)
+            .mount(&server)
+            .await;
+
+        let client = Pexels::new("test_key".to_string());
+        client
+            .make_request(&format!("{}/v1/curated", server.uri()))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_make_request_maps_error_statuses() {
+        use std::time::Duration;
+        use wiremock::ResponseTemplate;
+
+        let ok = make_request_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"page": 1})),
+        )
+        .await;
+        assert_eq!(ok.unwrap()["page"], 1);
+
+        // 错误响应体即使是合法 JSON 也不会被当作正常结果
+        let rate_limited = make_request_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "30")
+                .set_body_json(serde_json::json!({"error": "Rate limit exceeded"})),
+        )
+        .await;
+        assert!(matches!(
+            rate_limited,
+            Err(PexelsError::RateLimitError(Some(d))) if d == Duration::from_secs(30)
+        ));
+        assert!(matches!(
+            make_request_with(ResponseTemplate::new(429)).await,
+            Err(PexelsError::RateLimitError(None))
+        ));
+
+        assert!(matches!(
+            make_request_with(ResponseTemplate::new(401).set_body_string("Unauthorized")).await,
+            Err(PexelsError::AuthError(_))
+        ));
+        assert!(matches!(
+            make_request_with(ResponseTemplate::new(404)).await,
+            Err(PexelsError::NotFound(_))
+        ));
+        assert!(matches!(
+            make_request_with(ResponseTemplate::new(500).set_body_string("oops")).await,
+            Err(PexelsError::ApiError(msg)) if msg.contains("500")
+        ));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Pexels, PexelsError, PhotosResponse, PEXELS_API, PEXELS_VERSION};
+use crate::{Orientation, Pexels, PexelsError, PhotosResponse, PEXELS_API, PEXELS_VERSION};
 use url::Url;
 
 /// Pexels API path for curated photos
@@ -8,6 +8,7 @@ const PEXELS_CURATED_PATH: &str = "curated";
 pub struct Curated {
     page: Option<usize>,
     per_page: Option<usize>,
+    orientation_filter: Option<Orientation>,
 }
 
 impl Curated {
@@ -35,12 +36,36 @@ impl Curated {
         Ok(url.into())
     }
 
+    /// Keeps only photos matching `orientation` in the fetched response.
+    ///
+    /// The curated endpoint has no orientation parameter, so this filter is applied
+    /// client-side after the page has been fetched. A page may therefore contain fewer
+    /// than `per_page` photos, and `total_results` is set to the number of matching
+    /// photos on the fetched page.
+    pub fn with_orientation_filter(mut self, orientation: Orientation) -> Self {
+        self.orientation_filter = Some(orientation);
+        self
+    }
+
     /// Fetches the photo data from the Pexels API `Curated` endpoint.
     pub async fn fetch(&self, client: &Pexels) -> Result<PhotosResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
         let photos_response: PhotosResponse = serde_json::from_value(response)?;
-        Ok(photos_response)
+        Ok(self.apply_orientation_filter(photos_response))
+    }
+
+    /// Applies the orientation filter, if any, to a fetched response.
+    fn apply_orientation_filter(&self, mut response: PhotosResponse) -> PhotosResponse {
+        if let Some(orientation) = &self.orientation_filter {
+            response.photos.retain(|photo| match orientation {
+                Orientation::Landscape => photo.width > photo.height,
+                Orientation::Portrait => photo.height > photo.width,
+                Orientation::Square => photo.width == photo.height,
+            });
+            response.total_results = response.photos.len() as u32;
+        }
+        response
     }
 }
 
@@ -77,6 +102,7 @@ impl CuratedBuilder {
         Curated {
             page: self.page,
             per_page: self.per_page,
+            orientation_filter: None,
         }
     }
 }
@@ -84,6 +110,7 @@ impl CuratedBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Photo, PhotoSrc};
 
     #[test]
     fn test_page() {
@@ -102,4 +129,54 @@ mod tests {
             uri.create_uri().unwrap()
         );
     }
+
+    fn photo(id: u32, width: u32, height: u32) -> Photo {
+        Photo {
+            id,
+            width,
+            height,
+            url: format!("https://www.pexels.com/photo/{id}/"),
+            photographer: "Test".to_string(),
+            photographer_url: "https://www.pexels.com/@test".to_string(),
+            photographer_id: 1,
+            avg_color: "#000000".to_string(),
+            src: PhotoSrc {
+                original: String::new(),
+                large2x: String::new(),
+                large: String::new(),
+                medium: String::new(),
+                small: String::new(),
+                portrait: String::new(),
+                landscape: String::new(),
+                tiny: String::new(),
+            },
+            liked: false,
+            alt: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_orientation_filter_landscape() {
+        let response = PhotosResponse {
+            total_results: 8000,
+            page: 1,
+            per_page: 3,
+            photos: vec![
+                photo(1, 1920, 1080),
+                photo(2, 1080, 1920),
+                photo(3, 1000, 1000),
+            ],
+            next_page: None,
+            prev_page: None,
+        };
+
+        let curated = CuratedBuilder::new()
+            .build()
+            .with_orientation_filter(Orientation::Landscape);
+        let filtered = curated.apply_orientation_filter(response);
+
+        assert_eq!(filtered.photos.len(), 1);
+        assert_eq!(filtered.photos[0].id, 1);
+        assert_eq!(filtered.total_results, 1);
+    }
 }
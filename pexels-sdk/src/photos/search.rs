@@ -1,5 +1,6 @@
 use crate::{
-    Locale, Orientation, Pexels, PexelsError, PhotosResponse, Size, PEXELS_API, PEXELS_VERSION,
+    Locale, Orientation, Pexels, PexelsError, PhotosResponse, SearchParams, Size, PEXELS_API,
+    PEXELS_VERSION,
 };
 use url::Url;
 const PEXELS_PHOTO_SEARCH_PATH: &str = "search";
@@ -72,7 +73,52 @@ pub enum Color<'a> {
     Hex(Hex<'a>),
 }
 
-impl Color<'_> {
+impl<'a> Color<'a> {
+    /// Parses a color name (e.g. `red`) or a hexadecimal color code (e.g. `#FFFFFF`).
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::HexColorCodeError`] for an invalid hexadecimal color code and
+    /// [`PexelsError::InvalidParameter`] for an unknown color name.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_borrowed_str(v: &'a str) -> Result<Self, PexelsError> {
+        if v.starts_with('#') {
+            return Ok(Color::Hex(Hex::from_borrowed_str(v)?));
+        }
+
+        let color = match v.to_lowercase().as_str() {
+            "red" => Color::Red,
+            "orange" => Color::Orange,
+            "yellow" => Color::Yellow,
+            "green" => Color::Green,
+            "turquoise" => Color::Turquoise,
+            "blue" => Color::Blue,
+            "violet" => Color::Violet,
+            "pink" => Color::Pink,
+            "brown" => Color::Brown,
+            "black" => Color::Black,
+            "gray" => Color::Gray,
+            "white" => Color::White,
+            _ => {
+                return Err(PexelsError::InvalidParameter(format!(
+                    "{v} is not a supported color."
+                )))
+            }
+        };
+
+        Ok(color)
+    }
+
+    /// Returns the value sent as the `color` query parameter.
+    ///
+    /// Named colors map to their lowercase name; hex colors are returned as-is
+    /// (e.g. `#FFFFFF`) and are percent-encoded (`%23FFFFFF`) when placed in the URI.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::HexColorCodeError`] if a [`Color::Hex`] value is not a valid hexadecimal color code.
+    pub fn query_value(&self) -> Result<String, PexelsError> {
+        self.as_str().map(str::to_string)
+    }
+
     /// Returns the string representation of the color.
     fn as_str(&self) -> Result<&str, PexelsError> {
         let value = match self {
@@ -88,7 +134,7 @@ impl Color<'_> {
             Color::Black => "black",
             Color::Gray => "gray",
             Color::White => "white",
-            Color::Hex(v) => v.0,
+            Color::Hex(v) => Hex::from_borrowed_str(v.0)?.0,
         };
 
         Ok(value)
@@ -150,7 +196,14 @@ impl<'a> Search<'a> {
     }
 
     /// Fetches the list of photos from the Pexels API based on the search parameters.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] without sending a request if the query is empty
+    /// or whitespace-only.
     pub async fn fetch(&self, client: &Pexels) -> Result<PhotosResponse, PexelsError> {
+        if self.query.trim().is_empty() {
+            return Err(PexelsError::InvalidParameter("empty query".to_string()));
+        }
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
         let photos_response: PhotosResponse = serde_json::from_value(response)?;
@@ -184,6 +237,28 @@ impl<'a> SearchBuilder<'a> {
         }
     }
 
+    /// Creates a [`SearchBuilder`] with the same filters as a [`SearchParams`].
+    ///
+    /// This lets the [`Pexels`] client accept the parameters used by
+    /// [`PexelsClient`](crate::PexelsClient), producing the same request URL.
+    ///
+    /// # Errors
+    /// Returns an error if `params` contains an unsupported color or locale.
+    pub fn from_params(query: &'a str, params: &'a SearchParams) -> Result<Self, PexelsError> {
+        let mut builder = Self::new().query(query);
+        builder.page = params.page.map(|page| page as usize);
+        builder.per_page = params.per_page.map(|per_page| per_page as usize);
+        builder.orientation = params.orientation.clone();
+        builder.size = params.size.clone();
+        builder.color = params
+            .color
+            .as_deref()
+            .map(Color::from_borrowed_str)
+            .transpose()?;
+        builder.locale = params.locale.as_deref().map(str::parse).transpose()?;
+        Ok(builder)
+    }
+
     /// Sets the search query.
     pub fn query(mut self, query: &'a str) -> Self {
         self.query = query;
@@ -253,6 +328,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_multibyte_query_is_percent_encoded() {
+        let uri = SearchBuilder::new().query("山脉火山").build();
+        assert_eq!(
+            "https://api.pexels.com/v1/search?query=%E5%B1%B1%E8%84%89%E7%81%AB%E5%B1%B1",
+            uri.create_uri().unwrap()
+        );
+
+        let uri = SearchBuilder::new().query("山脉 🌋").build();
+        assert_eq!(
+            "https://api.pexels.com/v1/search?query=%E5%B1%B1%E8%84%89+%F0%9F%8C%8B",
+            uri.create_uri().unwrap()
+        );
+    }
+
     #[test]
     fn test_page() {
         let uri = SearchBuilder::new().page(1).build();
@@ -300,6 +390,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_all_named_colors() {
+        let cases = [
+            (Color::Red, "red"),
+            (Color::Orange, "orange"),
+            (Color::Yellow, "yellow"),
+            (Color::Green, "green"),
+            (Color::Turquoise, "turquoise"),
+            (Color::Blue, "blue"),
+            (Color::Violet, "violet"),
+            (Color::Pink, "pink"),
+            (Color::Brown, "brown"),
+            (Color::Black, "black"),
+            (Color::Gray, "gray"),
+            (Color::White, "white"),
+        ];
+
+        for (color, expected) in cases {
+            assert_eq!(color.query_value().unwrap(), expected);
+            let uri = SearchBuilder::new().color(color).build();
+            assert_eq!(
+                format!("https://api.pexels.com/v1/search?query=&color={expected}"),
+                uri.create_uri().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_hex_color_query_value() {
+        let color = Color::Hex(Hex::from_borrowed_str("#1A2b3C").unwrap());
+        assert_eq!(color.query_value().unwrap(), "#1A2b3C");
+
+        let uri = SearchBuilder::new().color(color).build();
+        assert_eq!(
+            "https://api.pexels.com/v1/search?query=&color=%231A2b3C",
+            uri.create_uri().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_hex_color_in_create_uri() {
+        // Bypasses `Hex::from_borrowed_str` validation, which is only possible inside the crate
+        let uri = SearchBuilder::new()
+            .color(Color::Hex(Hex("#ZZZZZZ")))
+            .build();
+        assert!(matches!(
+            uri.create_uri(),
+            Err(PexelsError::HexColorCodeError(_))
+        ));
+    }
+
     #[test]
     fn test_hex_color_code() {
         let hex_color = Hex::from_borrowed_str("#FFFFFF").unwrap();
@@ -351,4 +492,20 @@ mod tests {
             )))
         );
     }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_empty_query() {
+        let client = Pexels::new("test_key".to_string());
+        for query in ["", "   "] {
+            let result = SearchBuilder::new()
+                .query(query)
+                .build()
+                .fetch(&client)
+                .await;
+            assert!(matches!(
+                result,
+                Err(PexelsError::InvalidParameter(msg)) if msg == "empty query"
+            ));
+        }
+    }
 }
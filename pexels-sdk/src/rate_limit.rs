@@ -0,0 +1,91 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Pexels API 的速率限制状态，解析自最近一次成功响应的 `X-Ratelimit-*` 响应头
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// 当前周期内允许的请求总数（`X-Ratelimit-Limit`）
+    pub limit: u32,
+    /// 当前周期内剩余的请求数（`X-Ratelimit-Remaining`）
+    pub remaining: u32,
+    /// 周期重置时间，UNIX 时间戳（秒）（`X-Ratelimit-Reset`）
+    pub reset: u64,
+}
+
+impl RateLimitInfo {
+    /// 从响应头解析速率限制信息，任一响应头缺失或无法解析时返回 `None`
+    ///
+    /// Pexels 只在成功响应（2xx）中返回这些响应头
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        fn value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        }
+
+        Some(Self {
+            limit: value(headers, "x-ratelimit-limit")?,
+            remaining: value(headers, "x-ratelimit-remaining")?,
+            reset: value(headers, "x-ratelimit-reset")?,
+        })
+    }
+}
+
+/// 解析 429 响应的 `Retry-After` 响应头（秒数），缺失或为 HTTP 日期格式时返回 `None`
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let secs = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(secs))
+}
+
+/// 在客户端克隆之间共享的最近一次速率限制状态
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RateLimitState(Arc<Mutex<Option<RateLimitInfo>>>);
+
+impl RateLimitState {
+    /// 记录响应头中的速率限制信息，响应头不完整时保留上一次的状态
+    pub(crate) fn record(&self, headers: &HeaderMap) {
+        if let Some(info) = RateLimitInfo::from_headers(headers) {
+            *self.0.lock().unwrap() = Some(info);
+        }
+    }
+
+    /// 最近一次记录的速率限制信息
+    pub(crate) fn get(&self) -> Option<RateLimitInfo> {
+        *self.0.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Ratelimit-Limit", HeaderValue::from_static("20000"));
+        headers.insert("X-Ratelimit-Remaining", HeaderValue::from_static("19684"));
+        headers.insert("X-Ratelimit-Reset", HeaderValue::from_static("1590529646"));
+        assert_eq!(
+            RateLimitInfo::from_headers(&headers),
+            Some(RateLimitInfo {
+                limit: 20000,
+                remaining: 19684,
+                reset: 1590529646,
+            })
+        );
+
+        headers.remove("X-Ratelimit-Reset");
+        assert_eq!(RateLimitInfo::from_headers(&headers), None);
+
+        // 不完整的响应头不会覆盖已记录的状态
+        let state = RateLimitState::default();
+        state.record(&headers);
+        assert_eq!(state.get(), None);
+    }
+}
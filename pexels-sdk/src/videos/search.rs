@@ -57,7 +57,14 @@ impl<'a> Search<'a> {
     }
 
     /// Fetches the list of videos based on the search query from the Pexels API.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] without sending a request if the query is empty
+    /// or whitespace-only.
     pub async fn fetch(&self, client: &Pexels) -> Result<VideoResponse, PexelsError> {
+        if self.query.trim().is_empty() {
+            return Err(PexelsError::InvalidParameter("empty query".to_string()));
+        }
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
         let response_video: VideoResponse = serde_json::from_value(response)?;
@@ -198,4 +205,20 @@ mod tests {
             uri.create_uri().unwrap()
         );
     }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_empty_query() {
+        let client = crate::Pexels::new("test_key".to_string());
+        for query in ["", " \t"] {
+            let result = SearchBuilder::new()
+                .query(query)
+                .build()
+                .fetch(&client)
+                .await;
+            assert!(matches!(
+                result,
+                Err(crate::PexelsError::InvalidParameter(msg)) if msg == "empty query"
+            ));
+        }
+    }
 }
@@ -0,0 +1,33 @@
+//! Fixture replay for the integration tests.
+//!
+//! The fixture format, the `synthetic` marker and how to re-record are
+//! documented in the shared `http-fixtures` crate.
+#![allow(dead_code)]
+
+use http_fixtures::{Fixtures, LiveAuth};
+use pexels_sdk::PexelsClient;
+use wiremock::MockServer;
+
+/// The fixtures in `tests/fixtures/`, recorded from the live Pexels API.
+pub fn fixtures() -> Fixtures {
+    Fixtures::new(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"),
+        "https://api.pexels.com",
+        "PEXELS_API_KEY",
+        LiveAuth::Header("Authorization"),
+    )
+}
+
+/// Starts a mock server for the fixture, re-recording it first with `record-fixtures`.
+pub async fn replay(name: &str) -> MockServer {
+    fixtures()
+        .replay(name, cfg!(feature = "record-fixtures"))
+        .await
+}
+
+/// Creates a client pointing at the fixture's mock server.
+pub fn client(server: &MockServer) -> PexelsClient {
+    PexelsClient::new("fixture_key")
+        .with_base_url(format!("{}/v1", server.uri()))
+        .expect("Invalid mock server URL")
+}
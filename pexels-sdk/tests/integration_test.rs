@@ -1,6 +1,10 @@
 use dotenvy::dotenv;
+mod common;
+
 use pexels_sdk::{
-    CuratedBuilder, Pexels, PexelsError, PopularBuilder, SearchBuilder, VideoSearchBuilder,
+    CuratedBuilder, MediaItemType, MediaResponse, PaginationParams, Pexels, PexelsError,
+    PhotosResponse, PopularBuilder, SearchBuilder, SearchParams, VideoResponse, VideoSearchBuilder,
+    VideoSearchParams,
 };
 use std::env;
 
@@ -98,7 +102,7 @@ async fn test_curated_photos() {
     let client = get_test_client().expect("PEXELS_API_KEY not set");
 
     let result = client
-        .curated_photo(CuratedBuilder::new().per_page(10).page(1))
+        .curated_photos(CuratedBuilder::new().per_page(10).page(1))
         .await;
     assert!(result.is_ok());
 
@@ -193,3 +197,76 @@ async fn test_collection_operations() {
         Err(e) => println!("Failed to get collections: {:?}", e),
     }
 }
+
+#[tokio::test]
+async fn test_search_photos_fixture() {
+    let server = common::replay("search_photos").await;
+    let client = common::client(&server);
+
+    let result = client
+        .search_photos("yellow flowers", SearchParams::new().per_page(5).page(1))
+        .await;
+    assert!(result.is_ok());
+
+    let response = result.unwrap();
+    assert!(response.total_results > 0);
+    assert!(!response.photos.is_empty());
+    assert!(response.photos.len() <= 5);
+    assert!(response.next_page.is_some());
+}
+
+#[tokio::test]
+async fn test_search_videos_fixture() {
+    let server = common::replay("search_videos").await;
+    let client = common::client(&server);
+
+    let result = client
+        .search_videos("ocean waves", VideoSearchParams::new().per_page(3).page(1))
+        .await;
+    assert!(result.is_ok());
+
+    let response = result.unwrap();
+    assert!(response.total_results > 0);
+    assert_eq!(response.videos.len(), 3);
+    assert!(response
+        .videos
+        .iter()
+        .all(|video| !video.video_files.is_empty()));
+}
+
+#[tokio::test]
+async fn test_collection_media_fixture() {
+    let server = common::replay("collection_media").await;
+    let client = common::client(&server);
+
+    let result = client
+        .get_collection_media("9mp14cx", &PaginationParams::new().per_page(3).page(1))
+        .await;
+    assert!(result.is_ok());
+
+    let page = result.unwrap();
+    assert_eq!(page.id, "9mp14cx");
+    assert_eq!(page.media.len(), 3);
+    assert!(page
+        .media
+        .iter()
+        .any(|item| matches!(item, MediaItemType::Video(_))));
+}
+
+/// The builder client (`Pexels`) parses the same payloads into its own response models
+#[test]
+fn test_builder_models_parse_fixtures() {
+    let fixtures = common::fixtures();
+
+    let photos: PhotosResponse = serde_json::from_value(fixtures.body("search_photos")).unwrap();
+    assert_eq!(photos.photos.len(), 5);
+
+    let videos: VideoResponse = serde_json::from_value(fixtures.body("search_videos")).unwrap();
+    assert!(videos
+        .videos
+        .iter()
+        .all(|video| !video.video_files.is_empty()));
+
+    let media: MediaResponse = serde_json::from_value(fixtures.body("collection_media")).unwrap();
+    assert_eq!(media.media.len(), 3);
+}
@@ -0,0 +1,117 @@
+/*!
+同步（阻塞）调用包装，需要启用 `blocking` 特性。
+
+适用于 CLI 工具、GUI 事件循环等没有异步运行时的场景：每个 `*_blocking` 方法都在
+一个进程内共享的 current-thread Tokio 运行时上执行对应的异步方法并等待其完成，
+调用方无需自行创建运行时。
+
+# 注意
+
+不要在异步上下文中（例如 `#[tokio::main]` 或 `tokio::spawn` 的任务内）调用这些方法，
+否则 Tokio 会因嵌套运行时而 panic。异步代码请直接使用对应的异步方法。
+*/
+use std::sync::OnceLock;
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::client::{Pixabay, SearchImageParams, SearchVideoParams};
+use crate::error::Result;
+use crate::models::{Image, ImageResponse, Video, VideoResponse};
+
+/// 所有阻塞调用共享的运行时，首次使用时创建
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("无法创建阻塞调用所需的 Tokio 运行时")
+    })
+}
+
+impl Pixabay {
+    /// [`search_images`](Self::search_images) 的阻塞版本
+    pub fn search_images_blocking(
+        &self,
+        query: &str,
+        per_page: Option<u32>,
+        page: Option<u32>,
+    ) -> Result<ImageResponse> {
+        runtime().block_on(self.search_images(query, per_page, page))
+    }
+
+    /// [`search_images_advanced`](Self::search_images_advanced) 的阻塞版本
+    pub fn search_images_advanced_blocking(
+        &self,
+        params: SearchImageParams,
+    ) -> Result<ImageResponse> {
+        runtime().block_on(self.search_images_advanced(params))
+    }
+
+    /// [`get_image`](Self::get_image) 的阻塞版本
+    pub fn get_image_blocking(&self, id: u64) -> Result<Image> {
+        runtime().block_on(self.get_image(id))
+    }
+
+    /// [`search_videos`](Self::search_videos) 的阻塞版本
+    pub fn search_videos_blocking(
+        &self,
+        query: &str,
+        per_page: Option<u32>,
+        page: Option<u32>,
+    ) -> Result<VideoResponse> {
+        runtime().block_on(self.search_videos(query, per_page, page))
+    }
+
+    /// [`search_videos_advanced`](Self::search_videos_advanced) 的阻塞版本
+    pub fn search_videos_advanced_blocking(
+        &self,
+        params: SearchVideoParams,
+    ) -> Result<VideoResponse> {
+        runtime().block_on(self.search_videos_advanced(params))
+    }
+
+    /// [`get_video`](Self::get_video) 的阻塞版本
+    pub fn get_video_blocking(&self, id: u64) -> Result<Video> {
+        runtime().block_on(self.get_video(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_search_images_blocking() {
+        // 模拟服务器在测试自己的运行时中启动，阻塞调用发生在运行时之外
+        let rt = Runtime::new().unwrap();
+        let server = rt.block_on(MockServer::start());
+        rt.block_on(
+            Mock::given(method("GET"))
+                .and(query_param("q", "flowers"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "total": 42,
+                    "totalHits": 42,
+                    "hits": []
+                })))
+                .expect(1)
+                .mount(&server),
+        );
+
+        let client = Pixabay::new("test_key".to_string())
+            .with_base_urls(
+                format!("{}/api/", server.uri()),
+                format!("{}/api/videos/", server.uri()),
+            )
+            .unwrap();
+        let response = client
+            .search_images_blocking("flowers", Some(3), None)
+            .unwrap();
+        assert_eq!(response.total_hits, 42);
+
+        rt.block_on(server.verify());
+    }
+}
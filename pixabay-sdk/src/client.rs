@@ -1,15 +1,39 @@
 use crate::error::{PixabayError, Result};
 use crate::models::*;
-use reqwest::Client;
+use reqwest::{header, Client};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use url::Url;
 
+/// 发送请求所用的 HTTP 客户端，启用 `middleware` 特性时为带中间件的客户端
+#[cfg(feature = "middleware")]
+type HttpClient = reqwest_middleware::ClientWithMiddleware;
+#[cfg(not(feature = "middleware"))]
+type HttpClient = Client;
+
+/// 将普通 `reqwest::Client` 包装为 [`HttpClient`]（不附加任何中间件）
+fn http_client(client: Client) -> HttpClient {
+    #[cfg(feature = "middleware")]
+    return reqwest_middleware::ClientWithMiddleware::from(client);
+    #[cfg(not(feature = "middleware"))]
+    client
+}
+
 const BASE_URL: &str = "https://pixabay.com/api/";
 const VIDEO_BASE_URL: &str = "https://pixabay.com/api/videos/";
 
+/// 查询字符串的最大长度（根据 API 文档为 100 字符）
+const MAX_QUERY_LEN: usize = 100;
+
 #[derive(Debug, Clone)]
 pub struct Pixabay {
     pub api_key: String,
-    client: Client,
+    client: HttpClient,
+    base_url: String,
+    video_base_url: String,
+    strict_parse: bool,
+    /// 最近一次响应中的速率限制信息，克隆之间共享
+    rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
 }
 
 impl Pixabay {
@@ -21,8 +45,102 @@ impl Pixabay {
     pub fn new(api_key: String) -> Self {
         Self {
             api_key,
-            client: Client::new(),
+            // 与 `Client::new()` 相同，但显式启用 gzip/brotli 响应解压
+            client: http_client(
+                Client::builder()
+                    .gzip(true)
+                    .brotli(true)
+                    .build()
+                    .expect("无法创建 HTTP 客户端"),
+            ),
+            base_url: BASE_URL.to_string(),
+            video_base_url: VIDEO_BASE_URL.to_string(),
+            strict_parse: false,
+            rate_limit: Arc::default(),
+        }
+    }
+
+    /// 使用带中间件的 HTTP 客户端创建 Pixabay 客户端
+    ///
+    /// 需要启用 `middleware` 特性。可用于附加请求 ID、链路追踪（如 `reqwest-tracing`）、
+    /// 重试（如 `reqwest-retry`）等中间件。客户端按原样使用，响应解压等设置由调用方配置。
+    ///
+    /// # 参数
+    ///
+    /// * `api_key` - 你的 Pixabay API 密钥
+    /// * `client` - 带中间件的 HTTP 客户端
+    #[cfg(feature = "middleware")]
+    pub fn with_middleware(
+        api_key: String,
+        client: reqwest_middleware::ClientWithMiddleware,
+    ) -> Self {
+        Self {
+            api_key,
+            client,
+            base_url: BASE_URL.to_string(),
+            video_base_url: VIDEO_BASE_URL.to_string(),
+            strict_parse: false,
+            rate_limit: Arc::default(),
+        }
+    }
+
+    /// 设置图片和视频 API 的基础 URL（用于测试或代理）
+    ///
+    /// # 参数
+    ///
+    /// * `base_url` - 图片 API 基础 URL，默认 `https://pixabay.com/api/`
+    /// * `video_base_url` - 视频 API 基础 URL，默认 `https://pixabay.com/api/videos/`
+    ///
+    /// 两个 URL 都是完整的端点地址（查询参数直接附加在其后），因此保留原样，
+    /// 只在设置时校验能否解析以及协议是否为 `http`/`https`
+    pub fn with_base_urls(
+        mut self,
+        base_url: impl Into<String>,
+        video_base_url: impl Into<String>,
+    ) -> Result<Self> {
+        let base_url = base_url.into();
+        let video_base_url = video_base_url.into();
+        Self::validate_base_url(&base_url)?;
+        Self::validate_base_url(&video_base_url)?;
+        self.base_url = base_url;
+        self.video_base_url = video_base_url;
+        Ok(self)
+    }
+
+    /// 校验基础 URL 可以解析且使用 http/https 协议
+    fn validate_base_url(base_url: &str) -> Result<()> {
+        let url = Url::parse(base_url)?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(PixabayError::InvalidBaseUrl(base_url.to_string()));
+        }
+        Ok(())
+    }
+
+    /// 启用或关闭严格解析模式
+    ///
+    /// 默认关闭：响应按 serde 默认方式宽松解析，未知字段会被忽略。
+    /// 启用后，解析失败时返回的 `PixabayError::JsonError` 会包含出错字段的 JSON 路径
+    /// （例如 `hits[0].views`），便于排查上游字段变更。
+    pub fn with_strict_parse(mut self, strict_parse: bool) -> Self {
+        self.strict_parse = strict_parse;
+        self
+    }
+
+    /// 最近一次响应中的速率限制信息，尚未收到带速率限制响应头的响应时返回 `None`
+    ///
+    /// 克隆之间共享同一份状态
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    /// 验证查询长度（根据 API 文档最多 100 字符）
+    ///
+    /// 按字符而非字节计数，中文等多字节字符各算一个字符
+    fn validate_query(query: &str) -> Result<()> {
+        if query.chars().count() > MAX_QUERY_LEN {
+            return Err(PixabayError::QueryTooLong);
         }
+        Ok(())
     }
 
     /// 在 Pixabay 上搜索图片
@@ -61,8 +179,9 @@ impl Pixabay {
         // 验证 per_page 范围（根据 API 文档为 3-200）
         let per_page = per_page.unwrap_or(20).clamp(3, 200);
         let page = page.unwrap_or(1);
+        Self::validate_query(query)?;
 
-        let mut url = Url::parse(BASE_URL)?;
+        let mut url = Url::parse(&self.base_url)?;
 
         url.query_pairs_mut()
             .append_pair("key", &self.api_key)
@@ -70,11 +189,97 @@ impl Pixabay {
             .append_pair("per_page", &per_page.to_string())
             .append_pair("page", &page.to_string());
 
-        let response = self.client.get(url).send().await?;
+        let response = self.send_request(url).await?;
 
         self.handle_response(response).await
     }
 
+    /// 搜索图片，可选地按方向和图片类型筛选
+    ///
+    /// 介于 [`Pixabay::search_images`] 和 [`Pixabay::search_images_advanced`] 之间的便捷方法，
+    /// 适用于只需要方向或图片类型筛选的常见场景
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// # use pixabay_sdk::{Pixabay, Orientation, ImageType};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Pixabay::new("your_api_key".to_string());
+    /// let images = client
+    ///     .search_images_ext("mountains", Some(10), Some(1), Some(Orientation::Horizontal), Some(ImageType::Photo))
+    ///     .await?;
+    /// println!("找到 {} 张图片", images.total_hits);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_images_ext(
+        &self,
+        query: &str,
+        per_page: Option<u32>,
+        page: Option<u32>,
+        orientation: Option<Orientation>,
+        image_type: Option<ImageType>,
+    ) -> Result<ImageResponse> {
+        let mut params = SearchImageParams::new()
+            .query(query)
+            .per_page(per_page.unwrap_or(20))
+            .page(page.unwrap_or(1));
+        params.orientation = orientation;
+        params.image_type = image_type;
+
+        self.search_images_advanced(params).await
+    }
+
+    /// 按指定排序方式搜索图片
+    ///
+    /// 排序是最常用的额外参数，此方法省去了为设置 `order` 构建 [`SearchImageParams`] 的步骤；
+    /// 其他筛选条件仍请使用 [`Pixabay::search_images_advanced`]
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// # use pixabay_sdk::{Pixabay, Order};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Pixabay::new("your_api_key".to_string());
+    /// let images = client
+    ///     .search_images_ordered("sunset", Some(10), Some(1), Order::Latest)
+    ///     .await?;
+    /// println!("找到 {} 张图片", images.total_hits);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_images_ordered(
+        &self,
+        query: &str,
+        per_page: Option<u32>,
+        page: Option<u32>,
+        order: Order,
+    ) -> Result<ImageResponse> {
+        let params = SearchImageParams::new()
+            .query(query)
+            .per_page(per_page.unwrap_or(20))
+            .page(page.unwrap_or(1))
+            .order(order);
+
+        self.search_images_advanced(params).await
+    }
+
+    /// 发送 GET 请求，声明只接受 JSON 响应
+    ///
+    /// 响应中带有完整的速率限制响应头时会更新 [`rate_limit`](Self::rate_limit)
+    async fn send_request(&self, url: Url) -> Result<reqwest::Response> {
+        let response = self
+            .client
+            .get(url)
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await?;
+        if let Some(info) = RateLimitInfo::from_headers(response.headers()) {
+            *self.rate_limit.lock().unwrap() = Some(info);
+        }
+        Ok(response)
+    }
+
     /// 处理 API 响应并提取相应的错误
     async fn handle_response<T: serde::de::DeserializeOwned>(
         &self,
@@ -83,12 +288,18 @@ impl Pixabay {
         let status = response.status();
 
         if status.is_success() {
-            Ok(response.json().await?)
+            let response = Self::ensure_json(response).await?;
+            self.parse_json(response).await
         } else if status.as_u16() == 429 {
             Err(PixabayError::RateLimitExceeded)
         } else if status.as_u16() == 400 {
             let error_text = response.text().await?;
-            Err(PixabayError::ApiError(format!("错误请求: {}", error_text)))
+            // Pixabay 对无效的 API 密钥同样返回 400
+            if error_text.to_lowercase().contains("api key") {
+                Err(PixabayError::InvalidApiKey)
+            } else {
+                Err(PixabayError::BadRequest(error_text))
+            }
         } else if status.as_u16() == 401 || status.as_u16() == 403 {
             Err(PixabayError::InvalidApiKey)
         } else {
@@ -101,6 +312,50 @@ impl Pixabay {
         }
     }
 
+    /// 确认响应的 `Content-Type` 为 JSON
+    ///
+    /// 某些代理会以 200 返回 HTML 页面，直接反序列化只会得到难以理解的 serde 错误，
+    /// 因此在解析前检查并返回带有响应体片段的 `ApiError`
+    async fn ensure_json(response: reqwest::Response) -> Result<reqwest::Response> {
+        let is_json = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.to_ascii_lowercase().contains("json"));
+        if is_json {
+            return Ok(response);
+        }
+
+        let body = response.text().await?;
+        let snippet: String = body.chars().take(200).collect();
+        Err(PixabayError::ApiError(format!(
+            "non-JSON response: {}",
+            snippet
+        )))
+    }
+
+    /// 解析 JSON 响应体
+    ///
+    /// 严格模式下使用 `serde_path_to_error`，错误信息中包含出错字段的 JSON 路径
+    async fn parse_json<T: serde::de::DeserializeOwned>(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<T> {
+        if !self.strict_parse {
+            return Ok(response.json().await?);
+        }
+
+        let body = response.text().await?;
+        let deserializer = &mut serde_json::Deserializer::from_str(&body);
+        serde_path_to_error::deserialize(deserializer).map_err(|e| {
+            PixabayError::JsonError(serde::de::Error::custom(format!(
+                "{}: {}",
+                e.path(),
+                e.inner()
+            )))
+        })
+    }
+
     /// 使用高级参数搜索图片
     ///
     /// # 示例
@@ -130,62 +385,113 @@ impl Pixabay {
         // 验证 per_page 范围
         let per_page = params.per_page.unwrap_or(20).clamp(3, 200);
 
-        let mut url = Url::parse(BASE_URL)?;
+        let mut url = Url::parse(&self.base_url)?;
 
-        let mut query = url.query_pairs_mut();
-        query.append_pair("key", &self.api_key);
+        // 查询序列化器不能跨越 await 存活，否则返回的 Future 不满足 Send
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("key", &self.api_key);
 
-        if let Some(q) = &params.query {
-            // 验证查询长度（根据 API 文档最多 100 字符）
-            if q.len() > 100 {
-                drop(query);
-                return Err(PixabayError::ApiError(
-                    "查询字符串不能超过 100 个字符".to_string(),
-                ));
+            if let Some(q) = &params.query {
+                Self::validate_query(q)?;
+                query.append_pair("q", q);
             }
-            query.append_pair("q", q);
-        }
 
-        query.append_pair("per_page", &per_page.to_string());
-        query.append_pair("page", &params.page.unwrap_or(1).to_string());
+            query.append_pair("per_page", &per_page.to_string());
+            query.append_pair("page", &params.page.unwrap_or(1).to_string());
 
-        if let Some(image_type) = &params.image_type {
-            query.append_pair("image_type", &image_type.to_string());
-        }
-        if let Some(orientation) = &params.orientation {
-            query.append_pair("orientation", &orientation.to_string());
-        }
-        if let Some(category) = &params.category {
-            query.append_pair("category", &category.to_string());
-        }
-        if let Some(min_width) = params.min_width {
-            query.append_pair("min_width", &min_width.to_string());
-        }
-        if let Some(min_height) = params.min_height {
-            query.append_pair("min_height", &min_height.to_string());
-        }
-        if let Some(colors) = &params.colors {
-            query.append_pair("colors", colors);
-        }
-        if let Some(editors_choice) = params.editors_choice {
-            query.append_pair("editors_choice", &editors_choice.to_string());
-        }
-        if let Some(safesearch) = params.safesearch {
-            query.append_pair("safesearch", &safesearch.to_string());
-        }
-        if let Some(order) = &params.order {
-            query.append_pair("order", &order.to_string());
-        }
-        if let Some(lang) = &params.lang {
-            query.append_pair("lang", &lang.to_string());
+            if let Some(image_type) = &params.image_type {
+                query.append_pair("image_type", &image_type.to_string());
+            }
+            if let Some(orientation) = &params.orientation {
+                query.append_pair("orientation", &orientation.to_string());
+            }
+            if let Some(category) = &params.category {
+                query.append_pair("category", &category.to_string());
+            }
+            if let Some(min_width) = params.min_width {
+                query.append_pair("min_width", &min_width.to_string());
+            }
+            if let Some(min_height) = params.min_height {
+                query.append_pair("min_height", &min_height.to_string());
+            }
+            if let Some(colors) = &params.colors {
+                query.append_pair("colors", colors);
+            }
+            if let Some(editors_choice) = params.editors_choice {
+                query.append_pair("editors_choice", &editors_choice.to_string());
+            }
+            if let Some(safesearch) = params.safesearch {
+                query.append_pair("safesearch", &safesearch.to_string());
+            }
+            if let Some(order) = &params.order {
+                query.append_pair("order", &order.to_string());
+            }
+            if let Some(lang) = &params.lang {
+                query.append_pair("lang", &lang.to_string());
+            }
         }
 
-        drop(query);
-
-        let response = self.client.get(url).send().await?;
+        let response = self.send_request(url).await?;
         self.handle_response(response).await
     }
 
+    /// 逐页搜索图片并收集最多 `max` 张
+    ///
+    /// 从 `params.page`（默认第 1 页）开始依次请求，直到收集满 `max` 张、某页没有结果
+    /// 或已到达 `total_hits` 为止。翻页期间 API 可能挪动结果，导致同一张图片出现在相邻两页，
+    /// 因此按 `Image.id` 去重，并在结果中报告跳过的重复数。
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// # use pixabay_sdk::{Pixabay, SearchImageParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Pixabay::new("your_api_key".to_string());
+    /// let params = SearchImageParams::new().query("mountains").per_page(100);
+    /// let result = client.collect_images(params, 300).await?;
+    /// println!("收集到 {} 张图片，跳过 {} 张重复", result.images.len(), result.duplicates_skipped);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collect_images(
+        &self,
+        params: SearchImageParams,
+        max: usize,
+    ) -> Result<CollectResult> {
+        let per_page = params.per_page.unwrap_or(20).clamp(3, 200);
+        let mut page = params.page.unwrap_or(1);
+        let mut seen = HashSet::new();
+        let mut result = CollectResult {
+            images: Vec::new(),
+            duplicates_skipped: 0,
+        };
+
+        while result.images.len() < max {
+            let response = self
+                .search_images_advanced(params.clone().per_page(per_page).page(page))
+                .await?;
+            if response.hits.is_empty() {
+                break;
+            }
+
+            for image in response.hits {
+                if !seen.insert(image.id) {
+                    result.duplicates_skipped += 1;
+                } else if result.images.len() < max {
+                    result.images.push(image);
+                }
+            }
+
+            if page as u64 * per_page as u64 >= response.total_hits as u64 {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(result)
+    }
+
     /// 通过 ID 获取特定图片
     ///
     /// # 参数
@@ -204,13 +510,13 @@ impl Pixabay {
     /// # }
     /// ```
     pub async fn get_image(&self, id: u64) -> Result<Image> {
-        let mut url = Url::parse(BASE_URL)?;
+        let mut url = Url::parse(&self.base_url)?;
 
         url.query_pairs_mut()
             .append_pair("key", &self.api_key)
             .append_pair("id", &id.to_string());
 
-        let response = self.client.get(url).send().await?;
+        let response = self.send_request(url).await?;
 
         let image_response: ImageResponse = self.handle_response(response).await?;
         image_response
@@ -220,6 +526,64 @@ impl Pixabay {
             .ok_or_else(|| PixabayError::ApiError(format!("未找到 ID 为 {} 的图片", id)))
     }
 
+    /// 通过 ID 列表批量获取图片
+    ///
+    /// 以逗号分隔的 `id` 参数在一次请求中获取多张图片，每次请求最多 200 个 ID，超出时分批请求。
+    /// 返回与输入顺序一一对应的结果，响应中缺少的 ID 为 `PixabayError::ApiError`
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// # use pixabay_sdk::Pixabay;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Pixabay::new("your_api_key".to_string());
+    /// for image in client.get_images(&[195893, 736885]).await? {
+    ///     match image {
+    ///         Ok(image) => println!("{}: {}", image.id, image.tags),
+    ///         Err(e) => println!("获取失败: {}", e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_images(&self, ids: &[u64]) -> Result<Vec<Result<Image>>> {
+        let mut found = HashMap::new();
+        for chunk in ids.chunks(Self::MAX_IDS_PER_REQUEST) {
+            let url = self.batch_url(&self.base_url, chunk)?;
+            let response = self.send_request(url).await?;
+            let image_response: ImageResponse = self.handle_response(response).await?;
+            found.extend(image_response.hits.into_iter().map(|hit| (hit.id, hit)));
+        }
+
+        Ok(ids
+            .iter()
+            .map(|id| {
+                found
+                    .get(id)
+                    .cloned()
+                    .ok_or_else(|| PixabayError::ApiError(format!("未找到 ID 为 {} 的图片", id)))
+            })
+            .collect())
+    }
+
+    /// 单次批量请求的 ID 数上限，与 `per_page` 上限一致
+    const MAX_IDS_PER_REQUEST: usize = 200;
+
+    /// 构建批量获取的请求 URL，`per_page` 足以容纳所有 ID
+    fn batch_url(&self, base_url: &str, ids: &[u64]) -> Result<Url> {
+        let ids_param = ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut url = Url::parse(base_url)?;
+        url.query_pairs_mut()
+            .append_pair("key", &self.api_key)
+            .append_pair("id", &ids_param)
+            .append_pair("per_page", &ids.len().clamp(3, 200).to_string());
+        Ok(url)
+    }
+
     /// 在 Pixabay 上搜索视频
     ///
     /// # 参数
@@ -248,8 +612,9 @@ impl Pixabay {
         // 验证 per_page 范围（根据 API 文档为 3-200）
         let per_page = per_page.unwrap_or(20).clamp(3, 200);
         let page = page.unwrap_or(1);
+        Self::validate_query(query)?;
 
-        let mut url = Url::parse(VIDEO_BASE_URL)?;
+        let mut url = Url::parse(&self.video_base_url)?;
 
         url.query_pairs_mut()
             .append_pair("key", &self.api_key)
@@ -257,7 +622,7 @@ impl Pixabay {
             .append_pair("per_page", &per_page.to_string())
             .append_pair("page", &page.to_string());
 
-        let response = self.client.get(url).send().await?;
+        let response = self.send_request(url).await?;
         self.handle_response(response).await
     }
 
@@ -288,53 +653,48 @@ impl Pixabay {
         // 验证 per_page 范围
         let per_page = params.per_page.unwrap_or(20).clamp(3, 200);
 
-        let mut url = Url::parse(VIDEO_BASE_URL)?;
+        let mut url = Url::parse(&self.video_base_url)?;
 
-        let mut query = url.query_pairs_mut();
-        query.append_pair("key", &self.api_key);
+        // 查询序列化器不能跨越 await 存活，否则返回的 Future 不满足 Send
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("key", &self.api_key);
 
-        if let Some(q) = &params.query {
-            // 验证查询长度（根据 API 文档最多 100 字符）
-            if q.len() > 100 {
-                drop(query);
-                return Err(PixabayError::ApiError(
-                    "查询字符串不能超过 100 个字符".to_string(),
-                ));
+            if let Some(q) = &params.query {
+                Self::validate_query(q)?;
+                query.append_pair("q", q);
             }
-            query.append_pair("q", q);
-        }
 
-        query.append_pair("per_page", &per_page.to_string());
-        query.append_pair("page", &params.page.unwrap_or(1).to_string());
+            query.append_pair("per_page", &per_page.to_string());
+            query.append_pair("page", &params.page.unwrap_or(1).to_string());
 
-        if let Some(video_type) = &params.video_type {
-            query.append_pair("video_type", &video_type.to_string());
-        }
-        if let Some(category) = &params.category {
-            query.append_pair("category", &category.to_string());
-        }
-        if let Some(min_width) = params.min_width {
-            query.append_pair("min_width", &min_width.to_string());
-        }
-        if let Some(min_height) = params.min_height {
-            query.append_pair("min_height", &min_height.to_string());
-        }
-        if let Some(editors_choice) = params.editors_choice {
-            query.append_pair("editors_choice", &editors_choice.to_string());
-        }
-        if let Some(safesearch) = params.safesearch {
-            query.append_pair("safesearch", &safesearch.to_string());
-        }
-        if let Some(order) = &params.order {
-            query.append_pair("order", &order.to_string());
-        }
-        if let Some(lang) = &params.lang {
-            query.append_pair("lang", &lang.to_string());
+            if let Some(video_type) = &params.video_type {
+                query.append_pair("video_type", &video_type.to_string());
+            }
+            if let Some(category) = &params.category {
+                query.append_pair("category", &category.to_string());
+            }
+            if let Some(min_width) = params.min_width {
+                query.append_pair("min_width", &min_width.to_string());
+            }
+            if let Some(min_height) = params.min_height {
+                query.append_pair("min_height", &min_height.to_string());
+            }
+            if let Some(editors_choice) = params.editors_choice {
+                query.append_pair("editors_choice", &editors_choice.to_string());
+            }
+            if let Some(safesearch) = params.safesearch {
+                query.append_pair("safesearch", &safesearch.to_string());
+            }
+            if let Some(order) = &params.order {
+                query.append_pair("order", &order.to_string());
+            }
+            if let Some(lang) = &params.lang {
+                query.append_pair("lang", &lang.to_string());
+            }
         }
 
-        drop(query);
-
-        let response = self.client.get(url).send().await?;
+        let response = self.send_request(url).await?;
         self.handle_response(response).await
     }
 
@@ -356,13 +716,13 @@ impl Pixabay {
     /// # }
     /// ```
     pub async fn get_video(&self, id: u64) -> Result<Video> {
-        let mut url = Url::parse(VIDEO_BASE_URL)?;
+        let mut url = Url::parse(&self.video_base_url)?;
 
         url.query_pairs_mut()
             .append_pair("key", &self.api_key)
             .append_pair("id", &id.to_string());
 
-        let response = self.client.get(url).send().await?;
+        let response = self.send_request(url).await?;
 
         let video_response: VideoResponse = self.handle_response(response).await?;
         video_response
@@ -371,12 +731,38 @@ impl Pixabay {
             .next()
             .ok_or_else(|| PixabayError::ApiError(format!("未找到 ID 为 {} 的视频", id)))
     }
+
+    /// 通过 ID 列表批量获取视频
+    ///
+    /// 与 [`Pixabay::get_images`] 相同，在一次请求中获取多个视频
+    pub async fn get_videos(&self, ids: &[u64]) -> Result<Vec<Result<Video>>> {
+        let mut found = HashMap::new();
+        for chunk in ids.chunks(Self::MAX_IDS_PER_REQUEST) {
+            let url = self.batch_url(&self.video_base_url, chunk)?;
+            let response = self.send_request(url).await?;
+            let video_response: VideoResponse = self.handle_response(response).await?;
+            found.extend(video_response.hits.into_iter().map(|hit| (hit.id, hit)));
+        }
+
+        Ok(ids
+            .iter()
+            .map(|id| {
+                found
+                    .get(id)
+                    .cloned()
+                    .ok_or_else(|| PixabayError::ApiError(format!("未找到 ID 为 {} 的视频", id)))
+            })
+            .collect())
+    }
 }
 
 /// 高级图片搜索参数结构体
 ///
 /// 使用构建器模式来配置图片搜索的高级参数。
 ///
+/// 默认参数显式携带 `per_page = 20` 与 `page = 1`，与客户端未指定时实际发送的值一致，
+/// 因此检查或序列化参数时看到的就是将要发送的请求。
+///
 /// # 示例
 ///
 /// ```
@@ -390,7 +776,7 @@ impl Pixabay {
 ///     .category(Category::Nature)
 ///     .safesearch(true);
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct SearchImageParams {
     /// 搜索查询词
     pub query: Option<String>,
@@ -420,6 +806,26 @@ pub struct SearchImageParams {
     pub lang: Option<Language>,
 }
 
+impl Default for SearchImageParams {
+    fn default() -> Self {
+        Self {
+            query: None,
+            per_page: Some(20),
+            page: Some(1),
+            image_type: None,
+            orientation: None,
+            category: None,
+            min_width: None,
+            min_height: None,
+            colors: None,
+            editors_choice: None,
+            safesearch: None,
+            order: None,
+            lang: None,
+        }
+    }
+}
+
 impl SearchImageParams {
     /// 创建一个新的 SearchImageParams 实例
     pub fn new() -> Self {
@@ -480,6 +886,17 @@ impl SearchImageParams {
         self
     }
 
+    /// 使用 [`Color`] 设置颜色筛选，多个颜色以逗号连接；为空时清除筛选
+    pub fn with_colors(mut self, colors: impl IntoIterator<Item = Color>) -> Self {
+        let colors = colors
+            .into_iter()
+            .map(|color| color.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.colors = (!colors.is_empty()).then_some(colors);
+        self
+    }
+
     /// 设置是否只返回编辑精选结果
     pub fn editors_choice(mut self, editors_choice: bool) -> Self {
         self.editors_choice = Some(editors_choice);
@@ -509,6 +926,9 @@ impl SearchImageParams {
 ///
 /// 使用构建器模式来配置视频搜索的高级参数。
 ///
+/// 默认参数显式携带 `per_page = 20` 与 `page = 1`，与客户端未指定时实际发送的值一致，
+/// 因此检查或序列化参数时看到的就是将要发送的请求。
+///
 /// # 示例
 ///
 /// ```
@@ -521,7 +941,7 @@ impl SearchImageParams {
 ///     .category(Category::Nature)
 ///     .safesearch(true);
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct SearchVideoParams {
     /// 搜索查询词
     pub query: Option<String>,
@@ -547,6 +967,24 @@ pub struct SearchVideoParams {
     pub lang: Option<Language>,
 }
 
+impl Default for SearchVideoParams {
+    fn default() -> Self {
+        Self {
+            query: None,
+            per_page: Some(20),
+            page: Some(1),
+            video_type: None,
+            category: None,
+            min_width: None,
+            min_height: None,
+            editors_choice: None,
+            safesearch: None,
+            order: None,
+            lang: None,
+        }
+    }
+}
+
 impl SearchVideoParams {
     /// 创建一个新的 SearchVideoParams 实例
     pub fn new() -> Self {
@@ -619,3 +1057,456 @@ impl SearchVideoParams {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client_for(server: &MockServer) -> Pixabay {
+        Pixabay::new("test_key".to_string())
+            .with_base_urls(
+                format!("{}/api/", server.uri()),
+                format!("{}/api/videos/", server.uri()),
+            )
+            .unwrap()
+    }
+
+    async fn search_with_status(status: u16, body: &str) -> PixabayError {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(status).set_body_string(body))
+            .mount(&server)
+            .await;
+
+        client_for(&server)
+            .search_images("flowers", None, None)
+            .await
+            .unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn test_query_too_long_is_rejected_locally() {
+        let client = Pixabay::new("test_key".to_string());
+        let long_query = "a".repeat(101);
+
+        let result = client.search_images(&long_query, None, None).await;
+        assert!(matches!(result, Err(PixabayError::QueryTooLong)));
+
+        let params = SearchVideoParams::new().query(long_query);
+        let result = client.search_videos_advanced(params).await;
+        assert!(matches!(result, Err(PixabayError::QueryTooLong)));
+    }
+
+    #[tokio::test]
+    async fn test_query_length_counts_cjk_characters() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"total": 0, "totalHits": 0, "hits": []}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = client_for(&server);
+
+        // 100 个汉字占 300 字节，仍在 100 字符的上限内
+        let at_limit = "山".repeat(100);
+        client.search_images(&at_limit, None, None).await.unwrap();
+
+        let over_limit = "山".repeat(101);
+        let result = client.search_images(&over_limit, None, None).await;
+        assert!(matches!(result, Err(PixabayError::QueryTooLong)));
+    }
+
+    #[tokio::test]
+    async fn test_bad_request_mapping() {
+        let err = search_with_status(400, "[ERROR 400] \"per_page\" is out of valid range.").await;
+        assert!(matches!(err, PixabayError::BadRequest(msg) if msg.contains("per_page")));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_api_key_mapping() {
+        let err = search_with_status(400, "[ERROR 400] Invalid or missing API key").await;
+        assert!(matches!(err, PixabayError::InvalidApiKey));
+
+        let err = search_with_status(401, "").await;
+        assert!(matches!(err, PixabayError::InvalidApiKey));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_mapping() {
+        let err = search_with_status(429, "").await;
+        assert!(matches!(err, PixabayError::RateLimitExceeded));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_recorded_from_response_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-RateLimit-Limit", "100")
+                    .insert_header("X-RateLimit-Remaining", "42")
+                    .insert_header("X-RateLimit-Reset", "17")
+                    .set_body_json(serde_json::json!({"total": 0, "totalHits": 0, "hits": []})),
+            )
+            .mount(&server)
+            .await;
+
+        let client = client_for(&server);
+        assert_eq!(client.rate_limit(), None);
+
+        client.search_images("flowers", None, None).await.unwrap();
+        assert_eq!(
+            client.clone().rate_limit(),
+            Some(RateLimitInfo {
+                limit: 100,
+                remaining: 42,
+                reset: 17,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_error_mapping() {
+        let err = search_with_status(500, "oops").await;
+        assert!(matches!(err, PixabayError::ApiError(msg) if msg.contains("500")));
+    }
+
+    #[tokio::test]
+    async fn test_strict_parse_reports_json_path() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"total": 1, "totalHits": "many", "hits": []}"#,
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        let lenient = client_for(&server);
+        assert!(lenient.search_images("flowers", None, None).await.is_err());
+
+        let strict = client_for(&server).with_strict_parse(true);
+        match strict.search_images("flowers", None, None).await {
+            Err(PixabayError::JsonError(e)) => assert!(e.to_string().starts_with("totalHits:")),
+            other => panic!("expected JsonError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_with_base_urls_rejects_invalid_scheme() {
+        let result = Pixabay::new("test_key".to_string())
+            .with_base_urls("htps://pixabay.com/api/", "https://pixabay.com/api/videos/");
+        assert!(matches!(result, Err(PixabayError::InvalidBaseUrl(_))));
+
+        let result = Pixabay::new("test_key".to_string())
+            .with_base_urls("https://pixabay.com/api/", "not a url");
+        assert!(matches!(result, Err(PixabayError::UrlError(_))));
+    }
+
+    fn image_json(id: u64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "pageURL": format!("https://pixabay.com/photos/{id}/"),
+            "type": "photo",
+            "tags": "mountain, sky",
+            "previewURL": "https://cdn.pixabay.com/preview.jpg",
+            "previewWidth": 150,
+            "previewHeight": 100,
+            "webformatURL": "https://pixabay.com/webformat.jpg",
+            "webformatWidth": 640,
+            "webformatHeight": 427,
+            "largeImageURL": "https://pixabay.com/large.jpg",
+            "imageWidth": 1920,
+            "imageHeight": 1280,
+            "imageSize": 1000,
+            "views": 1,
+            "downloads": 1,
+            "likes": 1,
+            "comments": 0,
+            "user_id": 1,
+            "user": "tester",
+            "userImageURL": ""
+        })
+    }
+
+    #[tokio::test]
+    async fn test_collect_images_skips_overlapping_ids() {
+        let server = MockServer::start().await;
+        for (page, ids) in [("1", [1u64, 2, 3]), ("2", [3, 4, 5])] {
+            Mock::given(method("GET"))
+                .and(query_param("page", page))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "total": 6,
+                    "totalHits": 6,
+                    "hits": ids.iter().map(|id| image_json(*id)).collect::<Vec<_>>()
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let params = SearchImageParams::new().query("mountain").per_page(3);
+        let result = client_for(&server)
+            .collect_images(params, 10)
+            .await
+            .unwrap();
+
+        let ids: Vec<u64> = result.images.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+        assert_eq!(result.duplicates_skipped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_multibyte_query_round_trips() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("q", "山脉 🌋"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"total": 0, "totalHits": 0, "hits": []}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client_for(&server)
+            .search_images("山脉 🌋", None, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_long_cjk_query_round_trips() {
+        // 100 个字符（300 字节）的中文查询，含全角分隔符，应完整编码后发送
+        let query = "山脉火山，".repeat(20);
+        assert_eq!(query.chars().count(), 100);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("q", query.as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"total": 0, "totalHits": 0, "hits": []}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client_for(&server)
+            .search_images(&query, None, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_search_images_ext_sends_orientation() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("q", "mountains"))
+            .and(query_param("orientation", "vertical"))
+            .and(query_param("image_type", "photo"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"total": 0, "totalHits": 0, "hits": []}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client_for(&server)
+            .search_images_ext(
+                "mountains",
+                None,
+                None,
+                Some(Orientation::Vertical),
+                Some(ImageType::Photo),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_search_images_ordered_sends_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("q", "sunset"))
+            .and(query_param("order", "latest"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"total": 0, "totalHits": 0, "hits": []}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client_for(&server)
+            .search_images_ordered("sunset", None, None, Order::Latest)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_html_response_is_reported_as_non_json() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("accept", "application/json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw("<html><body>Proxy login</body></html>", "text/html"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let err = client_for(&server)
+            .search_images("flowers", None, None)
+            .await
+            .unwrap_err();
+        match err {
+            PixabayError::ApiError(msg) => {
+                assert!(msg.starts_with("non-JSON response"));
+                assert!(msg.contains("Proxy login"));
+            }
+            other => panic!("expected ApiError, got {other:?}"),
+        }
+    }
+
+    fn gzip(body: &[u8]) -> Vec<u8> {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_search_images_decodes_gzip_response() {
+        use wiremock::matchers::header_regex;
+
+        let server = MockServer::start().await;
+        let body = serde_json::to_vec(&serde_json::json!({
+            "total": 1,
+            "totalHits": 1,
+            "hits": [image_json(42)]
+        }))
+        .unwrap();
+        Mock::given(method("GET"))
+            .and(header_regex("accept-encoding", "gzip"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .set_body_raw(gzip(&body), "application/json"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let images = client_for(&server)
+            .search_images("flowers", None, None)
+            .await
+            .unwrap();
+        assert_eq!(images.hits[0].id, 42);
+    }
+
+    /// 记录经过的请求并附加 `x-request-id` 头的测试中间件
+    #[cfg(feature = "middleware")]
+    struct RecordingMiddleware {
+        seen: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[cfg(feature = "middleware")]
+    #[async_trait::async_trait]
+    impl reqwest_middleware::Middleware for RecordingMiddleware {
+        async fn handle(
+            &self,
+            mut req: reqwest::Request,
+            extensions: &mut http::Extensions,
+            next: reqwest_middleware::Next<'_>,
+        ) -> reqwest_middleware::Result<reqwest::Response> {
+            self.seen.lock().unwrap().push(req.url().path().to_string());
+            req.headers_mut()
+                .insert("x-request-id", "test-id".parse().unwrap());
+            next.run(req, extensions).await
+        }
+    }
+
+    #[cfg(feature = "middleware")]
+    #[tokio::test]
+    async fn test_middleware_observes_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("x-request-id", "test-id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total": 1,
+                "totalHits": 1,
+                "hits": [image_json(9)]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let http = reqwest_middleware::ClientBuilder::new(Client::new())
+            .with(RecordingMiddleware {
+                seen: std::sync::Arc::clone(&seen),
+            })
+            .build();
+        let client = Pixabay::with_middleware("test_key".to_string(), http)
+            .with_base_urls(
+                format!("{}/api/", server.uri()),
+                format!("{}/api/videos/", server.uri()),
+            )
+            .unwrap();
+
+        let images = client.search_images("flowers", None, None).await.unwrap();
+        assert_eq!(images.hits[0].id, 9);
+        assert_eq!(*seen.lock().unwrap(), vec!["/api/".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_get_images_preserves_order_in_one_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("id", "3,999,1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total": 2,
+                "totalHits": 2,
+                "hits": [image_json(1), image_json(3)]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Pixabay::new("test_key".to_string())
+            .with_base_urls(
+                format!("{}/api/", server.uri()),
+                format!("{}/api/videos/", server.uri()),
+            )
+            .unwrap();
+        let images = client.get_images(&[3, 999, 1]).await.unwrap();
+
+        assert_eq!(images.len(), 3);
+        assert_eq!(images[0].as_ref().unwrap().id, 3);
+        assert!(matches!(images[1], Err(PixabayError::ApiError(_))));
+        assert_eq!(images[2].as_ref().unwrap().id, 1);
+        assert!(client.get_images(&[]).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_default_params_carry_explicit_paging() {
+        let image = SearchImageParams::default();
+        assert_eq!(image.per_page, Some(20));
+        assert_eq!(image.page, Some(1));
+
+        let video = SearchVideoParams::new();
+        assert_eq!(video.per_page, Some(20));
+        assert_eq!(video.page, Some(1));
+    }
+}
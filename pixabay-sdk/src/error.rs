@@ -11,14 +11,39 @@ pub enum PixabayError {
     #[error("解析 URL 失败: {0}")]
     UrlError(#[from] url::ParseError),
 
+    #[error("无效的基础 URL: {0}")]
+    InvalidBaseUrl(String),
+
     #[error("API 错误: {0}")]
     ApiError(String),
 
+    #[error("查询字符串不能超过 100 个字符")]
+    QueryTooLong,
+
+    #[error("错误请求: {0}")]
+    BadRequest(String),
+
     #[error("超过速率限制")]
     RateLimitExceeded,
 
     #[error("无效的 API 密钥")]
     InvalidApiKey,
+
+    #[cfg(feature = "middleware")]
+    #[error("中间件错误: {0}")]
+    MiddlewareError(String),
+}
+
+#[cfg(feature = "middleware")]
+impl From<reqwest_middleware::Error> for PixabayError {
+    fn from(e: reqwest_middleware::Error) -> Self {
+        match e {
+            reqwest_middleware::Error::Reqwest(e) => PixabayError::RequestError(e),
+            reqwest_middleware::Error::Middleware(e) => {
+                PixabayError::MiddlewareError(e.to_string())
+            }
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, PixabayError>;
@@ -1,3 +1,5 @@
+#[cfg(feature = "blocking")]
+mod blocking;
 mod client;
 mod error;
 mod models;
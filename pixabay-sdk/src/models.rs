@@ -89,6 +89,51 @@ pub struct Image {
     pub user_image_url: String,
 }
 
+impl Image {
+    /// 将逗号分隔的 `tags` 拆分为去除首尾空白的标签列表，忽略空标签
+    pub fn tags_vec(&self) -> Vec<&str> {
+        split_tags(&self.tags)
+    }
+}
+
+/// Pixabay API 的速率限制状态，解析自最近一次响应的 `X-RateLimit-*` 响应头
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// 当前时间窗口内允许的请求总数（`X-RateLimit-Limit`）
+    pub limit: u32,
+    /// 当前时间窗口内剩余的请求数（`X-RateLimit-Remaining`）
+    pub remaining: u32,
+    /// 距离时间窗口重置的秒数，以收到响应时为准（`X-RateLimit-Reset`）
+    pub reset: u64,
+}
+
+impl RateLimitInfo {
+    /// 从响应头解析速率限制信息，任一响应头缺失或无法解析时返回 `None`
+    pub(crate) fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<Self> {
+        fn value<T: std::str::FromStr>(
+            headers: &reqwest::header::HeaderMap,
+            name: &str,
+        ) -> Option<T> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        }
+
+        Some(Self {
+            limit: value(headers, "x-ratelimit-limit")?,
+            remaining: value(headers, "x-ratelimit-remaining")?,
+            reset: value(headers, "x-ratelimit-reset")?,
+        })
+    }
+}
+
+/// 多页图片收集结果
+#[derive(Debug, Clone)]
+pub struct CollectResult {
+    /// 去重后的图片列表（按首次出现的顺序）
+    pub images: Vec<Image>,
+    /// 因 ID 重复而跳过的图片数
+    pub duplicates_skipped: usize,
+}
+
 /// 视频搜索响应
 ///
 /// 包含视频搜索结果的总数量和视频列表。
@@ -140,6 +185,21 @@ pub struct Video {
     pub user_image_url: String,
 }
 
+impl Video {
+    /// 将逗号分隔的 `tags` 拆分为去除首尾空白的标签列表，忽略空标签
+    pub fn tags_vec(&self) -> Vec<&str> {
+        split_tags(&self.tags)
+    }
+}
+
+/// 拆分 Pixabay 以逗号连接的标签字符串
+fn split_tags(tags: &str) -> Vec<&str> {
+    tags.split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
 /// 视频文件集合
 ///
 /// 包含不同分辨率的视频文件链接。
@@ -155,6 +215,32 @@ pub struct VideoFiles {
     pub tiny: Option<VideoFile>,
 }
 
+impl VideoFiles {
+    /// 按 large、medium、small、tiny 的顺序遍历可用的视频文件
+    ///
+    /// Pixabay 对不存在的尺寸可能返回 URL 为空、宽度为 0 的占位项，这些项会被跳过
+    pub fn iter(&self) -> impl Iterator<Item = &VideoFile> {
+        [&self.large, &self.medium, &self.small, &self.tiny]
+            .into_iter()
+            .flatten()
+            .filter(|file| !file.url.is_empty() && file.width > 0)
+    }
+
+    /// 宽度不小于 `width` 的视频文件中宽度最小的一个
+    ///
+    /// 按实际宽度而不是尺寸名称比较，因为 `large`/`medium` 等名称与分辨率并不固定对应
+    pub fn smallest_at_least(&self, width: u32) -> Option<&VideoFile> {
+        self.iter()
+            .filter(|file| file.width >= width)
+            .min_by_key(|file| file.width)
+    }
+
+    /// 宽度最大的视频文件
+    pub fn largest(&self) -> Option<&VideoFile> {
+        self.iter().max_by_key(|file| file.width)
+    }
+}
+
 /// 单个视频文件信息
 ///
 /// 包含视频文件的具体信息，包括 URL、分辨率、文件大小和预览图。
@@ -320,6 +406,64 @@ impl std::fmt::Display for Category {
     }
 }
 
+/// 图片颜色筛选枚举
+///
+/// 用于 [`SearchImageParams::with_colors`](crate::SearchImageParams::with_colors)，多个颜色以逗号分隔传给 API。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Color {
+    /// 灰度
+    Grayscale,
+    /// 透明背景
+    Transparent,
+    /// 红色
+    Red,
+    /// 橙色
+    Orange,
+    /// 黄色
+    Yellow,
+    /// 绿色
+    Green,
+    /// 青绿色
+    Turquoise,
+    /// 蓝色
+    Blue,
+    /// 淡紫色
+    Lilac,
+    /// 粉色
+    Pink,
+    /// 白色
+    White,
+    /// 灰色
+    Gray,
+    /// 黑色
+    Black,
+    /// 棕色
+    Brown,
+}
+
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Color::Grayscale => "grayscale",
+            Color::Transparent => "transparent",
+            Color::Red => "red",
+            Color::Orange => "orange",
+            Color::Yellow => "yellow",
+            Color::Green => "green",
+            Color::Turquoise => "turquoise",
+            Color::Blue => "blue",
+            Color::Lilac => "lilac",
+            Color::Pink => "pink",
+            Color::White => "white",
+            Color::Gray => "gray",
+            Color::Black => "black",
+            Color::Brown => "brown",
+        };
+        f.write_str(s)
+    }
+}
+
 /// 结果排序枚举
 ///
 /// 用于设置搜索结果的排序方式。
@@ -435,3 +579,51 @@ impl std::fmt::Display for Language {
         f.write_str(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(width: u32) -> Option<VideoFile> {
+        Some(VideoFile {
+            url: format!("https://cdn.pixabay.com/video/{width}.mp4"),
+            width,
+            height: width * 9 / 16,
+            size: width as u64 * 1000,
+            thumbnail: String::new(),
+        })
+    }
+
+    #[test]
+    fn test_video_files_select_by_width() {
+        // 名称与分辨率不一致：large 反而不是最大的，tiny 是空占位项
+        let files = VideoFiles {
+            large: file(1920),
+            medium: file(3840),
+            small: file(960),
+            tiny: Some(VideoFile {
+                url: String::new(),
+                width: 0,
+                height: 0,
+                size: 0,
+                thumbnail: String::new(),
+            }),
+        };
+
+        assert_eq!(files.iter().count(), 3);
+        assert_eq!(files.largest().unwrap().width, 3840);
+        assert_eq!(files.smallest_at_least(1280).unwrap().width, 1920);
+        assert_eq!(files.smallest_at_least(0).unwrap().width, 960);
+        assert!(files.smallest_at_least(4096).is_none());
+    }
+
+    #[test]
+    fn test_split_tags_trims_whitespace() {
+        assert_eq!(
+            split_tags(" sunset ,beach,  sea  water ,"),
+            vec!["sunset", "beach", "sea  water"]
+        );
+        assert!(split_tags("").is_empty());
+        assert!(split_tags(" , ").is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+//! Fixture replay for the integration tests.
+//!
+//! The fixture format, the `synthetic` marker and how to re-record are
+//! documented in the shared `http-fixtures` crate.
+#![allow(dead_code)]
+
+use http_fixtures::{Fixtures, LiveAuth};
+use pixabay_sdk::Pixabay;
+use wiremock::MockServer;
+
+/// The fixtures in `tests/fixtures/`, recorded from the live Pixabay API.
+pub fn fixtures() -> Fixtures {
+    Fixtures::new(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"),
+        "https://pixabay.com",
+        "PIXABAY_API_KEY",
+        LiveAuth::Query("key"),
+    )
+}
+
+/// Starts a mock server for the fixture, re-recording it first with `record-fixtures`.
+pub async fn replay(name: &str) -> MockServer {
+    fixtures()
+        .replay(name, cfg!(feature = "record-fixtures"))
+        .await
+}
+
+/// Creates a client pointing at the fixture's mock server.
+pub fn client(server: &MockServer) -> Pixabay {
+    Pixabay::new("fixture_key".to_string())
+        .with_base_urls(
+            format!("{}/api/", server.uri()),
+            format!("{}/api/videos/", server.uri()),
+        )
+        .expect("Invalid mock server URL")
+}
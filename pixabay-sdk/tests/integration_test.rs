@@ -1,3 +1,5 @@
+mod common;
+
 use dotenvy::dotenv;
 use pixabay_sdk::{
     Category, ImageType, Order, Orientation, Pixabay, PixabayError, SearchImageParams,
@@ -32,6 +34,28 @@ async fn test_simple_image_search() {
     );
 }
 
+#[tokio::test]
+async fn test_simple_image_search_fixture() {
+    let server = common::replay("search_images").await;
+    let client = common::client(&server);
+
+    let result = client
+        .search_images("yellow flowers", Some(5), Some(1))
+        .await;
+    assert!(result.is_ok());
+
+    let response = result.unwrap();
+    assert!(response.total > 0);
+    assert!(response.total_hits > 0);
+    assert!(!response.hits.is_empty());
+    assert!(response.hits.len() <= 5);
+    println!(
+        "Found {} total images, showing {}",
+        response.total_hits,
+        response.hits.len()
+    );
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_advanced_image_search() {
@@ -99,6 +123,26 @@ async fn test_simple_video_search() {
     );
 }
 
+#[tokio::test]
+async fn test_simple_video_search_fixture() {
+    let server = common::replay("search_videos").await;
+    let client = common::client(&server);
+
+    let result = client.search_videos("ocean", Some(5), Some(1)).await;
+    assert!(result.is_ok());
+
+    let response = result.unwrap();
+    assert!(response.total > 0);
+    assert!(response.total_hits > 0);
+    assert!(!response.hits.is_empty());
+    assert!(response.hits.len() <= 5);
+    println!(
+        "Found {} total videos, showing {}",
+        response.total_hits,
+        response.hits.len()
+    );
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_advanced_video_search() {